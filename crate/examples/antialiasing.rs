@@ -0,0 +1,61 @@
+extern crate gdl;
+use gdl::elements::*;
+use gdl::raster::Quality;
+use gdl::text::draw_text;
+use gdl::{new_with_background, Rgb};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let white = Rgb {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+    let black = Rgb { r: 0, g: 0, b: 0 };
+    let coral = Rgb {
+        r: 240,
+        g: 110,
+        b: 90,
+    };
+
+    let mut img = new_with_background(1000, 500, &black);
+
+    // Draw the same shapes on each half of the image, with fast rendering on the left
+    // and anti-aliased rendering on the right.
+    for (i, quality) in [Quality::Fast, Quality::AntiAliased].iter().enumerate() {
+        let offset = i as f32 * 500.0;
+
+        draw_polygon(
+            &mut img,
+            &[
+                (offset + 40.0, 420.0),
+                (offset + 220.0, 60.0),
+                (offset + 260.0, 380.0),
+            ],
+            &coral,
+            *quality,
+        );
+        draw_circle(&mut img, (offset + 360.0, 140.0), 70.5, &white, *quality);
+        draw_line(
+            &mut img,
+            (offset + 280.0, 460.0),
+            (offset + 470.0, 250.0),
+            3.0,
+            &white,
+            *quality,
+        );
+    }
+
+    draw_text(&mut img, "Fast", 20, 30, "Roboto-Regular", 30.0, &white);
+    draw_text(&mut img, "AntiAliased", 520, 30, "Roboto-Regular", 30.0, &white);
+
+    gdl::helpers::save_image(img, "example_output/antialiasing.png");
+
+    println!(
+        "Took {} seconds to create image.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output image in examples/example_output");
+}
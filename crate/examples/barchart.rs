@@ -15,7 +15,7 @@ fn main() {
     let mut img = new_with_background(1500, 1500, &black);
 
     // Insert the data into a vec
-    let data: Vec<u16> = vec![5, 10, 20, 40];
+    let data: Vec<f64> = vec![5.0, 12.5, 20.0, 37.5];
 
     // Create labels for the barchart
     let labels: Vec<String> = vec![
@@ -32,8 +32,8 @@ fn main() {
         b: 200,
     };
 
-    // Create a barchart struct
-    let barchart = Chart::new(
+    // Create a barchart struct, placed in the top half of the image
+    let mut barchart = Chart::new(
         "Earnings for 2019/2020".to_string(),
         blue,
         data,
@@ -41,8 +41,15 @@ fn main() {
         1500,
         1500,
     );
+    barchart.set_bounds(100, 100, 1300, 600);
+    barchart.y_axis.title = Some("Earnings".to_string());
 
     diagrams::draw_vertical_gradient_barchart(&mut img, &barchart, "lemongrass");
+
+    // The same data as horizontal bars in the bottom half, with the title beneath them
+    barchart.set_bounds(100, 800, 1300, 600);
+    barchart.style.title_position = diagrams::TitlePosition::Bottom;
+    diagrams::draw_horizontal_barchart(&mut img, &barchart);
     gdl::helpers::save_image(img, "example_output/barchart.png");
 
     println!(
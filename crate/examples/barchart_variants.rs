@@ -0,0 +1,86 @@
+extern crate gdl;
+use gdl::diagrams::{BarMode, BarOptions, Chart, ValueLabels};
+use gdl::{diagrams, new_with_background, Rgb};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let black = Rgb { r: 0, g: 0, b: 0 };
+    let mut img = new_with_background(1600, 1600, &black);
+
+    let labels: Vec<String> = vec![
+        "Q1".to_string(),
+        "Q2".to_string(),
+        "Q3".to_string(),
+        "Q4".to_string(),
+    ];
+    let palette = diagrams::palette("default");
+
+    let mut chart = Chart::new(
+        "Sales by region".to_string(),
+        palette[0].clone(),
+        vec![12.0, 18.0, 24.0, 21.5],
+        labels,
+        800,
+        800,
+    );
+    chart.series[0].name = "North".to_string();
+    chart.add_series("South", vec![8.0, 11.0, 9.5, 16.0], palette[1].clone());
+    chart.add_series("West", vec![5.0, 7.5, 12.0, 10.0], palette[2].clone());
+
+    // Grouped bars with rounded corners and values above them, in the top left
+    chart.set_bounds(0, 0, 800, 800);
+    let options = BarOptions {
+        corner_radius: 6.0,
+        value_labels: ValueLabels::Outside,
+        ..BarOptions::new(BarMode::Grouped)
+    };
+    diagrams::draw_barchart_with_options(&mut img, &chart, &options);
+
+    // Horizontal stacked bars with values inside each segment
+    chart.set_bounds(800, 0, 800, 800);
+    chart.title = "Stacked".to_string();
+    let options = BarOptions {
+        horizontal: true,
+        value_labels: ValueLabels::Inside,
+        ..BarOptions::new(BarMode::Stacked)
+    };
+    diagrams::draw_barchart_with_options(&mut img, &chart, &options);
+
+    // Each region's share of sales, as 100% stacked bars
+    chart.set_bounds(0, 800, 800, 800);
+    chart.title = "Share of sales".to_string();
+    chart.y_axis.title = Some("%".to_string());
+    let options = BarOptions {
+        value_labels: ValueLabels::Inside,
+        ..BarOptions::new(BarMode::PercentStacked)
+    };
+    diagrams::draw_barchart_with_options(&mut img, &chart, &options);
+
+    // A single series of rounded bars, with the best quarter highlighted in a colour of its own
+    let mut best = Chart::new(
+        "Best quarter".to_string(),
+        palette[0].clone(),
+        vec![25.0, 36.5, 45.5, 47.5],
+        chart.labels.clone(),
+        800,
+        800,
+    );
+    best.set_bounds(800, 800, 800, 800);
+    best.series[0].colors = vec![None, None, None, Some(palette[5].clone())];
+    let options = BarOptions {
+        corner_radius: 16.0,
+        value_labels: ValueLabels::Outside,
+        ..BarOptions::default()
+    };
+    diagrams::draw_barchart_with_options(&mut img, &best, &options);
+
+    gdl::helpers::save_image(img, "example_output/barchart_variants.png");
+
+    println!(
+        "Took {} seconds to create image.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output image in examples/example_output");
+}
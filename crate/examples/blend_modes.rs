@@ -0,0 +1,60 @@
+extern crate gdl;
+use gdl::blend::BlendMode;
+use gdl::elements::*;
+use gdl::text::draw_text_with_blend;
+use gdl::Rgb;
+use image::GenericImageView;
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let photo = gdl::helpers::open_image("examples/input_images/city_square.jpg");
+    let modes = [
+        ("multiply", BlendMode::Multiply),
+        ("screen", BlendMode::Screen),
+        ("overlay", BlendMode::Overlay),
+        ("soft_light", BlendMode::SoftLight),
+        ("color", BlendMode::Color),
+    ];
+
+    let coral = Rgb {
+        r: 240,
+        g: 110,
+        b: 90,
+    };
+    let white = Rgb {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+
+    for (name, mode) in modes.iter() {
+        // Lay a gradient over the photo with each blend mode.
+        let mut img = photo.clone();
+        draw_gradient_overlay(&mut img, "pinkblue", *mode, 0.9);
+        gdl::helpers::save_image(img, &format!("example_output/gradient_{}.png", name));
+
+        // Tint the photo with a solid colour, and blend the heading into it too.
+        let mut img = photo.clone();
+        draw_color_overlay(&mut img, &coral, *mode, 0.8);
+        let height = img.height();
+        draw_text_with_blend(
+            &mut img,
+            "Stockholm",
+            60,
+            height / 2,
+            "BebasKai",
+            140.0,
+            &white,
+            BlendMode::Overlay,
+        );
+        gdl::helpers::save_image(img, &format!("example_output/tint_{}.png", name));
+    }
+
+    println!(
+        "Took {} seconds to create images.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output images in examples/example_output");
+}
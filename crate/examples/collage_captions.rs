@@ -0,0 +1,108 @@
+extern crate gdl;
+use gdl::collage::{
+    create_collage, four_grid_with_caption, moodboard, six_grid_text, Caption, CaptionBand,
+    CaptionPosition, CollageLayout,
+};
+use gdl::filters::{apply_filters, Filter};
+use gdl::text::TextAlign;
+use gdl::{Rgb, Rgba};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let city = gdl::helpers::open_image("examples/input_images/city_square.jpg");
+    let drive = gdl::helpers::open_image("examples/input_images/drive.jpg");
+    let photos: Vec<_> = (0..6)
+        .map(|i| {
+            let mut img = if i % 2 == 0 {
+                city.clone()
+            } else {
+                drive.clone()
+            };
+            apply_filters(&mut img, &[Filter::HueRotate(i as f32 * 60.0)]);
+            img
+        })
+        .collect();
+
+    // The fixed layouts keep their original caption styles.
+    let img = moodboard(
+        &photos[0],
+        &photos[1],
+        &photos[2],
+        &photos[3],
+        "Weekend In The City",
+        1200,
+        900,
+    );
+    gdl::helpers::save_image(img, "example_output/caption_moodboard.png");
+
+    let img = six_grid_text(
+        &photos[0],
+        &photos[1],
+        &photos[2],
+        &photos[3],
+        &photos[4],
+        &photos[5],
+        "Six Sights Worth Seeing",
+        1200,
+        900,
+    );
+    gdl::helpers::save_image(img, "example_output/caption_six_grid.png");
+
+    // A frosted glass band, centred text and a different font.
+    let caption = Caption {
+        font: "BebasKai".to_string(),
+        color: Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        },
+        band: CaptionBand::Glass {
+            blur: 12.0,
+            tint: Rgba::new(0, 0, 0, 90),
+        },
+        position: CaptionPosition::Centre(0.3),
+        align: TextAlign::Centre,
+        ..Caption::new("Weekend In The City")
+    };
+    let img = four_grid_with_caption(
+        &photos[0], &photos[1], &photos[2], &photos[3], &caption, 1200, 900,
+    );
+    gdl::helpers::save_image(img, "example_output/caption_glass.png");
+
+    // A caption over each photo, with a gradient band and a translucent band.
+    let mut layout = CollageLayout::grid(3, 2);
+    layout.gutter = 10;
+    layout.margin = 10;
+    layout.background = Rgba::new(255, 255, 255, 255);
+    layout.captions = (0..6)
+        .map(|i| {
+            let band = if i % 2 == 0 {
+                CaptionBand::Gradient("pinkblue".to_string())
+            } else {
+                CaptionBand::Solid(Rgba::new(0, 0, 0, 140))
+            };
+            Some(Caption {
+                color: Rgb {
+                    r: 255,
+                    g: 255,
+                    b: 255,
+                },
+                max_font_size: 36.0,
+                band,
+                position: CaptionPosition::Bottom(0.2),
+                padding: 12,
+                ..Caption::new(&format!("Stop number {}", i + 1))
+            })
+        })
+        .collect();
+    let img = create_collage(&photos, &layout, 1200, 800);
+    gdl::helpers::save_image(img, "example_output/caption_cells.png");
+
+    println!(
+        "Took {} seconds to create images.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output images in examples/example_output");
+}
@@ -0,0 +1,69 @@
+extern crate gdl;
+use gdl::collage::{create_collage, Cell, CollageLayout};
+use gdl::filters::{apply_filters, Filter};
+use gdl::resize::Fit;
+use gdl::Rgba;
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let city = gdl::helpers::open_image("examples/input_images/city_square.jpg");
+    let drive = gdl::helpers::open_image("examples/input_images/drive.jpg");
+
+    // Tint copies of the photos, so that each cell of the collage is easy to tell apart.
+    let photos: Vec<_> = [0.0, 60.0, 120.0, 180.0, 240.0]
+        .iter()
+        .enumerate()
+        .map(|(i, &degrees)| {
+            let mut img = if i % 2 == 0 {
+                city.clone()
+            } else {
+                drive.clone()
+            };
+            apply_filters(&mut img, &[Filter::HueRotate(degrees)]);
+            img
+        })
+        .collect();
+
+    // A grid where the first photo spans both rows, with white gutters and rounded corners.
+    let mut layout = CollageLayout::grid(3, 2);
+    layout.cells = vec![
+        Cell::span(0, 0, 1, 2),
+        Cell::new(1, 0),
+        Cell::new(2, 0),
+        Cell::new(1, 1),
+        Cell::new(2, 1),
+    ];
+    layout.gutter = 16;
+    layout.margin = 24;
+    layout.background = Rgba::new(255, 255, 255, 255);
+    layout.corner_radius = 20.0;
+    let img = create_collage(&photos, &layout, 1200, 800);
+    gdl::helpers::save_image(img, "example_output/collage_spans.png");
+
+    // A strip of photos with columns of different widths.
+    let mut layout = CollageLayout::with_cells(vec![1.0, 2.0, 1.0, 2.0, 1.0], vec![1.0], vec![]);
+    layout.gutter = 8;
+    layout.margin = 8;
+    layout.background = Rgba::new(30, 30, 30, 255);
+    let img = create_collage(&photos, &layout, 1500, 400);
+    gdl::helpers::save_image(img, "example_output/collage_strip.png");
+
+    // The same strip, keeping the left edge of the first photo and the right edge of the second
+    // in frame when cropping.
+    layout.focal_points = vec![Some((0.0, 0.5)), Some((1.0, 0.5))];
+    let img = create_collage(&photos, &layout, 1500, 400);
+    gdl::helpers::save_image(img, "example_output/collage_focal_points.png");
+
+    // The same strip, letterboxing the photos instead of cropping them.
+    layout.fit = Fit::Contain(Rgba::new(30, 30, 30, 255));
+    let img = create_collage(&photos, &layout, 1500, 400);
+    gdl::helpers::save_image(img, "example_output/collage_contain.png");
+
+    println!(
+        "Took {} seconds to create images.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output images in examples/example_output");
+}
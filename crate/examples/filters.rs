@@ -0,0 +1,72 @@
+extern crate gdl;
+use gdl::blend::composite;
+use gdl::filters::{apply_filters, Filter};
+use gdl::text::draw_text;
+use gdl::{new_with_background, Rgb};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let black = Rgb { r: 0, g: 0, b: 0 };
+    let white = Rgb {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+    let navy = Rgb {
+        r: 20,
+        g: 30,
+        b: 90,
+    };
+    let peach = Rgb {
+        r: 255,
+        g: 200,
+        b: 150,
+    };
+
+    let photo = gdl::helpers::open_image("examples/input_images/city_square.jpg");
+    let thumbnail = photo.thumbnail_exact(300, 200);
+
+    // Apply each pipeline to a copy of the photo, and lay the results out in a grid.
+    let pipelines: Vec<(&str, Vec<Filter>)> = vec![
+        ("Original", vec![]),
+        ("Blur", vec![Filter::GaussianBlur(4.0)]),
+        ("Box blur", vec![Filter::BoxBlur(6)]),
+        (
+            "Darken",
+            vec![Filter::Brightness(-0.15), Filter::Contrast(0.1)],
+        ),
+        ("Saturate", vec![Filter::Saturation(0.8)]),
+        ("Hue", vec![Filter::HueRotate(120.0)]),
+        ("Grayscale", vec![Filter::Grayscale]),
+        ("Sepia", vec![Filter::Sepia, Filter::Vignette(0.7)]),
+        ("Duotone", vec![Filter::Duotone(navy, peach.clone())]),
+        ("Tint", vec![Filter::Tint(peach, 0.4)]),
+    ];
+
+    let mut img = new_with_background(1640, 540, &black);
+    for (i, (name, filters)) in pipelines.iter().enumerate() {
+        let (x, y) = (20 + (i as i32 % 5) * 325, 20 + (i as i32 / 5) * 260);
+        let mut treated = thumbnail.clone();
+        apply_filters(&mut treated, filters);
+        composite(&mut img, &treated, x, y, 1.0);
+        draw_text(
+            &mut img,
+            name,
+            x as u32,
+            y as u32 + 210,
+            "Roboto-Regular",
+            24.0,
+            &white,
+        );
+    }
+
+    gdl::helpers::save_image(img, "example_output/filters.png");
+
+    println!(
+        "Took {} seconds to create image.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output image in examples/example_output");
+}
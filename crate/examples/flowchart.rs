@@ -0,0 +1,70 @@
+extern crate gdl;
+use gdl::flowchart::{draw_flowchart, Direction, Flowchart, NodeShape, Routing};
+use gdl::{new_with_background, Rgb, Rgba};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let background = Rgb {
+        r: 20,
+        g: 24,
+        b: 28,
+    };
+    let mut img = new_with_background(1600, 1500, &background);
+
+    // A top-down process, laid out automatically, with a decision which loops back
+    let mut process = Flowchart::new(Direction::TopDown);
+    process.add_node("upload", "Upload photo", NodeShape::Ellipse);
+    let check = process.add_node("check", "Large enough?", NodeShape::Diamond);
+    check.width = 300.0;
+    check.height = 140.0;
+    process.add_node("upscale", "Ask for a larger photo", NodeShape::Rounded);
+    process.add_node("crop", "Smart crop to each format", NodeShape::Rounded);
+    process.add_node("caption", "Add caption", NodeShape::Rect);
+    process.add_node("publish", "Publish", NodeShape::Ellipse);
+    process.add_edge("upload", "check", None);
+    process.add_edge("check", "crop", Some("Yes"));
+    process.add_edge("check", "upscale", Some("No"));
+    process.add_edge("upscale", "upload", None).style.routing = Routing::Curved;
+    process.add_edge("crop", "caption", None);
+    process.add_edge("caption", "publish", None);
+    process.layout();
+    let (width, _) = process.size();
+    draw_flowchart(&mut img, &process, ((1600.0 - width) / 2.0) as u32, 40);
+
+    // A left-to-right pipeline, with curved connectors and its own colours
+    let mut pipeline = Flowchart::new(Direction::LeftRight);
+    pipeline.node_style.fill = Rgba::new(22, 160, 133, 255);
+    pipeline.node_style.border_width = 0.0;
+    pipeline.edge_style.routing = Routing::Curved;
+    pipeline.edge_style.color = Rgba::new(22, 160, 133, 255);
+    for (id, label) in &[
+        ("data", "Data"),
+        ("bars", "Bar chart"),
+        ("lines", "Line chart"),
+        ("table", "Table"),
+        ("post", "Post"),
+    ] {
+        let node = pipeline.add_node(id, label, NodeShape::Rounded);
+        node.width = 200.0;
+        node.height = 80.0;
+    }
+    pipeline.add_edge("data", "bars", None);
+    pipeline.add_edge("data", "lines", None);
+    pipeline.add_edge("data", "table", Some("CSV"));
+    pipeline.add_edge("bars", "post", None);
+    pipeline.add_edge("lines", "post", None);
+    pipeline.add_edge("table", "post", None);
+    pipeline.layout();
+    let (width, _) = pipeline.size();
+    draw_flowchart(&mut img, &pipeline, ((1600.0 - width) / 2.0) as u32, 1120);
+
+    gdl::helpers::save_image(img, "example_output/flowchart.png");
+
+    println!(
+        "Took {} seconds to create image.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output image in examples/example_output");
+}
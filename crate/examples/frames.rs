@@ -0,0 +1,71 @@
+extern crate gdl;
+use gdl::blend::composite;
+use gdl::collage::{
+    film_strip_collage, framed_collage, polaroid_collage, CollageLayout, FrameOptions,
+};
+use gdl::filters::{apply_filters, Filter};
+use gdl::frames::FrameStyle;
+use gdl::{new_with_background, Rgb, Rgba};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let city = gdl::helpers::open_image("examples/input_images/city_square.jpg");
+    let drive = gdl::helpers::open_image("examples/input_images/drive.jpg");
+    let photos: Vec<_> = (0..6)
+        .map(|i| {
+            let mut img = if i % 2 == 0 {
+                city.clone()
+            } else {
+                drive.clone()
+            };
+            apply_filters(&mut img, &[Filter::HueRotate(i as f32 * 60.0)]);
+            img
+        })
+        .collect();
+
+    let cork = Rgb {
+        r: 196,
+        g: 160,
+        b: 118,
+    };
+
+    // A scattered stack of polaroids on a corkboard.
+    let captions = [
+        "Old town",
+        "The tunnel",
+        "Graffiti",
+        "Night drive",
+        "Side streets",
+        "Home",
+    ];
+    let collage = polaroid_collage(&photos, &captions, 7, 1200, 800);
+    let mut img = new_with_background(1200, 800, &cork);
+    composite(&mut img, &collage, 0, 0, 1.0);
+    gdl::helpers::save_image(img, "example_output/frames_polaroid.png");
+
+    // A strip of film.
+    let collage = film_strip_collage(&photos[..4], 1200, 300);
+    let mut img = new_with_background(1200, 300, &cork);
+    composite(&mut img, &collage, 0, 0, 1.0);
+    gdl::helpers::save_image(img, "example_output/frames_film_strip.png");
+
+    // Plain white borders with a slight tilt, on a grid.
+    let mut layout = CollageLayout::grid(3, 2);
+    layout.background = Rgba::new(230, 234, 238, 255);
+    layout.margin = 20;
+    let frame = FrameOptions {
+        max_rotation: 3.0,
+        seed: 42,
+        ..FrameOptions::new(FrameStyle::Border(0.04, Rgba::new(255, 255, 255, 255)))
+    };
+    let img = framed_collage(&photos, &layout, &frame, 1200, 800);
+    gdl::helpers::save_image(img, "example_output/frames_borders.png");
+
+    println!(
+        "Took {} seconds to create images.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output images in examples/example_output");
+}
@@ -27,9 +27,9 @@ fn main() {
     let mut img = new_with_background(width, height, &black);
 
     // Draw gradients
-    draw_preset_rect_gradient(&mut img, 300, 300, 30, 30, "pinkblue");
-    draw_preset_rect_gradient(&mut img, 300, 300, 330, 30, "pink_pastel");
-    draw_preset_rect_gradient(&mut img, 300, 300, 630, 30, "lemongrass");
+    draw_preset_rect_gradient(&mut img, 300, 300, 30, 30, "pinkblue", 1.0);
+    draw_preset_rect_gradient(&mut img, 300, 300, 330, 30, "pink_pastel", 1.0);
+    draw_preset_rect_gradient(&mut img, 300, 300, 630, 30, "lemongrass", 1.0);
 
     // Draw text
     draw_text(&mut img, "pink_blue", 90, 350, "Roboto-Light", 60.0, &white);
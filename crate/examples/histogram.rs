@@ -0,0 +1,79 @@
+extern crate gdl;
+use gdl::diagrams::{Binning, Chart, HistogramOptions};
+use gdl::{diagrams, new_with_background, Rgb};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let black = Rgb { r: 0, g: 0, b: 0 };
+    let mut img = new_with_background(1600, 1600, &black);
+    let palette = diagrams::palette("default");
+
+    // Response times in milliseconds: mostly around 120ms, with a few slow outliers
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut samples: Vec<f64> = (0..500)
+        .map(|_| {
+            // Box-Muller transform, for normally distributed samples
+            let (u, v): (f64, f64) = (rng.gen_range(1e-9..1.0), rng.gen());
+            120.0 + 18.0 * (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+        })
+        .collect();
+    samples.extend(&[210.0, 225.0, 240.0]);
+
+    let mut chart = Chart::new(
+        "Sturges".to_string(),
+        palette[0].clone(),
+        samples,
+        vec![],
+        800,
+        800,
+    );
+    chart.x_axis.title = Some("Response time (ms)".to_string());
+    chart.y_axis.title = Some("Requests".to_string());
+
+    // Sturges' rule, in the top left
+    chart.set_bounds(0, 0, 800, 800);
+    diagrams::draw_histogram(&mut img, &chart);
+
+    // The Freedman-Diaconis rule, in the top right
+    chart.set_bounds(800, 0, 800, 800);
+    chart.title = "Freedman-Diaconis".to_string();
+    chart.series[0].color = palette[1].clone();
+    let options = HistogramOptions {
+        binning: Binning::FreedmanDiaconis,
+        ..HistogramOptions::default()
+    };
+    diagrams::draw_histogram_with_options(&mut img, &chart, &options);
+
+    // Bins 10ms wide, in the bottom left
+    chart.set_bounds(0, 800, 800, 800);
+    chart.title = "10ms bins".to_string();
+    chart.series[0].color = palette[2].clone();
+    let options = HistogramOptions {
+        binning: Binning::Width(10.0),
+        ..HistogramOptions::default()
+    };
+    diagrams::draw_histogram_with_options(&mut img, &chart, &options);
+
+    // Eight bins, as densities, in the bottom right
+    chart.set_bounds(800, 800, 800, 800);
+    chart.title = "Density".to_string();
+    chart.series[0].color = palette[3].clone();
+    chart.y_axis.title = Some("Density".to_string());
+    let options = HistogramOptions {
+        binning: Binning::Count(8),
+        density: true,
+    };
+    diagrams::draw_histogram_with_options(&mut img, &chart, &options);
+
+    gdl::helpers::save_image(img, "example_output/histogram.png");
+
+    println!(
+        "Took {} seconds to create image.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output image in examples/example_output");
+}
@@ -0,0 +1,114 @@
+extern crate gdl;
+use gdl::infographics::{
+    draw_comparison, draw_horizontal_timeline, draw_steps, draw_vertical_timeline, Comparison,
+    InfographicStyle, Mark, Step, TimelineItem,
+};
+use gdl::raster::{self, Contour, Quality};
+use gdl::{new_with_background, Rgb, Rgba};
+use image::DynamicImage;
+use std::time::Instant;
+
+// Draw a simple white icon from some shapes, on a transparent background
+fn icon(contours: &[Contour]) -> DynamicImage {
+    let mut icon = DynamicImage::new_rgba8(100, 100);
+    let white = Rgba::new(255, 255, 255, 230);
+    raster::fill_contours(&mut icon, contours, white.into(), Quality::AntiAliased);
+    icon
+}
+
+fn main() {
+    let start = Instant::now();
+
+    let background = Rgb {
+        r: 20,
+        g: 24,
+        b: 28,
+    };
+    let mut img = new_with_background(1800, 1900, &background);
+
+    // A roadmap across the top, with its line and markers in a gradient
+    let roadmap = vec![
+        TimelineItem::new("Q1", "Charts", "Bar, line and pie charts"),
+        TimelineItem::new("Q2", "Collages", "Grids, masonry and captions"),
+        TimelineItem::new("Q3", "Infographics", "Timelines, steps and comparisons"),
+        TimelineItem::new("Q4", "Templates", "Ready-made designs for every format"),
+        TimelineItem::new("2025", "Animation", "Short looping videos"),
+    ];
+    let gradient = InfographicStyle {
+        gradient: Some("pinkblue".to_string()),
+        ..InfographicStyle::default()
+    };
+    draw_horizontal_timeline(&mut img, &roadmap, &gradient, 40, 40, 1720, 420);
+
+    // Numbered steps with icons, and badges in another gradient
+    let mut steps = vec![
+        Step::new("Pick a template", "Start from one of the built-in designs."),
+        Step::new("Add your photo", "It's smart-cropped to fit each format."),
+        Step::new(
+            "Write a caption",
+            "The text shrinks to fit the space it has.",
+        ),
+        Step::new("Publish", "Export every size at once."),
+    ];
+    let icons = [
+        icon(&raster::rect_outline(15.0, 15.0, 70.0, 70.0, 8.0)),
+        icon(&[raster::circle(50.0, 50.0, 36.0)]),
+        icon(&raster::polyline(
+            &[(15.0, 30.0), (85.0, 30.0), (15.0, 55.0), (60.0, 75.0)],
+            8.0,
+        )),
+        icon(&[raster::regular_polygon(50.0, 52.0, 40.0, 3, 0.0)]),
+    ];
+    for (step, icon) in steps.iter_mut().zip(icons.iter()) {
+        step.icon = Some(icon.clone());
+    }
+    let lemongrass = InfographicStyle {
+        gradient: Some("lemongrass".to_string()),
+        ..InfographicStyle::default()
+    };
+    draw_steps(&mut img, &steps, &lemongrass, 40, 520, 840, 680);
+
+    // A vertical timeline in the plain accent colour
+    let history = vec![
+        TimelineItem::new("2019", "First release", "Text and gradients on photos"),
+        TimelineItem::new("2021", "Filters", "Blend modes, masks and filters"),
+        TimelineItem::new("2023", "Layouts", "Responsive designs for social formats"),
+        TimelineItem::new("Today", "Diagrams", ""),
+    ];
+    draw_vertical_timeline(
+        &mut img,
+        &history,
+        &InfographicStyle::default(),
+        940,
+        520,
+        820,
+        680,
+    );
+
+    // A comparison of plans
+    let mut plans = Comparison::new(&["Free", "Pro", "Team"]);
+    plans.add_feature(
+        "Social media formats",
+        vec![Mark::Yes, Mark::Yes, Mark::Yes],
+    );
+    plans.add_feature("Charts and diagrams", vec![Mark::No, Mark::Yes, Mark::Yes]);
+    plans.add_feature("Custom fonts", vec![Mark::No, Mark::Yes, Mark::Yes]);
+    plans.add_feature("Shared templates", vec![Mark::No, Mark::No, Mark::Yes]);
+    plans.add_feature(
+        "Storage",
+        vec![
+            Mark::Text("1 GB".to_string()),
+            Mark::Text("50 GB".to_string()),
+            Mark::Text("1 TB".to_string()),
+        ],
+    );
+    draw_comparison(&mut img, &plans, &gradient, 40, 1260, 1720, 600);
+
+    gdl::helpers::save_image(img, "example_output/infographics.png");
+
+    println!(
+        "Took {} seconds to create image.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output image in examples/example_output");
+}
@@ -0,0 +1,59 @@
+extern crate gdl;
+use gdl::blend::composite;
+use gdl::collage::{justified_collage, masonry_collage};
+use gdl::crop::{smart_crop, CropStrategy};
+use gdl::filters::{apply_filters, Filter};
+use gdl::{new_with_background, Rgb};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let white = Rgb {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+
+    let city = gdl::helpers::open_image("examples/input_images/city_square.jpg");
+    let drive = gdl::helpers::open_image("examples/input_images/drive.jpg");
+
+    // Crop copies of the photos to a mix of landscape, square and portrait sizes.
+    let sizes = [
+        (600, 400),
+        (400, 600),
+        (500, 500),
+        (700, 400),
+        (400, 500),
+        (600, 450),
+        (450, 600),
+        (640, 360),
+        (500, 400),
+    ];
+    let photos: Vec<_> = sizes
+        .iter()
+        .enumerate()
+        .map(|(i, &(width, height))| {
+            let source = if i % 2 == 0 { &city } else { &drive };
+            let mut img = smart_crop(source, width, height, CropStrategy::Saliency);
+            apply_filters(&mut img, &[Filter::HueRotate(i as f32 * 40.0)]);
+            img
+        })
+        .collect();
+
+    let collage = justified_collage(&photos, 10, 1200, 800);
+    let mut img = new_with_background(1240, 840, &white);
+    composite(&mut img, &collage, 20, 20, 1.0);
+    gdl::helpers::save_image(img, "example_output/justified_collage.png");
+
+    let collage = masonry_collage(&photos, 10, 1200, 800);
+    let mut img = new_with_background(1240, 840, &white);
+    composite(&mut img, &collage, 20, 20, 1.0);
+    gdl::helpers::save_image(img, "example_output/masonry_collage.png");
+
+    println!(
+        "Took {} seconds to create images.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output images in examples/example_output");
+}
@@ -6,7 +6,6 @@ use std::time::Instant;
 fn main() {
     let start = Instant::now();
 
-    let _beach = gdl::helpers::open_image("examples/input_images/beach.jpg");
     let _white = Rgb {
         r: 255,
         g: 255,
@@ -15,12 +14,13 @@ fn main() {
     let black = Rgb { r: 0, g: 0, b: 0 };
     let mut img = new_with_background(1500, 1500, &black);
 
-    let data: Vec<u16> = vec![0, 5, 40, 20, 30];
+    let data: Vec<f64> = vec![0.0, 5.0, 40.0, 20.0, 30.0];
     let labels: Vec<String> = vec![
         "a_spec".to_string(),
         "b_spec".to_string(),
         "c_spec".to_string(),
         "d_spec".to_string(),
+        "e_spec".to_string(),
     ];
     let blue = Rgb {
         r: 40,
@@ -28,7 +28,7 @@ fn main() {
         b: 200,
     };
 
-    let barchart = Chart::new(
+    let mut barchart = Chart::new(
         "Earnings for 2019/2020".to_string(),
         blue,
         data,
@@ -37,8 +37,19 @@ fn main() {
         1500,
     );
 
+    // A second series, which dips below zero.
+    let orange = Rgb {
+        r: 255,
+        g: 167,
+        b: 90,
+    };
+    barchart.add_series("Costs", vec![10.0, -5.0, 15.0, 25.0, 12.5], orange);
+
+    barchart.x_axis.title = Some("Quarter".to_string());
+    barchart.y_axis.title = Some("Thousands".to_string());
+
     diagrams::draw_linechart(&mut img, &barchart);
-    gdl::helpers::save_image(img, "postcard.png");
+    gdl::helpers::save_image(img, "example_output/linechart.png");
 
     println!(
         "Took {} seconds to create image.",
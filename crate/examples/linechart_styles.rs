@@ -0,0 +1,78 @@
+extern crate gdl;
+use gdl::diagrams::{AreaFill, Chart, LineOptions, Marker, Smoothing};
+use gdl::{diagrams, new_with_background, Rgb};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let black = Rgb { r: 0, g: 0, b: 0 };
+    let mut img = new_with_background(1600, 1600, &black);
+
+    let labels: Vec<String> = vec![
+        "Jan".to_string(),
+        "Feb".to_string(),
+        "Mar".to_string(),
+        "Apr".to_string(),
+        "May".to_string(),
+        "Jun".to_string(),
+        "Jul".to_string(),
+    ];
+    let palette = diagrams::palette("vivid");
+
+    let mut chart = Chart::new(
+        "Visitors".to_string(),
+        palette[0].clone(),
+        vec![12.0, 30.0, 18.0, 42.0, 40.0, 26.0, 34.0],
+        labels,
+        800,
+        800,
+    );
+    chart.series[0].name = "Web".to_string();
+    chart.add_series(
+        "App",
+        vec![4.0, 8.0, 15.0, 14.0, f64::NAN, 22.0, 28.0],
+        palette[1].clone(),
+    );
+
+    // Straight lines with circle markers; the gap in the second series breaks its line
+    chart.set_bounds(0, 0, 800, 800);
+    diagrams::draw_linechart_points(&mut img, &chart);
+
+    // Catmull-Rom smoothing with diamond markers
+    chart.set_bounds(800, 0, 800, 800);
+    chart.title = "Catmull-Rom".to_string();
+    let options = LineOptions {
+        smoothing: Smoothing::CatmullRom,
+        marker: Marker::Diamond,
+        marker_size: 16.0,
+        ..LineOptions::default()
+    };
+    diagrams::draw_linechart_with_options(&mut img, &chart, &options);
+
+    // Monotone smoothing, which never overshoots the data, over a translucent area
+    chart.set_bounds(0, 800, 800, 800);
+    chart.title = "Monotone".to_string();
+    let options = LineOptions {
+        thickness: 5.0,
+        smoothing: Smoothing::Monotone,
+        marker: Marker::Square,
+        area: AreaFill::Solid(0.3),
+        ..LineOptions::default()
+    };
+    diagrams::draw_linechart_with_options(&mut img, &chart, &options);
+
+    // An area chart, with gradients fading out towards the baseline
+    chart.set_bounds(800, 800, 800, 800);
+    chart.title = "Area".to_string();
+    chart.series[1].values[4] = 19.0;
+    diagrams::draw_areachart(&mut img, &chart);
+
+    gdl::helpers::save_image(img, "example_output/linechart_styles.png");
+
+    println!(
+        "Took {} seconds to create image.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output image in examples/example_output");
+}
@@ -0,0 +1,44 @@
+extern crate gdl;
+use gdl::mask::{draw_masked_image, Mask};
+use gdl::text::draw_text;
+use gdl::{new_with_background, Rgb};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let navy = Rgb {
+        r: 20,
+        g: 30,
+        b: 70,
+    };
+    let white = Rgb {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+    let mut img = new_with_background(1200, 630, &navy);
+
+    // Speaker-announcement style graphic, with photos placed into various shapes.
+    let photo = gdl::helpers::open_image("examples/input_images/city_square.jpg");
+    let avatar = photo.thumbnail_exact(300, 300);
+
+    draw_masked_image(&mut img, &avatar, &Mask::circle(300, 300), 60, 60);
+    draw_masked_image(&mut img, &avatar, &Mask::rounded_rect(300, 300, 40.0), 450, 60);
+    draw_masked_image(&mut img, &avatar, &Mask::hexagon(300, 300), 840, 60);
+
+    // Let the photo show through some text.
+    let banner = photo.thumbnail_exact(1200, 200);
+    let text_mask = Mask::from_text(1200, 200, "SPEAKERS 2020", 60, 20, "BebasKai", 180.0);
+    draw_masked_image(&mut img, &banner, &text_mask, 0, 400);
+
+    draw_text(&mut img, "Meet the speakers", 60, 380, "Roboto-Bold", 30.0, &white);
+
+    gdl::helpers::save_image(img, "example_output/masks.png");
+
+    println!(
+        "Took {} seconds to create image.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output image in examples/example_output");
+}
@@ -0,0 +1,46 @@
+extern crate gdl;
+use gdl::elements::*;
+use gdl::raster::Quality;
+use gdl::{new_with_background, Rgb, Rgba};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let navy = Rgb {
+        r: 20,
+        g: 30,
+        b: 70,
+    };
+    let mut img = new_with_background(900, 400, &navy);
+
+    // Translucent shapes are blended with whatever is underneath them.
+    let pink = Rgba::new(240, 80, 160, 160);
+    let teal = Rgba::new(40, 200, 180, 160);
+    draw_solid_rect(&mut img, pink, 200, 200, 40, 60);
+    draw_circle(&mut img, (240.0, 200.0), 100.0, teal, Quality::AntiAliased);
+
+    // A group is blended as a whole, so its overlapping elements don't show through each other.
+    let white = Rgb {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+    let yellow = Rgb {
+        r: 255,
+        g: 226,
+        b: 98,
+    };
+    draw_group(&mut img, 0.5, |layer| {
+        draw_solid_rect(layer, &white, 200, 200, 480, 60);
+        draw_circle(layer, (680.0, 200.0), 100.0, &yellow, Quality::AntiAliased);
+    });
+
+    gdl::helpers::save_image(img, "example_output/opacity.png");
+
+    println!(
+        "Took {} seconds to create image.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output image in examples/example_output");
+}
@@ -0,0 +1,93 @@
+extern crate gdl;
+use gdl::diagrams::{Chart, PieOptions, RadialProgress};
+use gdl::{diagrams, new_with_background, Rgb};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let black = Rgb { r: 0, g: 0, b: 0 };
+    let mut img = new_with_background(1500, 1500, &black);
+
+    let data: Vec<f64> = vec![42.0, 23.0, 15.0, 12.0, 8.0];
+    let labels: Vec<String> = vec![
+        "Instagram".to_string(),
+        "Twitter".to_string(),
+        "Facebook".to_string(),
+        "LinkedIn".to_string(),
+        "Other".to_string(),
+    ];
+    let blue = Rgb {
+        r: 40,
+        g: 50,
+        b: 200,
+    };
+
+    let mut chart = Chart::new(
+        "Posts by platform".to_string(),
+        blue,
+        data,
+        labels,
+        700,
+        700,
+    );
+
+    // A pie chart in the top left, with its largest slice pulled out
+    chart.set_bounds(25, 25, 700, 700);
+    let options = PieOptions {
+        exploded: vec![0],
+        ..PieOptions::default()
+    };
+    diagrams::draw_piechart_with_options(&mut img, &chart, &options);
+
+    // A donut chart in the top right, with the total in the middle, using a pastel palette
+    chart.set_bounds(775, 25, 700, 700);
+    chart.style.palette = diagrams::palette("pastel");
+    chart.style.legend = false;
+    diagrams::draw_donutchart(&mut img, &chart, "100 posts");
+
+    // Progress rings along the bottom left
+    let green = Rgb {
+        r: 89,
+        g: 161,
+        b: 79,
+    };
+    let orange = Rgb {
+        r: 242,
+        g: 142,
+        b: 43,
+    };
+    diagrams::draw_progress_ring(
+        &mut img,
+        &RadialProgress::new(72.0, 100.0, green),
+        75,
+        850,
+        280,
+    );
+    let mut followers = RadialProgress::new(8200.0, 10000.0, orange);
+    followers.label = Some("8.2k".to_string());
+    followers.thickness = 0.35;
+    diagrams::draw_progress_ring(&mut img, &followers, 425, 850, 280);
+
+    // A gauge in the bottom right
+    let red = Rgb {
+        r: 225,
+        g: 87,
+        b: 89,
+    };
+    diagrams::draw_gauge(
+        &mut img,
+        &RadialProgress::new(65.0, 120.0, red),
+        850,
+        900,
+        550,
+    );
+
+    gdl::helpers::save_image(img, "example_output/piechart.png");
+
+    println!(
+        "Took {} seconds to create image.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output image in examples/example_output");
+}
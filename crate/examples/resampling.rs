@@ -0,0 +1,60 @@
+extern crate gdl;
+use gdl::blend::composite;
+use gdl::resize::{resample, Resampling};
+use gdl::text::draw_text;
+use gdl::{new_with_background, Rgb};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let black = Rgb { r: 0, g: 0, b: 0 };
+    let white = Rgb {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+
+    let photo = gdl::helpers::open_image("examples/input_images/city_square.jpg");
+
+    // Shrink the same photo with each resampling filter, then enlarge a detail so the differences show.
+    let filters = [
+        ("Nearest", Resampling::Nearest),
+        ("Triangle", Resampling::Triangle),
+        ("CatmullRom", Resampling::CatmullRom),
+        ("Gaussian", Resampling::Gaussian),
+        ("Lanczos3", Resampling::Lanczos3),
+        ("Area", Resampling::Area),
+    ];
+
+    let mut img = new_with_background(20 + filters.len() as u32 * 220, 520, &black);
+    for (i, (name, filter)) in filters.iter().enumerate() {
+        let x = 20 + i as i32 * 220;
+        let thumbnail = resample(&photo, 200, 200, *filter);
+        let detail = resample(
+            &thumbnail.crop_imm(50, 50, 50, 50),
+            200,
+            200,
+            Resampling::Nearest,
+        );
+        composite(&mut img, &thumbnail, x, 20, 1.0);
+        composite(&mut img, &detail, x, 240, 1.0);
+        draw_text(
+            &mut img,
+            name,
+            x as u32,
+            460,
+            "Roboto-Regular",
+            24.0,
+            &white,
+        );
+    }
+
+    gdl::helpers::save_image(img, "example_output/resampling.png");
+
+    println!(
+        "Took {} seconds to create image.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output image in examples/example_output");
+}
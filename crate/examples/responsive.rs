@@ -0,0 +1,75 @@
+extern crate gdl;
+use gdl::crop::CropStrategy;
+use gdl::formats::FormatRegistry;
+use gdl::layout::{Anchor, Background, Constraint, Design, Layout};
+use gdl::{Rgb, Rgba};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let white = Rgb {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+    let photo = gdl::helpers::open_image("examples/input_images/city_square.jpg");
+
+    // One design, laid out side-by-side in landscape formats and stacked in portrait formats.
+    let mut design = Design::new(Background::Image(photo, CropStrategy::Saliency));
+    design.add_rect(
+        Rgba::new(10, 20, 40, 190),
+        0.08,
+        Layout::new(
+            Constraint::new(Anchor::Left, 0.04, 0.0, 0.5, 0.8),
+            Constraint::new(Anchor::Bottom, 0.0, 0.05, 0.9, 0.45),
+            Constraint::new(Anchor::Bottom, 0.0, 0.05, 0.9, 0.35),
+        ),
+    );
+    design.add_text(
+        "Visit Stockholm",
+        "BebasKai",
+        0.3,
+        &white,
+        Layout::new(
+            Constraint::new(Anchor::Left, 0.08, -0.08, 0.42, 0.4),
+            Constraint::new(Anchor::Bottom, 0.0, 0.25, 0.8, 0.2),
+            Constraint::new(Anchor::Bottom, 0.0, 0.2, 0.8, 0.15),
+        ),
+    );
+    design.add_text(
+        "Winter city breaks from 99 euro, book before the end of the month.",
+        "Roboto-Regular",
+        0.06,
+        &white,
+        Layout::new(
+            Constraint::new(Anchor::Left, 0.08, 0.15, 0.42, 0.25),
+            Constraint::new(Anchor::Bottom, 0.0, 0.08, 0.8, 0.15),
+            Constraint::new(Anchor::Bottom, 0.0, 0.08, 0.8, 0.1),
+        ),
+    );
+
+    let registry = FormatRegistry::new();
+    let formats: Vec<_> = [
+        "twitter_header",
+        "instagram_post",
+        "instagram_story",
+        "open_graph",
+    ]
+    .iter()
+    .map(|name| registry.get(name).unwrap().clone())
+    .collect();
+
+    for (format, img) in formats.iter().zip(design.render_all(&formats)) {
+        gdl::helpers::save_image(
+            img,
+            &format!("example_output/responsive_{}.png", format.name),
+        );
+    }
+
+    println!(
+        "Took {} seconds to create images.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output images in examples/example_output");
+}
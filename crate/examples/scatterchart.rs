@@ -0,0 +1,158 @@
+extern crate gdl;
+use gdl::diagrams::{AreaFill, Chart, ScatterOptions, Smoothing, Sparkline};
+use gdl::text::{self, TextAlign};
+use gdl::{diagrams, new_with_background, Rgb};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let black = Rgb { r: 0, g: 0, b: 0 };
+    let white = Rgb {
+        r: 230,
+        g: 230,
+        b: 230,
+    };
+    let mut img = new_with_background(1600, 1200, &black);
+    let palette = diagrams::palette("default");
+
+    // Height against weight, with a trend line through each group, in the top left
+    let mut chart = Chart::new(
+        "Height and weight".to_string(),
+        palette[0].clone(),
+        vec![],
+        vec![],
+        800,
+        800,
+    );
+    chart.series.clear();
+    chart.add_points(
+        "Adults",
+        &[
+            (158.0, 55.0),
+            (162.0, 61.0),
+            (165.0, 59.0),
+            (170.0, 68.0),
+            (172.0, 74.0),
+            (175.0, 71.0),
+            (178.0, 80.0),
+            (181.0, 79.0),
+            (185.0, 88.0),
+            (190.0, 92.0),
+        ],
+        palette[0].clone(),
+    );
+    chart.add_points(
+        "Teens",
+        &[
+            (150.0, 42.0),
+            (153.0, 47.0),
+            (157.0, 45.0),
+            (160.0, 52.0),
+            (164.0, 50.0),
+            (168.0, 57.0),
+            (171.0, 61.0),
+        ],
+        palette[1].clone(),
+    );
+    chart.x_axis.include_zero = false;
+    chart.y_axis.include_zero = false;
+    chart.x_axis.title = Some("Height (cm)".to_string());
+    chart.y_axis.title = Some("Weight (kg)".to_string());
+    chart.set_bounds(0, 0, 800, 800);
+    let options = ScatterOptions {
+        trend_line: true,
+        ..ScatterOptions::default()
+    };
+    diagrams::draw_scatterchart_with_options(&mut img, &chart, &options);
+
+    // Cities by population, income and area, as bubbles, in the top right
+    let mut chart = Chart::new(
+        "Cities".to_string(),
+        palette[2].clone(),
+        vec![],
+        vec![],
+        800,
+        800,
+    );
+    chart.series.clear();
+    chart.add_points(
+        "Cities",
+        &[
+            (1.2, 32.0),
+            (3.5, 41.0),
+            (6.8, 55.0),
+            (2.4, 28.0),
+            (8.9, 48.0),
+        ],
+        palette[2].clone(),
+    );
+    chart.series[0].sizes = vec![120.0, 300.0, 800.0, 60.0, 450.0];
+    chart.x_axis.title = Some("Population (m)".to_string());
+    chart.y_axis.title = Some("Income (k)".to_string());
+    chart.set_bounds(800, 0, 800, 800);
+    diagrams::draw_bubblechart(&mut img, &chart);
+
+    // KPI cards with sparklines along the bottom
+    let cards = [
+        (
+            "Visitors",
+            "12.4k",
+            vec![8.0, 9.5, 9.0, 11.0, 10.5, 12.0, 12.4],
+            Smoothing::None,
+            AreaFill::None,
+        ),
+        (
+            "Revenue",
+            "$3.1k",
+            vec![4.2, 3.8, 3.9, 3.2, 3.5, 2.9, 3.1],
+            Smoothing::Monotone,
+            AreaFill::Gradient(0.5),
+        ),
+        (
+            "Signups",
+            "318",
+            vec![120.0, 180.0, f64::NAN, 240.0, 260.0, 300.0, 318.0],
+            Smoothing::CatmullRom,
+            AreaFill::Solid(0.25),
+        ),
+    ];
+    for (i, (name, figure, values, smoothing, area)) in cards.iter().enumerate() {
+        let x = 40 + i as u32 * 520;
+        let color = palette[i + 3].clone();
+        text::draw_text_block(
+            &mut img,
+            name,
+            x,
+            880,
+            200,
+            "Roboto-Regular",
+            28.0,
+            &white,
+            TextAlign::Left,
+        );
+        text::draw_text_block(
+            &mut img,
+            figure,
+            x,
+            930,
+            200,
+            "Roboto-Bold",
+            64.0,
+            &white,
+            TextAlign::Left,
+        );
+        let mut sparkline = Sparkline::new(values.clone(), color);
+        sparkline.smoothing = *smoothing;
+        sparkline.area = *area;
+        diagrams::draw_sparkline(&mut img, &sparkline, x + 210, 920, 260, 80);
+    }
+
+    gdl::helpers::save_image(img, "example_output/scatterchart.png");
+
+    println!(
+        "Took {} seconds to create image.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output image in examples/example_output");
+}
@@ -0,0 +1,58 @@
+extern crate gdl;
+use gdl::blend::composite;
+use gdl::crop::CropStrategy;
+use gdl::resize::{resize_to_fit, Fit};
+use gdl::text::draw_text;
+use gdl::{new_with_background, Rgb, Rgba};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let black = Rgb { r: 0, g: 0, b: 0 };
+    let white = Rgb {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+
+    let photo = gdl::helpers::open_image("examples/input_images/drive.jpg");
+
+    // Fit the same photo into a tall, portrait format in various ways.
+    let fits = [
+        ("Stretch", Fit::Stretch),
+        ("Centre", Fit::Cover(CropStrategy::Centre)),
+        ("Saliency", Fit::Cover(CropStrategy::Saliency)),
+        ("Entropy", Fit::Cover(CropStrategy::Entropy)),
+        ("Edges", Fit::Cover(CropStrategy::Edges)),
+        (
+            "Focal point",
+            Fit::Cover(CropStrategy::FocalPoint(0.0, 0.5)),
+        ),
+        ("Contain", Fit::Contain(Rgba::new(40, 40, 40, 255))),
+    ];
+
+    let mut img = new_with_background(20 + fits.len() as u32 * 220, 420, &black);
+    for (i, (name, fit)) in fits.iter().enumerate() {
+        let x = 20 + i as i32 * 220;
+        let resized = resize_to_fit(&photo, 200, 340, *fit);
+        composite(&mut img, &resized, x, 20, 1.0);
+        draw_text(
+            &mut img,
+            name,
+            x as u32,
+            370,
+            "Roboto-Regular",
+            24.0,
+            &white,
+        );
+    }
+
+    gdl::helpers::save_image(img, "example_output/smart_crop.png");
+
+    println!(
+        "Took {} seconds to create image.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output image in examples/example_output");
+}
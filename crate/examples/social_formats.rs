@@ -0,0 +1,67 @@
+extern crate gdl;
+use gdl::elements::draw_opaque_rect;
+use gdl::formats::{FormatRegistry, SafeArea, SocialFormat};
+use gdl::resize::{resize_to_fit, Fit};
+use gdl::Rgb;
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let black = Rgb { r: 0, g: 0, b: 0 };
+    let photo = gdl::helpers::open_image("examples/input_images/city_square.jpg");
+
+    // Add a custom format alongside the built-in ones.
+    let mut registry = FormatRegistry::new();
+    registry.add(SocialFormat::new(
+        "newsletter_header",
+        600,
+        200,
+        SafeArea::uniform(20),
+    ));
+
+    let names = [
+        "instagram_story",
+        "tiktok",
+        "youtube_thumbnail",
+        "twitter_header",
+        "newsletter_header",
+    ];
+
+    // Resize the photo to each format, and shade the areas outside the safe area.
+    for name in names.iter() {
+        let format = registry.get(name).unwrap();
+        let mut img = resize_to_fit(&photo, format.width, format.height, Fit::default());
+
+        let (x, y, width, height) = format.safe_rect();
+        let area = format.safe_area;
+        draw_opaque_rect(&mut img, &black, 150, format.width, area.top, 0, 0);
+        draw_opaque_rect(
+            &mut img,
+            &black,
+            150,
+            format.width,
+            area.bottom,
+            0,
+            (y + height) as i32,
+        );
+        draw_opaque_rect(&mut img, &black, 150, area.left, height, 0, y as i32);
+        draw_opaque_rect(
+            &mut img,
+            &black,
+            150,
+            area.right,
+            height,
+            (x + width) as i32,
+            y as i32,
+        );
+
+        gdl::helpers::save_image(img, &format!("example_output/format_{}.png", name));
+    }
+
+    println!(
+        "Took {} seconds to create images.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output images in examples/example_output");
+}
@@ -0,0 +1,97 @@
+extern crate gdl;
+use gdl::blend;
+use gdl::table::{create_table, draw_table, Borders, Cell, Table, TableStyle};
+use gdl::text::TextAlign;
+use gdl::{new_with_background, Rgb, Rgba};
+use std::time::Instant;
+
+fn main() {
+    let start = Instant::now();
+
+    let background = Rgb {
+        r: 20,
+        g: 24,
+        b: 28,
+    };
+    let mut img = new_with_background(920, 980, &background);
+
+    // Text and numbers, with columns sized to fit and numbers right-aligned
+    let mut formats = Table::new(&["Format", "Width", "Height", "Aspect ratio"]);
+    for (name, width, height) in &[
+        ("Instagram post", 1080, 1080),
+        ("Instagram story", 1080, 1920),
+        ("Twitter post", 1200, 675),
+        ("Facebook cover", 820, 312),
+        ("LinkedIn banner", 1584, 396),
+    ] {
+        formats.add_row(vec![
+            Cell::from(*name),
+            Cell::from(*width),
+            Cell::from(*height),
+            Cell::from(*width as f64 / *height as f64),
+        ]);
+    }
+    draw_table(&mut img, &formats, &TableStyle::default(), 40, 40);
+
+    // A gradient header, full grid, fixed decimals and long text wrapped to a maximum width
+    let mut results = Table::new(&["Filter", "Notes", "Time (ms)"]);
+    results.add_row(vec![
+        "Gaussian blur".into(),
+        "Separable, so its cost grows with the radius rather than its square.".into(),
+        12.5.into(),
+    ]);
+    results.add_row(vec!["Sharpen".into(), "3×3 kernel.".into(), 3.25.into()]);
+    results.add_row(vec![
+        "Smart crop".into(),
+        "Uses saliency, so it's the slowest, but finds faces and text.".into(),
+        41.0.into(),
+    ]);
+    results.add_row(vec![
+        "Resample".into(),
+        "Lanczos3 by default.".into(),
+        8.0.into(),
+    ]);
+    let grid = TableStyle {
+        header_gradient: Some("pinkblue".to_string()),
+        borders: Borders::Grid,
+        max_column_width: Some(500),
+        decimals: Some(1),
+        ..TableStyle::default()
+    };
+    draw_table(&mut img, &results, &grid, 40, 400);
+
+    // A light table with centred columns and an outer border only, created as its own image
+    let mut scores = Table::new(&["Team", "Played", "Won", "Points"]);
+    scores.add_row(vec!["Reds".into(), 10.into(), 7.into(), 22.into()]);
+    scores.add_row(vec!["Blues".into(), 10.into(), 6.into(), 19.into()]);
+    scores.add_row(vec!["Greens".into(), 10.into(), 3.into(), 11.into()]);
+    for column in 1..4 {
+        scores.set_alignment(column, TextAlign::Centre);
+    }
+    let dark = Rgb {
+        r: 40,
+        g: 40,
+        b: 40,
+    };
+    let light = TableStyle {
+        text_color: dark.clone(),
+        header_text_color: dark,
+        header_background: Rgba::new(220, 224, 230, 255),
+        background: Rgba::new(255, 255, 255, 255),
+        stripe_color: Some(Rgba::new(0, 0, 0, 12)),
+        borders: Borders::Outer,
+        border_color: Rgba::new(0, 0, 0, 60),
+        border_width: 2.0,
+        ..TableStyle::default()
+    };
+    let table = create_table(&scores, &light);
+    blend::composite(&mut img, &table, 40, 740, 1.0);
+
+    gdl::helpers::save_image(img, "example_output/table.png");
+
+    println!(
+        "Took {} seconds to create image.",
+        start.elapsed().as_secs()
+    );
+    println!("You'll find the output image in examples/example_output");
+}
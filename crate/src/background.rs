@@ -2,9 +2,10 @@
 
 use image::{DynamicImage, GenericImage};
 // use wasm_bindgen::prelude::*;
-use crate::Rgb;
+use crate::blend;
+use crate::raster::{self, Quality};
+use crate::{Rgb, Rgba};
 use image::{GenericImageView, ImageBuffer, RgbaImage};
-use imageproc::drawing::{draw_filled_circle_mut, draw_line_segment_mut};
 use palette::rgb::LinSrgba;
 use palette::{FromColor, Gradient, Lch, Pixel, Srgba};
 use std::convert::TryInto;
@@ -29,12 +30,12 @@ pub fn circle_background(width: u32, height: u32) -> DynamicImage {
 
     for x in 0..5 {
         for y in 0..6 {
-            draw_filled_circle_mut(
-                &mut rgba_img,
-                (x * circle_radius * 2, y * circle_radius * 2),
-                circle_radius,
-                white_pixel,
+            let circle = raster::circle(
+                (x * circle_radius * 2) as f32,
+                (y * circle_radius * 2) as f32,
+                circle_radius as f32,
             );
+            raster::fill_contours(&mut rgba_img, &[circle], white_pixel, Quality::AntiAliased);
         }
     }
     return rgba_img;
@@ -62,12 +63,12 @@ pub fn spaced_circle_background(width: u32, height: u32) -> DynamicImage {
 
     for x in 0..8 {
         for y in 0..10 {
-            draw_filled_circle_mut(
-                &mut rgba_img,
-                (x * circle_radius * 3, y * circle_radius * 3),
-                circle_radius,
-                white_pixel,
+            let circle = raster::circle(
+                (x * circle_radius * 3) as f32,
+                (y * circle_radius * 3) as f32,
+                circle_radius as f32,
             );
+            raster::fill_contours(&mut rgba_img, &[circle], white_pixel, Quality::AntiAliased);
         }
     }
 
@@ -80,8 +81,8 @@ pub fn spaced_circle_background(width: u32, height: u32) -> DynamicImage {
 /// # Arguments
 /// * `width` - u32 - Desired width of final graphic
 /// * `height` - u32 - Desired height of final graphic
-/// * `background_color` - Rgb color the background should comprise of
-pub fn solid_background(width: u32, height: u32, background_color: Rgb) -> DynamicImage {
+/// * `background_color` - Rgb or Rgba color the background should comprise of
+pub fn solid_background(width: u32, height: u32, background_color: impl Into<Rgba>) -> DynamicImage {
     let rgba_img = create_image_from_pixel(background_color, width, height);
     return rgba_img;
 }
@@ -92,19 +93,15 @@ pub fn solid_background(width: u32, height: u32, background_color: Rgb) -> Dynam
 /// # Arguments
 /// * `width` - u32 - Desired width of final graphic
 /// * `height` - u32 - Desired height of final graphic
-/// * `background_color` - Rgb color the background should comprise of
-pub fn lined_background(width: u32, height: u32, background_color: Rgb) -> DynamicImage {
+/// * `background_color` - Rgb or Rgba color the background should comprise of
+pub fn lined_background(width: u32, height: u32, background_color: impl Into<Rgba>) -> DynamicImage {
     let mut rgba_img = create_image_from_pixel(background_color, width, height);
     let line_pixel = image::Rgba([255, 167, 90, 255]);
 
-    for y in 0..50 {
-        draw_line_segment_mut(
-            &mut rgba_img,
-            (0 as f32, (y * 20) as f32),
-            (width as f32, (y * 20) as f32),
-            line_pixel,
-        );
-    }
+    let lines: Vec<raster::Contour> = (0..50)
+        .map(|y| horizontal_line(y as f32 * 20.0, width as f32))
+        .collect();
+    raster::fill_contours(&mut rgba_img, &lines, line_pixel, Quality::AntiAliased);
     return rgba_img;
 }
 
@@ -114,28 +111,17 @@ pub fn lined_background(width: u32, height: u32, background_color: Rgb) -> Dynam
 /// # Arguments
 /// * `width` - u32 - Desired width of final graphic
 /// * `height` - u32 - Desired height of final graphic
-/// * `background_color` - Rgb color the background should comprise of.
-pub fn grid_background(width: u32, height: u32, background_color: Rgb) -> DynamicImage {
+/// * `background_color` - Rgb or Rgba color the background should comprise of.
+pub fn grid_background(width: u32, height: u32, background_color: impl Into<Rgba>) -> DynamicImage {
     let mut rgba_img = create_image_from_pixel(background_color, width, height);
 
     let line_pixel = image::Rgba([255, 167, 90, 255]);
 
-    for y in 0..50 {
-        draw_line_segment_mut(
-            &mut rgba_img,
-            (0.0, (y * 20) as f32),
-            (width as f32, (y * 20) as f32),
-            line_pixel,
-        );
-    }
-    for x in 0..50 {
-        draw_line_segment_mut(
-            &mut rgba_img,
-            (x as f32 * 50.0, 0.0),
-            (x as f32 * 50.0, height as f32),
-            line_pixel,
-        );
-    }
+    let mut lines: Vec<raster::Contour> = (0..50)
+        .map(|y| horizontal_line(y as f32 * 20.0, width as f32))
+        .collect();
+    lines.extend((0..50).map(|x| vertical_line(x as f32 * 50.0, height as f32)));
+    raster::fill_contours(&mut rgba_img, &lines, line_pixel, Quality::AntiAliased);
     return rgba_img;
 }
 
@@ -145,23 +131,24 @@ pub fn grid_background(width: u32, height: u32, background_color: Rgb) -> Dynami
 /// # Arguments
 /// * `width` - u32 - Desired width of final graphic
 /// * `height` - u32 - Desired height of final graphic
-/// * `background_color` - Rgb color the background should comprise of.
+/// * `background_color` - Rgb or Rgba color the background should comprise of.
 /// * `img` - A DynamicImage to be painted onto the background in a pattern.
 pub fn pattern_from_img(
     width: u32,
     height: u32,
-    background_color: Rgb,
+    background_color: impl Into<Rgba>,
     img: DynamicImage,
 ) -> DynamicImage {
     let mut rgba_img = create_image_from_pixel(background_color, width, height);
 
     for x in 0..8 {
         for y in 0..10 {
-            image::imageops::overlay(
+            blend::composite(
                 &mut rgba_img,
                 &img,
-                x + img.width() * 3,
-                y * img.height() + 50,
+                (x + img.width() * 3) as i32,
+                (y * img.height() + 50) as i32,
+                1.0,
             );
         }
     }
@@ -208,14 +195,23 @@ pub fn gradient_background(width: u32, height: u32) -> DynamicImage {
     return rgba_img;
 }
 
-fn create_image_from_pixel(background_color: Rgb, width: u32, height: u32) -> DynamicImage {
-    let pixel = image::Rgba([
-        background_color.r,
-        background_color.g,
-        background_color.b,
-        255,
-    ]);
+fn create_image_from_pixel(
+    background_color: impl Into<Rgba>,
+    width: u32,
+    height: u32,
+) -> DynamicImage {
+    let pixel: image::Rgba<u8> = background_color.into().into();
     let image_buffer = ImageBuffer::from_pixel(width, height, pixel);
     let rgba_img = image::DynamicImage::ImageRgba8(image_buffer);
     return rgba_img;
 }
+
+// One pixel thick horizontal line covering the row of pixels starting at `y`.
+fn horizontal_line(y: f32, width: f32) -> raster::Contour {
+    raster::line((0.0, y + 0.5), (width, y + 0.5), 1.0)
+}
+
+// One pixel thick vertical line covering the column of pixels starting at `x`.
+fn vertical_line(x: f32, height: f32) -> raster::Contour {
+    raster::line((x + 0.5, 0.0), (x + 0.5, height), 1.0)
+}
@@ -1,3 +1,4 @@
+use gdl::filters::{apply_filters, Filter};
 use gdl::text::*;
 use gdl::{new_with_background, presets, Rgb};
 use std::time::Instant;
@@ -33,6 +34,15 @@ fn main() {
         b: 255,
     };
 
+    // Soften and darken the busy background, so that the text stands out.
+    apply_filters(
+        &mut alleyway,
+        &[
+            Filter::GaussianBlur(2.0),
+            Filter::Brightness(-0.15),
+            Filter::Vignette(0.5),
+        ],
+    );
     draw_text(&mut alleyway, "Visit", 230, 270, "BebasKai", 140.0, &white);
     draw_text(
         &mut alleyway,
@@ -0,0 +1,273 @@
+//! Composite colours and images onto each other, respecting their alpha channels,
+//! with blend modes such as multiply, screen and overlay.
+
+use image::{DynamicImage, GenericImage, GenericImageView, Rgba};
+
+/// Blend modes for compositing one layer onto another, as found in most graphic design tools.
+///
+/// The separable modes (`Normal` to `Exclusion`) work on each colour channel independently,
+/// while the non-separable modes (`Hue` to `Luminosity`) mix properties of both colours.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum BlendMode {
+    /// The top layer is placed over the bottom layer.
+    #[default]
+    Normal,
+    /// Multiplies the colours, which always darkens the result.
+    Multiply,
+    /// Inverse of multiply, which always lightens the result.
+    Screen,
+    /// Multiplies or screens depending on the bottom colour, increasing contrast.
+    Overlay,
+    /// Keeps the darker of the two colours.
+    Darken,
+    /// Keeps the lighter of the two colours.
+    Lighten,
+    /// Brightens the bottom colour to reflect the top colour.
+    ColorDodge,
+    /// Darkens the bottom colour to reflect the top colour.
+    ColorBurn,
+    /// Multiplies or screens depending on the top colour.
+    HardLight,
+    /// A softer version of hard light, which darkens or lightens depending on the top colour.
+    SoftLight,
+    /// Absolute difference between the colours.
+    Difference,
+    /// Similar to difference, but with lower contrast.
+    Exclusion,
+    /// Hue of the top colour, with the saturation and luminosity of the bottom colour.
+    Hue,
+    /// Saturation of the top colour, with the hue and luminosity of the bottom colour.
+    Saturation,
+    /// Hue and saturation of the top colour, with the luminosity of the bottom colour.
+    Color,
+    /// Luminosity of the top colour, with the hue and saturation of the bottom colour.
+    Luminosity,
+}
+
+/// Composite a pixel over another using source-over blending.
+///
+/// Returns the resulting pixel.
+///
+/// ### Arguments
+/// * `dst` - The pixel underneath.
+/// * `src` - The pixel being drawn on top.
+/// * `opacity` - Multiplier for the alpha of `src`, between 0.0 and 1.0.
+pub fn source_over(dst: Rgba<u8>, src: Rgba<u8>, opacity: f32) -> Rgba<u8> {
+    blend_pixel(dst, src, BlendMode::Normal, opacity)
+}
+
+/// Blend a pixel onto another with a blend mode, then composite it using source-over blending.
+///
+/// Where the pixel underneath is transparent, the top pixel is drawn unchanged.
+///
+/// ### Arguments
+/// * `dst` - The pixel underneath.
+/// * `src` - The pixel being drawn on top.
+/// * `mode` - Blend mode used to mix the colours of the two pixels.
+/// * `opacity` - Multiplier for the alpha of `src`, between 0.0 and 1.0.
+pub fn blend_pixel(dst: Rgba<u8>, src: Rgba<u8>, mode: BlendMode, opacity: f32) -> Rgba<u8> {
+    let src_a = src[3] as f32 / 255.0 * opacity.clamp(0.0, 1.0);
+    let dst_a = dst[3] as f32 / 255.0;
+    let out_a = src_a + dst_a * (1.0 - src_a);
+    if out_a <= 0.0 {
+        return Rgba([0, 0, 0, 0]);
+    }
+
+    let cb = [
+        dst[0] as f32 / 255.0,
+        dst[1] as f32 / 255.0,
+        dst[2] as f32 / 255.0,
+    ];
+    let cs = [
+        src[0] as f32 / 255.0,
+        src[1] as f32 / 255.0,
+        src[2] as f32 / 255.0,
+    ];
+    let mixed = blend_colors(cb, cs, mode);
+
+    let mut out = [0u8; 4];
+    for c in 0..3 {
+        // The blended colour only applies where the bottom layer is opaque.
+        let top = (1.0 - dst_a) * cs[c] + dst_a * mixed[c];
+        let value = (top * src_a + cb[c] * dst_a * (1.0 - src_a)) / out_a;
+        out[c] = (value * 255.0).round().clamp(0.0, 255.0) as u8;
+    }
+    out[3] = (out_a * 255.0).round() as u8;
+    Rgba(out)
+}
+
+/// Composite an image onto another using source-over blending, with a given opacity.
+///
+/// Unlike `image::imageops::overlay`, the top image may be placed at negative
+/// coordinates, in which case it is cropped.
+///
+/// ### Arguments
+/// * `bottom` - A mutable ref to the DynamicImage being drawn onto.
+/// * `top` - The image to be drawn on top.
+/// * `x` - X-coordinate of the top-left corner of `top` on `bottom`.
+/// * `y` - Y-coordinate of the top-left corner of `top` on `bottom`.
+/// * `opacity` - Opacity of `top`, between 0.0 and 1.0.
+pub fn composite(bottom: &mut DynamicImage, top: &DynamicImage, x: i32, y: i32, opacity: f32) {
+    blend(bottom, top, x, y, BlendMode::Normal, opacity);
+}
+
+/// Blend an image onto another with a blend mode and opacity.
+///
+/// ### Arguments
+/// * `bottom` - A mutable ref to the DynamicImage being drawn onto.
+/// * `top` - The image to be blended on top.
+/// * `x` - X-coordinate of the top-left corner of `top` on `bottom`.
+/// * `y` - Y-coordinate of the top-left corner of `top` on `bottom`.
+/// * `mode` - Blend mode, such as `BlendMode::Multiply` or `BlendMode::Screen`.
+/// * `opacity` - Opacity of `top`, between 0.0 and 1.0.
+///
+/// ### Example
+/// ```ignore
+/// // Darken a photo with a gradient, as with a multiply layer in an image editor.
+/// let gradient = create_gradient_preset(photo.width(), photo.height(), "pinkblue");
+/// blend(&mut photo, &gradient, 0, 0, BlendMode::Multiply, 0.8);
+/// ```
+pub fn blend(
+    bottom: &mut DynamicImage,
+    top: &DynamicImage,
+    x: i32,
+    y: i32,
+    mode: BlendMode,
+    opacity: f32,
+) {
+    let (bottom_width, bottom_height) = bottom.dimensions();
+    let (top_width, top_height) = top.dimensions();
+
+    // Only visit the region where the two images overlap.
+    let start_x = (-x).max(0) as u32;
+    let start_y = (-y).max(0) as u32;
+    let end_x = (bottom_width as i64 - x as i64).clamp(0, top_width as i64) as u32;
+    let end_y = (bottom_height as i64 - y as i64).clamp(0, top_height as i64) as u32;
+
+    for top_y in start_y..end_y {
+        for top_x in start_x..end_x {
+            let src = top.get_pixel(top_x, top_y);
+            if src[3] == 0 {
+                continue;
+            }
+            let (px, py) = ((x + top_x as i32) as u32, (y + top_y as i32) as u32);
+            let dst = bottom.get_pixel(px, py);
+            bottom.put_pixel(px, py, blend_pixel(dst, src, mode, opacity));
+        }
+    }
+}
+
+// Mix two colours, with channels between 0.0 and 1.0, using a blend mode.
+fn blend_colors(cb: [f32; 3], cs: [f32; 3], mode: BlendMode) -> [f32; 3] {
+    match mode {
+        BlendMode::Hue => set_lum(set_sat(cs, sat(cb)), lum(cb)),
+        BlendMode::Saturation => set_lum(set_sat(cb, sat(cs)), lum(cb)),
+        BlendMode::Color => set_lum(cs, lum(cb)),
+        BlendMode::Luminosity => set_lum(cb, lum(cs)),
+        _ => [
+            blend_channel(cb[0], cs[0], mode),
+            blend_channel(cb[1], cs[1], mode),
+            blend_channel(cb[2], cs[2], mode),
+        ],
+    }
+}
+
+// Mix a single channel using one of the separable blend modes.
+fn blend_channel(cb: f32, cs: f32, mode: BlendMode) -> f32 {
+    match mode {
+        BlendMode::Multiply => cb * cs,
+        BlendMode::Screen => cb + cs - cb * cs,
+        BlendMode::Overlay => blend_channel(cs, cb, BlendMode::HardLight),
+        BlendMode::Darken => cb.min(cs),
+        BlendMode::Lighten => cb.max(cs),
+        BlendMode::ColorDodge => {
+            if cb == 0.0 {
+                0.0
+            } else if cs >= 1.0 {
+                1.0
+            } else {
+                (cb / (1.0 - cs)).min(1.0)
+            }
+        }
+        BlendMode::ColorBurn => {
+            if cb >= 1.0 {
+                1.0
+            } else if cs == 0.0 {
+                0.0
+            } else {
+                1.0 - ((1.0 - cb) / cs).min(1.0)
+            }
+        }
+        BlendMode::HardLight => {
+            if cs <= 0.5 {
+                cb * 2.0 * cs
+            } else {
+                blend_channel(cb, 2.0 * cs - 1.0, BlendMode::Screen)
+            }
+        }
+        BlendMode::SoftLight => {
+            if cs <= 0.5 {
+                cb - (1.0 - 2.0 * cs) * cb * (1.0 - cb)
+            } else {
+                let d = if cb <= 0.25 {
+                    ((16.0 * cb - 12.0) * cb + 4.0) * cb
+                } else {
+                    cb.sqrt()
+                };
+                cb + (2.0 * cs - 1.0) * (d - cb)
+            }
+        }
+        BlendMode::Difference => (cb - cs).abs(),
+        BlendMode::Exclusion => cb + cs - 2.0 * cb * cs,
+        _ => cs,
+    }
+}
+
+// Helpers for the non-separable blend modes, which work on luminosity and saturation.
+fn lum(c: [f32; 3]) -> f32 {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+fn clip_color(c: [f32; 3]) -> [f32; 3] {
+    let l = lum(c);
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+    let mut out = c;
+    for channel in out.iter_mut() {
+        if n < 0.0 {
+            *channel = l + (*channel - l) * l / (l - n);
+        }
+        if x > 1.0 {
+            *channel = l + (*channel - l) * (1.0 - l) / (x - l);
+        }
+    }
+    out
+}
+
+fn set_lum(c: [f32; 3], l: f32) -> [f32; 3] {
+    let d = l - lum(c);
+    clip_color([c[0] + d, c[1] + d, c[2] + d])
+}
+
+fn sat(c: [f32; 3]) -> f32 {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+fn set_sat(c: [f32; 3], s: f32) -> [f32; 3] {
+    let max = c[0].max(c[1]).max(c[2]);
+    let min = c[0].min(c[1]).min(c[2]);
+    if max <= min {
+        return [0.0; 3];
+    }
+    let mut out = [0.0; 3];
+    for i in 0..3 {
+        out[i] = if c[i] == max {
+            s
+        } else if c[i] == min {
+            0.0
+        } else {
+            (c[i] - min) * s / (max - min)
+        };
+    }
+    out
+}
@@ -1,11 +1,17 @@
 //! Create image collages.
 
+use crate::blend::{self, BlendMode};
+use crate::crop::{smart_crop, CropStrategy};
 use crate::elements::*;
+use crate::filters::gaussian_blur;
+use crate::frames::{self, FrameStyle, Shadow};
+use crate::mask::{clip_image, Mask};
+use crate::resize::{self, Fit, Resampling};
 use crate::text::*;
-use crate::Rgb;
-use image::{DynamicImage, GenericImageView, Rgba};
-use imageproc::drawing::draw_filled_rect_mut;
-use imageproc::rect::Rect;
+use crate::{Rgb, Rgba};
+use image::{DynamicImage, GenericImageView};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 /// Two grid collage.
 /// # Arguments
@@ -19,20 +25,8 @@ pub fn two_grid(
     width: u32,
     height: u32,
 ) -> DynamicImage {
-    // Convert all photon images to DynamicImages, for interop with the image crate.
-    let imgs = vec![photon_img, photon_img2];
-
-    // distribute the width evenly by allocating the same space to both images
-    let img_width = width / 2;
-    let imgs = resize_imgs(imgs, img_width, height);
-
-    let mut container_img: DynamicImage = DynamicImage::new_rgba8(width, height);
-
-    image::imageops::overlay(&mut container_img, &imgs[0], 0, 0);
-    image::imageops::overlay(&mut container_img, &imgs[1], img_width, 0);
-
-    // return the collage
-    return container_img;
+    let imgs = [photon_img, photon_img2];
+    layout_collage(&imgs, &CollageLayout::row(2), width, height)
 }
 
 /// Four grid collage.
@@ -51,25 +45,12 @@ pub fn two_grid_text(
     // distribute the width evenly by allocating the same space to both images
     let img_width = width / 2;
     let img_height = height / 2;
-
-    let sampling_filter = image::imageops::FilterType::Nearest;
-    let image = image::DynamicImage::ImageRgba8(image::imageops::resize(
-        &image,
-        img_width,
-        img_height,
-        sampling_filter,
-    ));
-    let image2 = image::DynamicImage::ImageRgba8(image::imageops::resize(
-        &image2,
-        img_width,
-        img_height,
-        sampling_filter,
-    ));
-
-    let mut container_img: DynamicImage = DynamicImage::new_rgba8(width, height);
-
-    image::imageops::overlay(&mut container_img, &image, 0, 0);
-    image::imageops::overlay(&mut container_img, &image2, image.width(), image.height());
+    let layout = CollageLayout::with_cells(
+        vec![1.0, 1.0],
+        vec![1.0, 1.0],
+        vec![Cell::new(0, 0), Cell::new(1, 1)],
+    );
+    let mut container_img = layout_collage(&[&image, &image2], &layout, width, height);
 
     let lilac = Rgb {
         r: 204,
@@ -86,7 +67,7 @@ pub fn two_grid_text(
         &yellow,
         img_height,
         img_width,
-        image.width() as i32,
+        img_width as i32,
         0,
     );
     draw_solid_rect(
@@ -95,7 +76,7 @@ pub fn two_grid_text(
         img_height,
         img_width,
         0,
-        image.height() as i32,
+        img_height as i32,
     );
     let rgb_white = Rgb {
         r: 255,
@@ -106,7 +87,7 @@ pub fn two_grid_text(
     draw_text(
         &mut container_img,
         "Daisies In the Underground",
-        image.width() + 30,
+        img_width + 30,
         img_height / 2,
         "Roboto-Bold",
         30.0,
@@ -133,25 +114,12 @@ pub fn split_imgs_text(
     // distribute the width evenly by allocating the same space to both images
     let img_width = width / 2;
     let img_height = height / 2;
-
-    let sampling_filter = image::imageops::FilterType::Nearest;
-    let image = image::DynamicImage::ImageRgba8(image::imageops::resize(
-        &image,
-        img_width,
-        img_height,
-        sampling_filter,
-    ));
-    let image2 = image::DynamicImage::ImageRgba8(image::imageops::resize(
-        &image2,
-        img_width,
-        img_height,
-        sampling_filter,
-    ));
-
-    let mut container_img: DynamicImage = DynamicImage::new_rgba8(width, height);
-
-    image::imageops::overlay(&mut container_img, &image, img_width, 0);
-    image::imageops::overlay(&mut container_img, &image2, image.width(), image.height());
+    let layout = CollageLayout::with_cells(
+        vec![1.0, 1.0],
+        vec![1.0, 1.0],
+        vec![Cell::new(1, 0), Cell::new(1, 1)],
+    );
+    let mut container_img = layout_collage(&[&image, &image2], &layout, width, height);
 
     let white = Rgb {
         r: 255,
@@ -191,23 +159,8 @@ pub fn four_grid(
     width: u32,
     height: u32,
 ) -> DynamicImage {
-    let imgs = vec![photon_img, photon_img2, photon_img3, photon_img4];
-
-    // distribute the width evenly by allocating the same space to all images
-    let img_width = width / 2;
-    let img_height = height / 2;
-
-    let imgs = resize_imgs(imgs, img_width, img_height);
-
-    let mut container_img: DynamicImage = DynamicImage::new_rgba8(width, height);
-
-    image::imageops::overlay(&mut container_img, &imgs[0], 0, 0);
-    image::imageops::overlay(&mut container_img, &imgs[1], img_width, 0);
-    image::imageops::overlay(&mut container_img, &imgs[2], 0, img_height);
-    image::imageops::overlay(&mut container_img, &imgs[3], img_width, img_height);
-
-    // return the collage
-    return container_img;
+    let imgs = [photon_img, photon_img2, photon_img3, photon_img4];
+    layout_collage(&imgs, &CollageLayout::grid(2, 2), width, height)
 }
 
 /// Create a triple grid collage graphic.
@@ -225,20 +178,8 @@ pub fn triple_grid(
     width: u32,
     height: u32,
 ) -> DynamicImage {
-    let imgs = vec![photon_img, photon_img2, photon_img3];
-
-    // distribute the width evenly by allocating the same space to both images
-    let img_width = width / 3;
-    let imgs = resize_imgs(imgs, img_width, height);
-
-    let mut container_img: DynamicImage = DynamicImage::new_rgba8(width, height);
-
-    image::imageops::overlay(&mut container_img, &imgs[0], 0, 0);
-    image::imageops::overlay(&mut container_img, &imgs[1], img_width, 0);
-    image::imageops::overlay(&mut container_img, &imgs[2], img_width * 2, 0);
-
-    // return the collage
-    return container_img;
+    let imgs = [photon_img, photon_img2, photon_img3];
+    layout_collage(&imgs, &CollageLayout::row(3), width, height)
 }
 
 /// Four-image collage with a centre square containing text.
@@ -260,64 +201,45 @@ pub fn four_grid_center_square(
     width: u32,
     height: u32,
 ) -> DynamicImage {
-    let imgs = vec![photon_img, photon_img2, photon_img3, photon_img4];
-
-    // distribute the width evenly by allocating the same space to both images
-    let img_width = width / 2;
-    let img_height = height / 2;
-
-    let imgs = resize_imgs(imgs, img_width, img_height);
-
-    let mut container_img: DynamicImage = DynamicImage::new_rgba8(width, height);
-
-    image::imageops::overlay(&mut container_img, &imgs[0], 0, 0);
-    image::imageops::overlay(&mut container_img, &imgs[1], img_width, 0);
-    image::imageops::overlay(&mut container_img, &imgs[2], 0, img_height);
-    image::imageops::overlay(&mut container_img, &imgs[3], img_width, img_height);
-
-    let white_rgb = Rgb {
-        r: 255,
-        g: 255,
-        b: 255,
+    let caption = Caption {
+        font: "BebasKai".to_string(),
+        position: CaptionPosition::Rect(0.3, 0.15, 0.3, 0.8),
+        ..Caption::new(text)
     };
-    let black_rgb = Rgb { r: 0, g: 0, b: 0 };
-    // Draw a square in the center
-
-    let mut height_mul: f32 = 0.2;
-    let mut word_vec = vec![];
-    // Split words
-    for word in text.split_whitespace() {
-        println!("> {}", word);
-        if word.len() > 7 {
-            word_vec.push(word);
-            continue;
-        } else {
-            word_vec.push(word);
-        }
-    }
-    draw_solid_rect(
-        &mut container_img,
-        &white_rgb,
-        (width as f32 * 0.3) as u32,
-        (height as f32 * 0.8) as u32,
-        (width as f32 * 0.3) as i32,
-        (height as f32 * 0.15) as i32,
-    );
-
-    for word in word_vec {
-        draw_text(
-            &mut container_img,
-            word,
-            (width as f32 * 0.32) as u32,
-            (height as f32 * height_mul) as u32,
-            "BebasKai",
-            100.0,
-            &black_rgb,
-        );
-        height_mul += 0.15;
-    }
+    four_grid_with_caption(
+        photon_img,
+        photon_img2,
+        photon_img3,
+        photon_img4,
+        &caption,
+        width,
+        height,
+    )
+}
 
-    return container_img;
+/// Four grid collage with a caption drawn over it.
+///
+/// # Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `img2` - A mutable ref to a DynamicImage.
+/// * `img3` - A mutable ref to a DynamicImage.
+/// * `img4` - A mutable ref to a DynamicImage.
+/// * `caption` - Caption for the graphic, positioned relative to the whole collage.
+/// * `width` - u32 - Desired width of final graphic
+/// * `height` - u32 - Desired height of final graphic
+pub fn four_grid_with_caption(
+    photon_img: &DynamicImage,
+    photon_img2: &DynamicImage,
+    photon_img3: &DynamicImage,
+    photon_img4: &DynamicImage,
+    caption: &Caption,
+    width: u32,
+    height: u32,
+) -> DynamicImage {
+    let imgs = [photon_img, photon_img2, photon_img3, photon_img4];
+    let mut container_img = layout_collage(&imgs, &CollageLayout::grid(2, 2), width, height);
+    draw_caption(&mut container_img, caption, (0, 0, width, height));
+    container_img
 }
 
 /// Create a moodboard style graphic with 4 images within a collage.
@@ -339,67 +261,329 @@ pub fn moodboard(
     width: u32,
     height: u32,
 ) -> DynamicImage {
-    // Exclude the first image, since it will have different dimensions when resized.
-    let imgs = vec![photon_img2, photon_img3, photon_img4];
+    let caption = Caption {
+        font: "Oswald-Regular".to_string(),
+        padding: (width as f32 * 0.05) as u32,
+        ..Caption::new(text)
+    };
+    moodboard_with_caption(
+        photon_img,
+        photon_img2,
+        photon_img3,
+        photon_img4,
+        &caption,
+        width,
+        height,
+    )
+}
 
-    // distribute the width evenly by allocating the same space to both images
-    let img_width = width / 4;
-    let img_height = height / 4;
+/// Create a moodboard style graphic with 4 images and a configurable caption.
+///
+/// The main image fills the top four fifths of the graphic, and the caption usually sits along the
+/// bottom quarter, overlapping it.
+///
+/// # Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `img2` - A mutable ref to a DynamicImage.
+/// * `img3` - A mutable ref to a DynamicImage.
+/// * `img4` - A mutable ref to a DynamicImage.
+/// * `caption` - Caption for the graphic, positioned relative to the whole collage.
+/// * `width` - u32 - Desired width of final graphic
+/// * `height` - u32 - Desired height of final graphic
+pub fn moodboard_with_caption(
+    photon_img: &DynamicImage,
+    photon_img2: &DynamicImage,
+    photon_img3: &DynamicImage,
+    photon_img4: &DynamicImage,
+    caption: &Caption,
+    width: u32,
+    height: u32,
+) -> DynamicImage {
+    // The main image spans three quarters of the width and four fifths of the height, with the
+    // others stacked beside it, each a quarter of the height.
+    let imgs = [photon_img, photon_img2, photon_img3, photon_img4];
+    let layout = CollageLayout::with_cells(
+        vec![3.0, 1.0],
+        vec![5.0, 5.0, 5.0, 1.0, 4.0],
+        vec![
+            Cell::span(0, 0, 1, 4),
+            Cell::new(1, 0),
+            Cell::new(1, 1),
+            Cell::new(1, 2),
+        ],
+    );
+    let mut container_img = layout_collage(&imgs, &layout, width, height);
+    draw_caption(&mut container_img, caption, (0, 0, width, height));
+    container_img
+}
 
-    let first_img_width: u32 = width - img_width;
-    let sampling_filter = image::imageops::FilterType::Nearest;
+/// Justified collage, where photos are laid out in rows like a photo gallery.
+///
+/// Every photo in a row has the same height, and each row fills the full width.
+/// The number of rows is chosen so that the photos keep close to their own aspect ratios,
+/// and any difference is made up by cropping them slightly.
+///
+/// # Arguments
+/// * `imgs` - Photos to be placed into the collage, in order.
+/// * `gutter` - Space between neighbouring photos, in pixels.
+/// * `width` - u32 - Desired width of final graphic
+/// * `height` - u32 - Desired height of final graphic
+pub fn justified_collage(
+    imgs: &[DynamicImage],
+    gutter: u32,
+    width: u32,
+    height: u32,
+) -> DynamicImage {
+    let aspects: Vec<f32> = imgs.iter().map(aspect_ratio).collect();
+    let rows = justified_rows(&aspects, gutter, width, height);
+
+    let row_heights: Vec<f32> = rows
+        .iter()
+        .map(|row| row_height(&aspects[row.clone()], gutter, width))
+        .collect();
+    let row_edges = track_edges(&row_heights, height, 0, gutter);
+
+    let mut rects = Vec::with_capacity(imgs.len());
+    for (row, (y, end_y)) in rows.iter().zip(row_edges) {
+        for (x, end_x) in track_edges(&aspects[row.clone()], width, 0, gutter) {
+            rects.push((x, y, end_x - x, end_y - y));
+        }
+    }
 
-    let image = image::DynamicImage::ImageRgba8(image::imageops::resize(
-        photon_img,
-        first_img_width,
-        (height as f32 * 0.8) as u32,
-        sampling_filter,
-    ));
+    let imgs: Vec<&DynamicImage> = imgs.iter().collect();
+    let mut container_img = DynamicImage::new_rgba8(width, height);
+    let fits = vec![Fit::default(); imgs.len()];
+    place_images(&mut container_img, &imgs, &rects, &fits, 0.0);
+    container_img
+}
 
-    let imgs = resize_imgs(imgs, img_width, img_height);
+/// Masonry collage, where photos are stacked in columns of equal width like a pinboard.
+///
+/// Each photo goes into the shortest column so far, and each column fills the full height.
+/// The number of columns is chosen so that the photos keep close to their own aspect ratios,
+/// and any difference is made up by cropping them slightly.
+///
+/// # Arguments
+/// * `imgs` - Photos to be placed into the collage, in order.
+/// * `gutter` - Space between neighbouring photos, in pixels.
+/// * `width` - u32 - Desired width of final graphic
+/// * `height` - u32 - Desired height of final graphic
+pub fn masonry_collage(
+    imgs: &[DynamicImage],
+    gutter: u32,
+    width: u32,
+    height: u32,
+) -> DynamicImage {
+    let aspects: Vec<f32> = imgs.iter().map(aspect_ratio).collect();
+
+    // Try each number of columns, and keep whichever needs the least cropping.
+    let columns = (1..=imgs.len().max(1))
+        .map(|count| masonry_columns(&aspects, count))
+        .min_by(|a, b| {
+            let cost_a = masonry_cost(&aspects, a, gutter, width, height);
+            let cost_b = masonry_cost(&aspects, b, gutter, width, height);
+            cost_a.total_cmp(&cost_b)
+        })
+        .unwrap_or_default();
+
+    let mut rects = vec![(0, 0, 0, 0); imgs.len()];
+    let column_edges = track_edges(&vec![1.0; columns.len()], width, 0, gutter);
+    for (column, (x, end_x)) in columns.iter().zip(column_edges) {
+        let heights: Vec<f32> = column.iter().map(|&i| 1.0 / aspects[i]).collect();
+        for (&i, (y, end_y)) in column.iter().zip(track_edges(&heights, height, 0, gutter)) {
+            rects[i] = (x, y, end_x - x, end_y - y);
+        }
+    }
 
-    let mut container_img: DynamicImage = DynamicImage::new_rgba8(width, height);
+    let imgs: Vec<&DynamicImage> = imgs.iter().collect();
+    let mut container_img = DynamicImage::new_rgba8(width, height);
+    let fits = vec![Fit::default(); imgs.len()];
+    place_images(&mut container_img, &imgs, &rects, &fits, 0.0);
+    container_img
+}
 
-    image::imageops::overlay(&mut container_img, &image, 0, 0);
-    image::imageops::overlay(&mut container_img, &imgs[0], first_img_width, 0);
-    image::imageops::overlay(&mut container_img, &imgs[1], first_img_width, img_height);
-    image::imageops::overlay(
-        &mut container_img,
-        &imgs[2],
-        first_img_width,
-        img_height * 2,
-    );
+/// How images are framed in a framed collage.
+#[derive(Debug, Clone)]
+pub struct FrameOptions {
+    pub style: FrameStyle,
+    /// Shadow cast by each framed image, if any.
+    pub shadow: Option<Shadow>,
+    /// Largest angle, in degrees, that each image is tilted by, clockwise or anticlockwise.
+    pub max_rotation: f32,
+    /// Seed for the random tilts, so that the same seed always gives the same collage.
+    pub seed: u64,
+    /// Caption written beneath each image, in the same order as the images. Only used by polaroids.
+    pub captions: Vec<Option<String>>,
+}
 
-    let white_rgb = Rgb {
-        r: 255,
-        g: 255,
-        b: 255,
-    };
-    let black_rgb = Rgb { r: 0, g: 0, b: 0 };
-    // Draw a square in the center
+impl FrameOptions {
+    /// Frame images with the given style and a soft shadow, without tilting them.
+    pub fn new(style: FrameStyle) -> FrameOptions {
+        FrameOptions {
+            style,
+            shadow: Some(Shadow::default()),
+            max_rotation: 0.0,
+            seed: 0,
+            captions: vec![],
+        }
+    }
+}
 
-    let height_mul: f32 = 0.75;
+/// Create a collage where each image is framed, and optionally tilted and given a shadow.
+///
+/// Images keep their aspect ratios, and are scaled so that the framed, tilted image and its shadow
+/// fit within its cell. The layout's `fit` and `corner_radius` are not used.
+///
+/// # Arguments
+/// * `imgs` - Images to be placed into the collage, in order.
+/// * `layout` - Grid, spacing and background of the collage.
+/// * `frame` - Style of frame, shadow and tilt.
+/// * `width` - u32 - Desired width of final graphic
+/// * `height` - u32 - Desired height of final graphic
+pub fn framed_collage(
+    imgs: &[DynamicImage],
+    layout: &CollageLayout,
+    frame: &FrameOptions,
+    width: u32,
+    height: u32,
+) -> DynamicImage {
+    let background: image::Rgba<u8> = layout.background.into();
+    let mut container_img =
+        DynamicImage::ImageRgba8(image::ImageBuffer::from_pixel(width, height, background));
+    let mut rng = StdRng::seed_from_u64(frame.seed);
+    let (top, right, bottom, left) = frame.style.borders();
+    let padding = frame
+        .shadow
+        .as_ref()
+        .map(frames::shadow_padding)
+        .unwrap_or(0);
+
+    for (i, (img, (x, y, cell_width, cell_height))) in imgs
+        .iter()
+        .zip(layout.cell_rects(width, height))
+        .enumerate()
+    {
+        let degrees = if frame.max_rotation > 0.0 {
+            rng.gen_range(-frame.max_rotation..=frame.max_rotation)
+        } else {
+            0.0
+        };
+
+        // Size of the framed image for each pixel of the photo's width, and its bounds once tilted.
+        let aspect = aspect_ratio(img);
+        let (bounds_width, bounds_height) =
+            frames::rotated_size(1.0 + left + right, 1.0 / aspect + top + bottom, degrees);
+        let available_width = cell_width.saturating_sub(2 * padding) as f32;
+        let available_height = cell_height.saturating_sub(2 * padding) as f32;
+        let photo_width = (available_width / bounds_width).min(available_height / bounds_height);
+        let photo_height = photo_width / aspect;
+        if photo_width < 1.0 || photo_height < 1.0 {
+            continue;
+        }
 
-    draw_solid_rect(
-        &mut container_img,
-        &white_rgb,
-        width as u32,
-        (height as f32 * 0.3) as u32,
-        0 as i32,
-        (height as f32 * 0.75) as i32,
-    );
+        let photo = resize::resample(
+            img,
+            photo_width.round() as u32,
+            photo_height.round() as u32,
+            Resampling::default(),
+        );
+        let caption = frame.captions.get(i).and_then(|c| c.as_deref());
+        let mut framed = frames::frame_image(&photo, &frame.style, caption);
+        if degrees != 0.0 {
+            framed = frames::rotate(&framed, degrees);
+        }
+        if let Some(shadow) = &frame.shadow {
+            framed = frames::drop_shadow(&framed, shadow).0;
+        }
 
-    draw_text(
-        &mut container_img,
-        text,
-        (width as f32 * 0.10) as u32,
-        (height as f32 * height_mul) as u32,
-        "Oswald-Regular",
-        100.0,
-        &black_rgb,
-    );
+        // Centre the framed image within its cell.
+        let offset_x = (cell_width as i32 - framed.width() as i32) / 2;
+        let offset_y = (cell_height as i32 - framed.height() as i32) / 2;
+        blend::composite(
+            &mut container_img,
+            &framed,
+            x as i32 + offset_x,
+            y as i32 + offset_y,
+            1.0,
+        );
+    }
+    container_img
+}
 
-    return container_img;
+/// Create a collage of polaroids, scattered in a loose grid with random tilts and handwritten captions.
+///
+/// Photos are cropped to squares, keeping their most distinctive region.
+///
+/// # Arguments
+/// * `imgs` - Photos to be placed into the collage, in order.
+/// * `captions` - Caption written beneath each photo, in the same order. May be shorter than `imgs`.
+/// * `seed` - Seed for the random tilts, so that the same seed always gives the same collage.
+/// * `width` - u32 - Desired width of final graphic
+/// * `height` - u32 - Desired height of final graphic
+pub fn polaroid_collage(
+    imgs: &[DynamicImage],
+    captions: &[&str],
+    seed: u64,
+    width: u32,
+    height: u32,
+) -> DynamicImage {
+    let squares: Vec<DynamicImage> = imgs
+        .iter()
+        .map(|img| {
+            let side = img.width().min(img.height());
+            smart_crop(img, side, side, CropStrategy::Saliency)
+        })
+        .collect();
+
+    // Choose the number of columns so that the cells are roughly the shape of a polaroid.
+    let count = imgs.len().max(1) as f32;
+    let columns = (count * width as f32 / height as f32 / 0.85)
+        .sqrt()
+        .round()
+        .clamp(1.0, count);
+    let rows = (count / columns).ceil();
+    let layout = CollageLayout::grid(columns as u32, rows as u32);
+
+    let frame = FrameOptions {
+        max_rotation: 6.0,
+        seed,
+        captions: captions.iter().map(|c| Some(c.to_string())).collect(),
+        ..FrameOptions::new(FrameStyle::Polaroid(Rgba::new(252, 252, 248, 255)))
+    };
+    framed_collage(&squares, &layout, &frame, width, height)
+}
+
+/// Create a film strip collage, with the photos in a row of frames along a strip of film.
+///
+/// Photos are cropped to fill their frames, keeping their most distinctive region.
+///
+/// # Arguments
+/// * `imgs` - Photos to be placed into the collage, in order.
+/// * `width` - u32 - Desired width of final graphic
+/// * `height` - u32 - Desired height of final graphic
+pub fn film_strip_collage(imgs: &[DynamicImage], width: u32, height: u32) -> DynamicImage {
+    let mut container_img = DynamicImage::new_rgba8(width, height);
+    let (top, right, bottom, left) = FrameStyle::FilmStrip.borders();
+    let frame_edges = track_edges(&vec![1.0; imgs.len()], width, 0, 0);
+
+    for (img, (x, end_x)) in imgs.iter().zip(frame_edges) {
+        // Borders are sized from the photo's width, so work back from the width of the frame.
+        let frame_width = end_x - x;
+        let photo_width = (frame_width as f32 / (1.0 + left + right)).round() as u32;
+        let border_top = (top * photo_width as f32).round() as u32;
+        let border_bottom = (bottom * photo_width as f32).round() as u32;
+        let photo_height = height.saturating_sub(border_top + border_bottom);
+        if photo_width == 0 || photo_height == 0 {
+            continue;
+        }
+
+        let photo = resize::resize_to_fit(img, photo_width, photo_height, Fit::default());
+        let framed = frames::frame_image(&photo, &FrameStyle::FilmStrip, None);
+        // Stretch any rounding difference, so that neighbouring frames meet.
+        let framed = resize::resample(&framed, frame_width, height, Resampling::default());
+        blend::composite(&mut container_img, &framed, x as i32, 0, 1.0);
+    }
+    container_img
 }
 
 /// Three-image collage containing main text, and a feature-style grid.
@@ -419,30 +603,16 @@ pub fn feature_grid(
     width: u32,
     height: u32,
 ) -> DynamicImage {
-    let imgs = vec![photon_img, photon_img2, photon_img3];
-
-    // distribute the width evenly by allocating the same space to all images
-    let img_width = width / 2;
-    let img_height = height / 2;
-
-    let imgs = resize_imgs(imgs, img_width, img_height);
-
-    let mut container_img: DynamicImage = DynamicImage::new_rgba8(width, height);
-    let white = Rgb {
-        r: 255,
-        g: 255,
-        b: 255,
-    };
+    let imgs = [photon_img, photon_img2, photon_img3];
 
-    draw_filled_rect_mut(
-        &mut container_img,
-        Rect::at(0, 0).of_size((width / 2) as u32, (height / 2) as u32),
-        Rgba([white.r, white.g, white.b, 255u8]),
+    // The top-left cell is left empty, and filled with white for the text.
+    let mut layout = CollageLayout::with_cells(
+        vec![1.0, 1.0],
+        vec![1.0, 1.0],
+        vec![Cell::new(1, 0), Cell::new(0, 1), Cell::new(1, 1)],
     );
-
-    image::imageops::overlay(&mut container_img, &imgs[0], img_width, 0);
-    image::imageops::overlay(&mut container_img, &imgs[1], 0, img_height);
-    image::imageops::overlay(&mut container_img, &imgs[2], img_width, img_height);
+    layout.background = Rgba::new(255, 255, 255, 255);
+    let mut container_img = layout_collage(&imgs, &layout, width, height);
 
     let black_rgb = Rgb { r: 0, g: 0, b: 0 };
     draw_text(
@@ -457,13 +627,12 @@ pub fn feature_grid(
     return container_img;
 }
 
-/// Triple-image collage with a centre square containing text.
+/// Triple-image collage with a band of text underneath.
 ///
 /// # Arguments
 /// * `img` - A mutable ref to a DynamicImage.
 /// * `img2` - A mutable ref to a DynamicImage.
 /// * `img3` - A mutable ref to a DynamicImage.
-/// * `img4` - A mutable ref to a DynamicImage.
 /// * `text` - Main heading for the graphic.
 /// * `width` - u32 - Desired width of final graphic
 /// * `height` - u32 - Desired height of final graphic
@@ -475,47 +644,58 @@ pub fn triple_grid_text(
     width: u32,
     height: u32,
 ) -> DynamicImage {
-    let imgs = vec![photon_img, photon_img2, photon_img3];
-
-    // distribute the width evenly by allocating the same space to both images
-    let img_width = width / 3;
-    let img_height = (height as f32 * 0.8) as u32;
-    let imgs = resize_imgs(imgs, img_width, img_height);
-
-    let mut container_img: DynamicImage = DynamicImage::new_rgba8(width, height);
-    let white = Rgb {
-        r: 255,
-        g: 255,
-        b: 255,
+    let caption = Caption {
+        font: "Montserrat-Regular".to_string(),
+        max_font_size: 90.0,
+        padding: (width as f32 * 0.05) as u32,
+        ..Caption::new(text)
     };
+    triple_grid_with_caption(
+        photon_img,
+        photon_img2,
+        photon_img3,
+        &caption,
+        width,
+        height,
+    )
+}
 
-    image::imageops::overlay(&mut container_img, &imgs[0], 0, 0);
-    image::imageops::overlay(&mut container_img, &imgs[1], img_width, 0);
-    image::imageops::overlay(&mut container_img, &imgs[2], img_width * 2, 0);
-
-    let black_rgb = Rgb { r: 0, g: 0, b: 0 };
-
-    draw_solid_rect(
-        &mut container_img,
-        &white,
-        width as u32,
-        (height as f32 * 0.3) as u32,
-        0 as i32,
-        (height as f32 * 0.75) as i32,
-    );
-    draw_text(
-        &mut container_img,
-        text,
-        (width as f32 * 0.05) as u32,
-        (height as f32 * 0.8) as u32,
-        "Montserrat-Regular",
-        90.0,
-        &black_rgb,
+/// Triple-image collage with a configurable caption.
+///
+/// # Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `img2` - A mutable ref to a DynamicImage.
+/// * `img3` - A mutable ref to a DynamicImage.
+/// * `caption` - Caption for the graphic, positioned relative to the whole collage.
+/// * `width` - u32 - Desired width of final graphic
+/// * `height` - u32 - Desired height of final graphic
+pub fn triple_grid_with_caption(
+    photon_img: &DynamicImage,
+    photon_img2: &DynamicImage,
+    photon_img3: &DynamicImage,
+    caption: &Caption,
+    width: u32,
+    height: u32,
+) -> DynamicImage {
+    let imgs = [photon_img, photon_img2, photon_img3];
+    let layout = CollageLayout::with_cells(
+        vec![1.0, 1.0, 1.0],
+        vec![0.8, 0.2],
+        vec![Cell::new(0, 0), Cell::new(1, 0), Cell::new(2, 0)],
     );
-
-    return container_img;
+    let mut container_img = layout_collage(&imgs, &layout, width, height);
+    draw_caption(&mut container_img, caption, (0, 0, width, height));
+    container_img
 }
 
+/// Six-image collage, with a band of text between two rows of images.
+///
+/// # Arguments
+/// * `img` to `img6` - Refs to DynamicImages, filling the top row then the bottom row.
+/// * `text` - Main heading for the graphic.
+/// * `width` - u32 - Desired width of final graphic
+/// * `height` - u32 - Desired height of final graphic
+#[allow(clippy::too_many_arguments)]
 pub fn six_grid_text(
     photon_img: &DynamicImage,
     photon_img2: &DynamicImage,
@@ -527,73 +707,548 @@ pub fn six_grid_text(
     width: u32,
     height: u32,
 ) -> DynamicImage {
-    let imgs = vec![
-        photon_img,
-        photon_img2,
-        photon_img3,
-        photon_img4,
-        photon_img5,
-        photon_img6,
-    ];
+    let caption = Caption {
+        font: "Montserrat-Regular".to_string(),
+        max_font_size: 90.0,
+        position: CaptionPosition::Centre(1.0 / 3.0),
+        padding: (width as f32 * 0.05) as u32,
+        ..Caption::new(text)
+    };
+    six_grid_with_caption(
+        [
+            photon_img,
+            photon_img2,
+            photon_img3,
+            photon_img4,
+            photon_img5,
+            photon_img6,
+        ],
+        &caption,
+        width,
+        height,
+    )
+}
 
-    // distribute the width evenly by allocating the same space to both images
-    let img_width = width / 3;
-    let img_height = height / 3;
-    let imgs = resize_imgs(imgs, img_width, img_height);
+/// Six-image collage, with images in the top and bottom rows and a configurable caption.
+///
+/// # Arguments
+/// * `imgs` - Refs to six DynamicImages, filling the top row then the bottom row.
+/// * `caption` - Caption for the graphic, positioned relative to the whole collage.
+/// * `width` - u32 - Desired width of final graphic
+/// * `height` - u32 - Desired height of final graphic
+pub fn six_grid_with_caption(
+    imgs: [&DynamicImage; 6],
+    caption: &Caption,
+    width: u32,
+    height: u32,
+) -> DynamicImage {
+    // Images fill the top and bottom rows, leaving the middle row for the caption.
+    let mut layout = CollageLayout::grid(3, 3);
+    layout.cells = (0..3)
+        .map(|column| Cell::new(column, 0))
+        .chain((0..3).map(|column| Cell::new(column, 2)))
+        .collect();
+    let mut container_img = layout_collage(&imgs, &layout, width, height);
+    draw_caption(&mut container_img, caption, (0, 0, width, height));
+    container_img
+}
 
-    let mut container_img: DynamicImage = DynamicImage::new_rgba8(width, height);
-    let white = Rgb {
-        r: 255,
-        g: 255,
-        b: 255,
-    };
+/// Position of an image within a collage grid, and how many columns and rows it spans.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub column: u32,
+    pub row: u32,
+    pub column_span: u32,
+    pub row_span: u32,
+}
 
-    image::imageops::overlay(&mut container_img, &imgs[0], 0, 0);
-    image::imageops::overlay(&mut container_img, &imgs[1], img_width, 0);
-    image::imageops::overlay(&mut container_img, &imgs[2], img_width * 2, 0);
-    image::imageops::overlay(&mut container_img, &imgs[3], 0, img_height * 2);
-    image::imageops::overlay(&mut container_img, &imgs[4], img_width, img_height * 2);
-    image::imageops::overlay(&mut container_img, &imgs[5], img_width * 2, img_height * 2);
+impl Cell {
+    /// A cell occupying a single column and row.
+    pub fn new(column: u32, row: u32) -> Cell {
+        Cell::span(column, row, 1, 1)
+    }
 
-    let black_rgb = Rgb { r: 0, g: 0, b: 0 };
+    /// A cell spanning several columns and/or rows, starting from `column` and `row`.
+    pub fn span(column: u32, row: u32, column_span: u32, row_span: u32) -> Cell {
+        Cell {
+            column,
+            row,
+            column_span: column_span.max(1),
+            row_span: row_span.max(1),
+        }
+    }
+}
 
-    draw_solid_rect(
-        &mut container_img,
-        &white,
-        width as u32,
-        img_height,
-        0 as i32,
-        img_height as i32,
-    );
-    draw_text(
+/// Layout of a collage: a grid of columns and rows, with spacing, a background and rounded corners.
+///
+/// Images are placed into `cells` in order. If no cells are given, images flow into the grid
+/// left to right, top to bottom, one per cell.
+#[derive(Debug, Clone)]
+pub struct CollageLayout {
+    /// Relative widths of the columns, e.g. `vec![2.0, 1.0]` makes the first column twice as wide.
+    pub columns: Vec<f32>,
+    /// Relative heights of the rows.
+    pub rows: Vec<f32>,
+    /// Cells to place the images into, in order.
+    pub cells: Vec<Cell>,
+    /// Space between neighbouring cells, in pixels.
+    pub gutter: u32,
+    /// Space between the cells and the edge of the collage, in pixels.
+    pub margin: u32,
+    /// Colour behind the images, visible in the gutters, margins and empty cells.
+    pub background: Rgba,
+    /// Radius of each image's rounded corners, in pixels.
+    pub corner_radius: f32,
+    /// How images are fitted into cells with a different aspect ratio: cropped to fill (cover),
+    /// letterboxed (contain) or stretched.
+    pub fit: Fit,
+    /// Point to keep in frame when cropping each image, as fractions of its width and height,
+    /// in the same order as the images. Overrides the crop strategy of `Fit::Cover` for that image.
+    pub focal_points: Vec<Option<(f32, f32)>>,
+    /// Caption to draw over each image's cell, in the same order as the images.
+    pub captions: Vec<Option<Caption>>,
+}
+
+impl CollageLayout {
+    /// A grid of equally-sized columns and rows, with no spacing and a transparent background.
+    pub fn grid(columns: u32, rows: u32) -> CollageLayout {
+        CollageLayout {
+            columns: vec![1.0; columns.max(1) as usize],
+            rows: vec![1.0; rows.max(1) as usize],
+            cells: vec![],
+            gutter: 0,
+            margin: 0,
+            background: Rgba::new(0, 0, 0, 0),
+            corner_radius: 0.0,
+            fit: Fit::default(),
+            focal_points: vec![],
+            captions: vec![],
+        }
+    }
+
+    /// A single row of images, side by side.
+    pub fn row(images: u32) -> CollageLayout {
+        CollageLayout::grid(images, 1)
+    }
+
+    /// A single column of images, stacked on top of each other.
+    pub fn column(images: u32) -> CollageLayout {
+        CollageLayout::grid(1, images)
+    }
+
+    /// A grid with columns and rows of the given relative sizes, and images placed into specific cells.
+    pub fn with_cells(columns: Vec<f32>, rows: Vec<f32>, cells: Vec<Cell>) -> CollageLayout {
+        CollageLayout {
+            columns,
+            rows,
+            cells,
+            ..CollageLayout::grid(1, 1)
+        }
+    }
+
+    // The cells images are placed into, either those given or one per grid cell in reading order.
+    fn placements(&self) -> Vec<Cell> {
+        if !self.cells.is_empty() {
+            return self.cells.clone();
+        }
+        let columns = self.columns.len() as u32;
+        (0..self.rows.len() as u32)
+            .flat_map(|row| (0..columns).map(move |column| Cell::new(column, row)))
+            .collect()
+    }
+
+    /// How the image at `index` is fitted into its cell, taking its focal point into account.
+    pub fn fit_for(&self, index: usize) -> Fit {
+        match (self.fit, self.focal_points.get(index)) {
+            (Fit::Cover(_), Some(&Some((x, y)))) => Fit::Cover(CropStrategy::FocalPoint(x, y)),
+            (fit, _) => fit,
+        }
+    }
+
+    /// The position and size of each cell in a collage of the given size, as `(x, y, width, height)`.
+    /// Cells are returned in the order images are placed into them. Cells outside the grid have no size.
+    pub fn cell_rects(&self, width: u32, height: u32) -> Vec<(u32, u32, u32, u32)> {
+        let column_edges = track_edges(&self.columns, width, self.margin, self.gutter);
+        let row_edges = track_edges(&self.rows, height, self.margin, self.gutter);
+
+        self.placements()
+            .iter()
+            .map(|cell| {
+                let last_column = (cell.column + cell.column_span - 1) as usize;
+                let last_row = (cell.row + cell.row_span - 1) as usize;
+                if last_column >= column_edges.len() || last_row >= row_edges.len() {
+                    return (0, 0, 0, 0);
+                }
+                let (x, _) = column_edges[cell.column as usize];
+                let (y, _) = row_edges[cell.row as usize];
+                let (_, end_x) = column_edges[last_column];
+                let (_, end_y) = row_edges[last_row];
+                (x, y, end_x.saturating_sub(x), end_y.saturating_sub(y))
+            })
+            .collect()
+    }
+}
+
+/// Create a collage from any number of images, arranged by a layout.
+///
+/// Each image is fitted into its cell according to the layout's `fit`, which by default crops
+/// it to fill the cell without distorting it. Images beyond the number of cells are left out,
+/// and cells without an image show the background.
+///
+/// # Arguments
+/// * `imgs` - Images to be placed into the collage, in order.
+/// * `layout` - Grid, spacing, background and corners of the collage.
+/// * `width` - u32 - Desired width of final graphic
+/// * `height` - u32 - Desired height of final graphic
+///
+/// # Example
+/// ```ignore
+/// // A 3x2 grid with white gutters, where the first image spans both rows.
+/// let mut layout = CollageLayout::grid(3, 2);
+/// layout.cells = vec![Cell::span(0, 0, 1, 2), Cell::new(1, 0), Cell::new(2, 0), Cell::new(1, 1), Cell::new(2, 1)];
+/// layout.gutter = 10;
+/// layout.margin = 10;
+/// layout.background = Rgba::new(255, 255, 255, 255);
+/// let img = create_collage(&photos, &layout, 1200, 800);
+/// ```
+pub fn create_collage(
+    imgs: &[DynamicImage],
+    layout: &CollageLayout,
+    width: u32,
+    height: u32,
+) -> DynamicImage {
+    let imgs: Vec<&DynamicImage> = imgs.iter().collect();
+    layout_collage(&imgs, layout, width, height)
+}
+
+// Shared by `create_collage` and the fixed layouts, which hold references to their images.
+fn layout_collage(
+    imgs: &[&DynamicImage],
+    layout: &CollageLayout,
+    width: u32,
+    height: u32,
+) -> DynamicImage {
+    let background: image::Rgba<u8> = layout.background.into();
+    let mut container_img =
+        DynamicImage::ImageRgba8(image::ImageBuffer::from_pixel(width, height, background));
+
+    let rects = layout.cell_rects(width, height);
+    let fits: Vec<Fit> = (0..imgs.len()).map(|i| layout.fit_for(i)).collect();
+    place_images(
         &mut container_img,
-        text,
-        (width as f32 * 0.05) as u32,
-        (img_height + (img_height as f32 * 0.3) as u32) as u32,
-        "Montserrat-Regular",
-        90.0,
-        &black_rgb,
+        imgs,
+        &rects,
+        &fits,
+        layout.corner_radius,
     );
+    for (caption, &rect) in layout.captions.iter().zip(&rects) {
+        if let Some(caption) = caption {
+            draw_caption(&mut container_img, caption, rect);
+        }
+    }
+    container_img
+}
 
-    return container_img;
+// Resize each image to fit its rect, and draw it.
+fn place_images(
+    container_img: &mut DynamicImage,
+    imgs: &[&DynamicImage],
+    rects: &[(u32, u32, u32, u32)],
+    fits: &[Fit],
+    corner_radius: f32,
+) {
+    for ((img, &(x, y, cell_width, cell_height)), &fit) in imgs.iter().zip(rects).zip(fits) {
+        if cell_width == 0 || cell_height == 0 {
+            continue;
+        }
+        let mut resized = resize::resize_to_fit(img, cell_width, cell_height, fit);
+        if corner_radius > 0.0 {
+            let mask = Mask::rounded_rect(cell_width, cell_height, corner_radius);
+            clip_image(&mut resized, &mask);
+        }
+        blend::composite(container_img, &resized, x as i32, y as i32, 1.0);
+    }
 }
 
-// Resize images in a vec, returns a new vec with resized images.
-fn resize_imgs(imgs: Vec<&DynamicImage>, img_width: u32, img_height: u32) -> Vec<DynamicImage> {
-    let sampling_filter = image::imageops::FilterType::Nearest;
-    let mut resized_imgs = vec![];
+fn aspect_ratio(img: &DynamicImage) -> f32 {
+    let (width, height) = img.dimensions();
+    width.max(1) as f32 / height.max(1) as f32
+}
 
-    for i in 0..imgs.len() {
-        let item = imgs[i];
-        let image = image::DynamicImage::ImageRgba8(image::imageops::resize(
-            item,
-            img_width,
-            img_height,
-            sampling_filter,
-        ));
+// Height of a row of images which exactly fills the width.
+fn row_height(aspects: &[f32], gutter: u32, width: u32) -> f32 {
+    let gutters = gutter * (aspects.len() as u32).saturating_sub(1);
+    width.saturating_sub(gutters) as f32 / aspects.iter().sum::<f32>()
+}
 
-        resized_imgs.push(image);
+// Split images into rows for a justified collage, as ranges of their indices.
+// Each possible number of rows is tried, and whichever total height is closest to the
+// desired height is kept, so that the rows need the least stretching.
+fn justified_rows(
+    aspects: &[f32],
+    gutter: u32,
+    width: u32,
+    height: u32,
+) -> Vec<std::ops::Range<usize>> {
+    let total: f32 = aspects.iter().sum();
+    let mut best = (f32::MAX, vec![]);
+
+    for count in 1..=aspects.len() {
+        // Fill each row until adding another image would take it further from an even share.
+        let target = total / count as f32;
+        let mut rows = vec![];
+        let (mut start, mut sum) = (0, 0.0);
+        for (i, &aspect) in aspects.iter().enumerate() {
+            if i > start && (sum + aspect - target).abs() > (sum - target).abs() {
+                rows.push(start..i);
+                start = i;
+                sum = 0.0;
+            }
+            sum += aspect;
+        }
+        rows.push(start..aspects.len());
+
+        let gutters = gutter * (rows.len() as u32 - 1);
+        let natural: f32 = rows
+            .iter()
+            .map(|row| row_height(&aspects[row.clone()], gutter, width))
+            .sum();
+        let cost = (natural / height.saturating_sub(gutters).max(1) as f32)
+            .ln()
+            .abs();
+        if cost < best.0 {
+            best = (cost, rows);
+        }
+    }
+    best.1
+}
+
+// Assign images to columns for a masonry collage, each going into the shortest column so far.
+fn masonry_columns(aspects: &[f32], count: usize) -> Vec<Vec<usize>> {
+    let mut columns = vec![vec![]; count];
+    let mut heights = vec![0.0f32; count];
+    for (i, aspect) in aspects.iter().enumerate() {
+        let shortest = (0..count)
+            .min_by(|&a, &b| heights[a].total_cmp(&heights[b]))
+            .unwrap_or(0);
+        columns[shortest].push(i);
+        heights[shortest] += 1.0 / aspect;
+    }
+    columns
+}
+
+// How much the images in a masonry collage must be stretched to fill their columns,
+// averaged over the images.
+fn masonry_cost(
+    aspects: &[f32],
+    columns: &[Vec<usize>],
+    gutter: u32,
+    width: u32,
+    height: u32,
+) -> f32 {
+    let column_gutters = gutter * (columns.len() as u32).saturating_sub(1);
+    let column_width = width.saturating_sub(column_gutters) as f32 / columns.len() as f32;
+
+    let cost: f32 = columns
+        .iter()
+        .map(|column| {
+            if column.is_empty() {
+                // Empty columns would leave a gap, so are never chosen.
+                return f32::MAX;
+            }
+            let gutters = gutter * (column.len() as u32 - 1);
+            let natural: f32 = column.iter().map(|&i| column_width / aspects[i]).sum();
+            let available = height.saturating_sub(gutters).max(1) as f32;
+            (natural / available).ln().abs() * column.len() as f32
+        })
+        .sum();
+    cost / aspects.len().max(1) as f32
+}
+
+// Start and end of each column or row, after the margins and gutters have been taken out.
+// Edges are rounded so that neighbouring tracks meet exactly.
+fn track_edges(weights: &[f32], length: u32, margin: u32, gutter: u32) -> Vec<(u32, u32)> {
+    let gutters = gutter * (weights.len() as u32).saturating_sub(1);
+    let available = length.saturating_sub(2 * margin + gutters) as f32;
+    let total: f32 = weights
+        .iter()
+        .map(|w| w.max(0.0))
+        .sum::<f32>()
+        .max(f32::EPSILON);
+
+    let mut edges = Vec::with_capacity(weights.len());
+    let mut cumulative = 0.0;
+    for (i, weight) in weights.iter().enumerate() {
+        let start = (available * cumulative / total).round() as u32;
+        cumulative += weight.max(0.0);
+        let end = (available * cumulative / total).round() as u32;
+        let offset = margin + gutter * i as u32;
+        edges.push((offset + start, offset + end));
+    }
+    edges
+}
+
+/// Style of the band drawn behind a caption, to keep the text legible over photos.
+#[derive(Debug, Clone)]
+pub enum CaptionBand {
+    /// No band, with the text drawn straight onto the collage.
+    None,
+    /// A band of solid colour, which may be translucent.
+    Solid(Rgba),
+    /// One of the preset gradients, such as "pinkblue" (see `elements::create_gradient_preset`).
+    Gradient(String),
+    /// Frosted glass: the collage behind the band is blurred by `blur`, then tinted with `tint`.
+    Glass { blur: f32, tint: Rgba },
+}
+
+/// Where a caption's band is placed within the collage, or within a cell for per-cell captions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CaptionPosition {
+    /// A full-width band along the top, with its height given as a fraction of the area's height.
+    Top(f32),
+    /// A full-width band across the middle, with its height given as a fraction of the area's height.
+    Centre(f32),
+    /// A full-width band along the bottom, with its height given as a fraction of the area's height.
+    Bottom(f32),
+    /// A band at `(x, y, width, height)`, given as fractions of the area's width and height.
+    Rect(f32, f32, f32, f32),
+}
+
+impl CaptionPosition {
+    // The band's position and size within an area given as `(x, y, width, height)`.
+    fn resolve(&self, area: (u32, u32, u32, u32)) -> (u32, u32, u32, u32) {
+        let (x, y, width, height) = area;
+        let (fx, fy, fw, fh) = match *self {
+            CaptionPosition::Top(fh) => (0.0, 0.0, 1.0, fh),
+            CaptionPosition::Centre(fh) => (0.0, (1.0 - fh) / 2.0, 1.0, fh),
+            CaptionPosition::Bottom(fh) => (0.0, 1.0 - fh, 1.0, fh),
+            CaptionPosition::Rect(fx, fy, fw, fh) => (fx, fy, fw, fh),
+        };
+        let scale =
+            |fraction: f32, length: u32| (fraction.clamp(0.0, 1.0) * length as f32).round() as u32;
+        (
+            x + scale(fx, width),
+            y + scale(fy, height),
+            scale(fw, width),
+            scale(fh, height),
+        )
+    }
+}
+
+/// A block of text drawn over a collage, such as a heading, or a caption for a single photo.
+///
+/// The text is wrapped to fit the band, and its font size reduced from `max_font_size` if needed
+/// so that it fits within the band's padding.
+#[derive(Debug, Clone)]
+pub struct Caption {
+    pub text: String,
+    /// Font name, such as "Roboto-Regular".
+    pub font: String,
+    pub color: Rgb,
+    /// Largest font size to use, in pixels.
+    pub max_font_size: f32,
+    pub band: CaptionBand,
+    pub position: CaptionPosition,
+    /// Alignment of each line within the band.
+    pub align: TextAlign,
+    /// Space between the edge of the band and the text, in pixels.
+    pub padding: u32,
+}
+
+impl Caption {
+    /// Create a caption with black text on a white band along the bottom.
+    pub fn new(text: &str) -> Caption {
+        Caption {
+            text: text.to_string(),
+            font: "Roboto-Bold".to_string(),
+            color: Rgb { r: 0, g: 0, b: 0 },
+            max_font_size: 100.0,
+            band: CaptionBand::Solid(Rgba::new(255, 255, 255, 255)),
+            position: CaptionPosition::Bottom(0.25),
+            align: TextAlign::Left,
+            padding: 20,
+        }
+    }
+}
+
+/// Draw a caption and its band within an area of an image, such as the whole collage or a single cell.
+///
+/// # Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `caption` - The caption to be drawn.
+/// * `area` - Area the caption's position is relative to, as `(x, y, width, height)`.
+pub fn draw_caption(img: &mut DynamicImage, caption: &Caption, area: (u32, u32, u32, u32)) {
+    let (x, y, width, height) = caption.position.resolve(area);
+    let width = width.min(img.width().saturating_sub(x));
+    let height = height.min(img.height().saturating_sub(y));
+    if width == 0 || height == 0 {
+        return;
     }
 
-    return resized_imgs;
+    match &caption.band {
+        CaptionBand::None => {}
+        CaptionBand::Solid(color) => {
+            draw_solid_rect(img, *color, width, height, x as i32, y as i32)
+        }
+        CaptionBand::Gradient(name) => {
+            let gradient = create_gradient_preset(width, height, name);
+            blend::composite(img, &gradient, x as i32, y as i32, 1.0);
+        }
+        CaptionBand::Glass { blur, tint } => {
+            let mut glass = img.crop_imm(x, y, width, height);
+            gaussian_blur(&mut glass, *blur);
+            blend::composite(img, &glass, x as i32, y as i32, 1.0);
+            draw_solid_rect(img, *tint, width, height, x as i32, y as i32);
+        }
+    }
+
+    let padding = caption.padding.min(width / 2).min(height / 2);
+    let (text_width, text_height) = (width - 2 * padding, height - 2 * padding);
+    let font_vec = open_font(&caption.font);
+    let font = rusttype::Font::try_from_bytes(&font_vec).unwrap();
+    let (font_size, block_height) = fit_with_font(
+        &font,
+        &caption.text,
+        caption.max_font_size,
+        text_width as f32,
+        text_height as f32,
+    );
+
+    // Centre the text vertically within the band.
+    let text_y = y as f32 + padding as f32 + (text_height as f32 - block_height).max(0.0) / 2.0;
+    draw_text_block(
+        img,
+        &caption.text,
+        x + padding,
+        text_y.round() as u32,
+        text_width,
+        &caption.font,
+        font_size,
+        &caption.color,
+        caption.align,
+    );
+}
+
+/// Lay an image, such as a texture, gradient or photo, over a whole collage with a blend mode.
+///
+/// The overlay is stretched to the size of the collage.
+///
+/// # Arguments
+/// * `collage` - A mutable ref to the collage, as a DynamicImage.
+/// * `overlay` - Image to be laid over the collage.
+/// * `mode` - Blend mode, such as `BlendMode::Multiply` or `BlendMode::Screen`.
+/// * `opacity` - Opacity of the overlay, between 0.0 and 1.0.
+pub fn overlay_collage(
+    collage: &mut DynamicImage,
+    overlay: &DynamicImage,
+    mode: BlendMode,
+    opacity: f32,
+) {
+    let overlay = resize::resample(
+        overlay,
+        collage.width(),
+        collage.height(),
+        Resampling::default(),
+    );
+    blend::blend(collage, &overlay, 0, 0, mode, opacity);
 }
@@ -0,0 +1,370 @@
+//! Content-aware cropping, which chooses the part of an image to keep when cropping it to a new aspect ratio.
+//!
+//! The image is scored by saliency, entropy or edge density, and the crop window with
+//! the highest total score is chosen, so that the subject of a photo stays in frame.
+//! A focal point can be given instead, to choose the window manually.
+
+use crate::resize::{self, Fit};
+use image::{DynamicImage, GenericImageView};
+use palette::{IntoColor, Lab, Srgb};
+
+// Images are scored at a reduced size, as fine detail doesn't affect the choice of crop.
+const ANALYSIS_SIZE: u32 = 160;
+
+/// How to choose which part of an image is kept when cropping.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CropStrategy {
+    /// Keep the centre of the image.
+    Centre,
+    /// Keep the most visually distinctive region, i.e. colours which stand out from the rest of the image.
+    #[default]
+    Saliency,
+    /// Keep the most detailed region, measured by the entropy of its brightness.
+    Entropy,
+    /// Keep the region with the most edges.
+    Edges,
+    /// Keep the region around a point, given as fractions of the image's width and height,
+    /// where (0.0, 0.0) is the top-left corner and (1.0, 1.0) the bottom-right.
+    FocalPoint(f32, f32),
+}
+
+/// Find the largest window with the given aspect ratio which fits within an image,
+/// positioned according to the crop strategy.
+///
+/// Returns the window as `(x, y, width, height)`.
+///
+/// ### Arguments
+/// * `img` - The image to be cropped.
+/// * `aspect_width` - Width of the target aspect ratio, such as 16 for 16:9.
+/// * `aspect_height` - Height of the target aspect ratio, such as 9 for 16:9.
+/// * `strategy` - How to choose the window.
+pub fn crop_window(
+    img: &DynamicImage,
+    aspect_width: u32,
+    aspect_height: u32,
+    strategy: CropStrategy,
+) -> (u32, u32, u32, u32) {
+    let (width, height) = img.dimensions();
+    // An empty image has no window to choose, so keep all of it.
+    if width == 0 || height == 0 {
+        return (0, 0, width, height);
+    }
+    let aspect = aspect_width.max(1) as f64 / aspect_height.max(1) as f64;
+
+    // Keep the full width or height, whichever is the limiting dimension.
+    let (crop_width, crop_height) = if width as f64 / height as f64 > aspect {
+        (
+            ((height as f64 * aspect).round() as u32).clamp(1, width),
+            height,
+        )
+    } else {
+        (
+            width,
+            ((width as f64 / aspect).round() as u32).clamp(1, height),
+        )
+    };
+
+    let (x, y) = match strategy {
+        CropStrategy::Centre => ((width - crop_width) / 2, (height - crop_height) / 2),
+        CropStrategy::FocalPoint(fx, fy) => (
+            centre_on(fx * width as f32, crop_width, width),
+            centre_on(fy * height as f32, crop_height, height),
+        ),
+        _ => best_window(img, crop_width, crop_height, strategy),
+    };
+    (x, y, crop_width, crop_height)
+}
+
+/// Crop an image to the aspect ratio of the given size, keeping the region chosen by the crop strategy,
+/// then resize it to exactly that size.
+///
+/// ### Arguments
+/// * `img` - The image to be cropped.
+/// * `width` - Width of the new image.
+/// * `height` - Height of the new image.
+/// * `strategy` - How to choose which part of the image is kept.
+///
+/// ### Example
+/// ```ignore
+/// // Crop a landscape photo to a square, keeping the subject in frame.
+/// let square = smart_crop(&photo, 1080, 1080, CropStrategy::Saliency);
+/// ```
+pub fn smart_crop(
+    img: &DynamicImage,
+    width: u32,
+    height: u32,
+    strategy: CropStrategy,
+) -> DynamicImage {
+    resize::resize_to_fit(img, width, height, Fit::Cover(strategy))
+}
+
+/// Score each pixel of an image by how important it is to keep when cropping.
+///
+/// The image is first reduced in size, so the returned scores are for the reduced image.
+/// Returns the scores in row-major order, along with the width and height of the reduced image.
+pub fn score_map(img: &DynamicImage, strategy: CropStrategy) -> (Vec<f32>, u32, u32) {
+    let small = img.thumbnail(ANALYSIS_SIZE, ANALYSIS_SIZE);
+    let (width, height) = small.dimensions();
+    let scores = match strategy {
+        CropStrategy::Entropy => entropy_scores(&small),
+        CropStrategy::Edges => edge_scores(&small),
+        CropStrategy::Saliency => saliency_scores(&small),
+        CropStrategy::Centre | CropStrategy::FocalPoint(..) => {
+            vec![1.0; (width * height) as usize]
+        }
+    };
+    (scores, width, height)
+}
+
+// Position a window of the given length so that it's centred on a point, without leaving the image.
+fn centre_on(point: f32, window: u32, length: u32) -> u32 {
+    let start = point - window as f32 / 2.0;
+    start.round().clamp(0.0, (length - window) as f32) as u32
+}
+
+// Slide the crop window along the image, and choose the position with the highest total score.
+fn best_window(
+    img: &DynamicImage,
+    crop_width: u32,
+    crop_height: u32,
+    strategy: CropStrategy,
+) -> (u32, u32) {
+    let (width, height) = img.dimensions();
+    let (scores, map_width, map_height) = score_map(img, strategy);
+    let scale_x = map_width as f32 / width as f32;
+    let scale_y = map_height as f32 / height as f32;
+
+    // The window always spans the full width or height, so only one axis needs searching.
+    if crop_width < width {
+        let profile: Vec<f32> = (0..map_width)
+            .map(|x| {
+                (0..map_height)
+                    .map(|y| scores[(y * map_width + x) as usize])
+                    .sum()
+            })
+            .collect();
+        let window = ((crop_width as f32 * scale_x).round() as usize).clamp(1, profile.len());
+        let start = best_start(&profile, window);
+        let x = (start as f32 / scale_x).round() as u32;
+        (x.min(width - crop_width), 0)
+    } else if crop_height < height {
+        let profile: Vec<f32> = (0..map_height)
+            .map(|y| {
+                (0..map_width)
+                    .map(|x| scores[(y * map_width + x) as usize])
+                    .sum()
+            })
+            .collect();
+        let window = ((crop_height as f32 * scale_y).round() as usize).clamp(1, profile.len());
+        let start = best_start(&profile, window);
+        let y = (start as f32 / scale_y).round() as u32;
+        (0, y.min(height - crop_height))
+    } else {
+        (0, 0)
+    }
+}
+
+// Find the start of the window over a profile with the highest sum.
+// Ties are broken in favour of the window closest to the centre.
+fn best_start(profile: &[f32], window: usize) -> usize {
+    let positions = profile.len() - window + 1;
+    let centre = (positions - 1) as f32 / 2.0;
+    let mut sum: f32 = profile[..window].iter().sum();
+    let mut best = (sum, 0);
+
+    for start in 1..positions {
+        sum += profile[start + window - 1] - profile[start - 1];
+        let closer = (start as f32 - centre).abs() < (best.1 as f32 - centre).abs();
+        if sum > best.0 + 1e-3 || ((sum - best.0).abs() <= 1e-3 && closer) {
+            best = (sum, start);
+        }
+    }
+    best.1
+}
+
+fn luma_values(img: &DynamicImage) -> Vec<f32> {
+    img.to_luma8()
+        .pixels()
+        .map(|p| p[0] as f32 / 255.0)
+        .collect()
+}
+
+// Magnitude of the Sobel gradient at each pixel.
+fn edge_scores(img: &DynamicImage) -> Vec<f32> {
+    let (width, height) = img.dimensions();
+    let luma = luma_values(img);
+    let at = |x: i64, y: i64| {
+        let x = x.clamp(0, width as i64 - 1);
+        let y = y.clamp(0, height as i64 - 1);
+        luma[(y * width as i64 + x) as usize]
+    };
+
+    let mut scores = Vec::with_capacity(luma.len());
+    for y in 0..height as i64 {
+        for x in 0..width as i64 {
+            let gx = at(x + 1, y - 1) + 2.0 * at(x + 1, y) + at(x + 1, y + 1)
+                - at(x - 1, y - 1)
+                - 2.0 * at(x - 1, y)
+                - at(x - 1, y + 1);
+            let gy = at(x - 1, y + 1) + 2.0 * at(x, y + 1) + at(x + 1, y + 1)
+                - at(x - 1, y - 1)
+                - 2.0 * at(x, y - 1)
+                - at(x + 1, y - 1);
+            scores.push((gx * gx + gy * gy).sqrt());
+        }
+    }
+    scores
+}
+
+// Entropy of the brightness histogram in the block surrounding each pixel.
+fn entropy_scores(img: &DynamicImage) -> Vec<f32> {
+    const BLOCK: u32 = 8;
+    const BINS: usize = 16;
+    let (width, height) = img.dimensions();
+    let luma = img.to_luma8();
+    let mut scores = vec![0.0; (width * height) as usize];
+
+    for block_y in (0..height).step_by(BLOCK as usize) {
+        for block_x in (0..width).step_by(BLOCK as usize) {
+            let (end_x, end_y) = ((block_x + BLOCK).min(width), (block_y + BLOCK).min(height));
+            let mut histogram = [0u32; BINS];
+            for y in block_y..end_y {
+                for x in block_x..end_x {
+                    histogram[luma.get_pixel(x, y)[0] as usize * BINS / 256] += 1;
+                }
+            }
+
+            let total = ((end_x - block_x) * (end_y - block_y)) as f32;
+            let entropy: f32 = histogram
+                .iter()
+                .filter(|&&count| count > 0)
+                .map(|&count| {
+                    let p = count as f32 / total;
+                    -p * p.log2()
+                })
+                .sum();
+
+            for y in block_y..end_y {
+                for x in block_x..end_x {
+                    scores[(y * width + x) as usize] = entropy;
+                }
+            }
+        }
+    }
+    scores
+}
+
+// Distance of each pixel's colour from the average colour of the image, in Lab space.
+// The image is blurred first, so that regions stand out rather than noise.
+fn saliency_scores(img: &DynamicImage) -> Vec<f32> {
+    let blurred = img.blur(1.5).to_rgb8();
+    let labs: Vec<Lab> = blurred
+        .pixels()
+        .map(|p| {
+            Srgb::new(p[0], p[1], p[2])
+                .into_format::<f32>()
+                .into_linear()
+                .into_color()
+        })
+        .collect();
+
+    let count = labs.len().max(1) as f32;
+    let (l, a, b) = labs.iter().fold((0.0, 0.0, 0.0), |(l, a, b), lab| {
+        (l + lab.l, a + lab.a, b + lab.b)
+    });
+    let mean = (l / count, a / count, b / count);
+
+    labs.iter()
+        .map(|lab| {
+            let (dl, da, db) = (lab.l - mean.0, lab.a - mean.1, lab.b - mean.2);
+            (dl * dl + da * da + db * db).sqrt()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn centre_window_keeps_full_height() {
+        let img = DynamicImage::new_rgba8(200, 100);
+        assert_eq!(
+            crop_window(&img, 1, 1, CropStrategy::Centre),
+            (50, 0, 100, 100)
+        );
+        assert_eq!(
+            crop_window(&img, 4, 1, CropStrategy::Centre),
+            (0, 25, 200, 50)
+        );
+        assert_eq!(
+            crop_window(&img, 2, 1, CropStrategy::Centre),
+            (0, 0, 200, 100)
+        );
+    }
+
+    #[test]
+    fn focal_point_window_stays_within_image() {
+        let img = DynamicImage::new_rgba8(200, 100);
+        let window = |fx, fy| crop_window(&img, 1, 1, CropStrategy::FocalPoint(fx, fy));
+        assert_eq!(window(0.3, 0.5), (10, 0, 100, 100));
+        assert_eq!(window(0.0, 0.0), (0, 0, 100, 100));
+        assert_eq!(window(1.0, 1.0), (100, 0, 100, 100));
+        assert_eq!(window(-2.0, 0.5), (0, 0, 100, 100));
+        assert_eq!(window(2.0, 0.5), (100, 0, 100, 100));
+    }
+
+    #[test]
+    fn extreme_aspect_ratios_keep_at_least_one_pixel() {
+        let img = DynamicImage::new_rgba8(200, 100);
+        assert_eq!(
+            crop_window(&img, 1, 1000, CropStrategy::Centre),
+            (99, 0, 1, 100)
+        );
+        assert_eq!(
+            crop_window(&img, 1000, 1, CropStrategy::Centre),
+            (0, 49, 200, 1)
+        );
+        // A zero aspect dimension is treated as 1.
+        assert_eq!(
+            crop_window(&img, 0, 0, CropStrategy::Centre),
+            (50, 0, 100, 100)
+        );
+    }
+
+    #[test]
+    fn empty_images_keep_the_whole_image() {
+        for &(width, height) in &[(0, 0), (0, 10), (10, 0)] {
+            let img = DynamicImage::new_rgba8(width, height);
+            for &strategy in &[CropStrategy::Centre, CropStrategy::Saliency] {
+                assert_eq!(crop_window(&img, 16, 9, strategy), (0, 0, width, height));
+            }
+        }
+    }
+
+    #[test]
+    fn single_pixel_image_keeps_its_pixel() {
+        let img = DynamicImage::new_rgba8(1, 1);
+        for &strategy in &[
+            CropStrategy::Entropy,
+            CropStrategy::Edges,
+            CropStrategy::Saliency,
+        ] {
+            assert_eq!(crop_window(&img, 16, 9, strategy), (0, 0, 1, 1));
+        }
+    }
+
+    #[test]
+    fn saliency_window_contains_the_subject() {
+        let mut img = image::RgbaImage::from_pixel(300, 100, image::Rgba([0, 0, 0, 255]));
+        for y in 40..60 {
+            for x in 250..270 {
+                img.put_pixel(x, y, image::Rgba([255, 40, 40, 255]));
+            }
+        }
+        let img = DynamicImage::ImageRgba8(img);
+        let (x, y, width, height) = crop_window(&img, 1, 1, CropStrategy::Saliency);
+        assert_eq!((y, width, height), (0, 100, 100));
+        assert!(x <= 250 && x + width >= 270, "window starts at {}", x);
+    }
+}
@@ -1,11 +1,16 @@
 //! Create diagrams, flowcharts, graphs, etc.,
+//!
+//! Charts are drawn by this module; flowcharts of nodes and connectors are drawn by the `flowchart` module.
 
 // use wasm_bindgen::prelude::*;
-use crate::elements::{draw_preset_rect_gradient, draw_solid_rect};
-use crate::text::draw_text;
-use crate::Rgb;
-use image::{DynamicImage, Rgba};
-use imageproc::drawing::*;
+use crate::elements::{self, draw_preset_rect_gradient, draw_solid_rect};
+use crate::mask::{clip_image, Mask};
+use crate::raster::{self, Quality};
+use crate::resize::{self, Resampling};
+use crate::text::{self, TextAlign};
+use crate::{blend, frames, Rgb, Rgba};
+use image::{DynamicImage, GenericImageView, ImageBuffer};
+use std::f32::consts::PI;
 
 /// Draw a horizontal barchart, with a specified title and data.
 ///
@@ -13,7 +18,7 @@ use imageproc::drawing::*;
 /// * `img` - Image to draw the barchart onto.
 /// * `barchart` - Barchart struct, which contains all data & meta-data about the barchart.
 pub fn draw_horizontal_barchart(img: &mut DynamicImage, barchart: &Chart) {
-    draw_horizontal_bars(img, &barchart, "barchart");
+    draw_bars(img, barchart, &BarOptions::horizontal(), BarFill::Solid);
 }
 
 /// Draw a vertical barchart, with a specified title and data.
@@ -22,127 +27,94 @@ pub fn draw_horizontal_barchart(img: &mut DynamicImage, barchart: &Chart) {
 /// * `img` - Image to draw the barchart onto.
 /// * `barchart` - Barchart struct, which contains all data & meta-data about the barchart.
 pub fn draw_vertical_barchart(img: &mut DynamicImage, barchart: &Chart) {
-    draw_vertical_bars(img, barchart, "barchart");
+    draw_bars(img, barchart, &BarOptions::default(), BarFill::Solid);
 }
 
 /// Draw a histogram with a specified title, and data.
+/// Each value is drawn as a bar, so must already be a count; `draw_histogram` bins raw samples.
 ///
 /// /// ### Arguments
 /// * `img` - Image to draw the barchart onto.
 /// * `barchart` - Barchart struct, which contains all data & meta-data about the barchart.
 
 pub fn draw_horizontal_histogram(img: &mut DynamicImage, barchart: &Chart) {
-    draw_horizontal_bars(img, &barchart, "histogram");
+    let options = BarOptions {
+        gap: 0.0,
+        ..BarOptions::horizontal()
+    };
+    draw_bars(img, barchart, &options, BarFill::Solid);
 }
 
 /// Draw a vertical barchart, with a specified title and data.
+/// Each value is drawn as a bar, so must already be a count; `draw_histogram` bins raw samples.
 ///
 /// ### Arguments
 /// * `img` - Image to draw the barchart onto.
 /// * `histogram` - Chart struct, which contains all data & meta-data about the barchart.
 pub fn draw_vertical_histogram(img: &mut DynamicImage, histogram: &Chart) {
-    draw_vertical_bars(img, histogram, "histogram");
+    let options = BarOptions {
+        gap: 0.0,
+        ..BarOptions::default()
+    };
+    draw_bars(img, histogram, &options, BarFill::Solid);
 }
 
-/// Draw a vertical barchart, where the bars are filled with a gradient.
+/// Draw a histogram of the first series of a chart, treating its values as raw samples which are
+/// divided into bins by Sturges' rule. Each bar covers one bin, with the bins' edges labelled along the x-axis.
 ///
 /// ### Arguments
-/// * `img` - Image to draw the barchart onto.
-/// * `barchart` - Barchart struct, which contains all data & meta-data about the barchart.
-/// * `preset` - Preset name for the gradient. Can be: "pinkblue", "pastel_pink", "pastel_mauve", "lemongrass"
-pub fn draw_vertical_gradient_barchart(img: &mut DynamicImage, barchart: &Chart, preset: &str) {
-    let mut start_x: u32 = 20;
-    let start_y: u32 = barchart.height - 40;
-
-    let max_item = barchart.data.iter().max().unwrap();
-    let max_bar_height: u32 = barchart.height - 2 * (barchart.height / 10);
-    let num_bars: u32 = barchart.data.len() as u32;
-    let bar_width: u32 = ((barchart.height / num_bars) as f32 * 0.8) as u32;
-
-    let yellow = Rgb {
-        r: 255,
-        g: 226,
-        b: 98,
-    };
-
-    for item in &barchart.data {
-        let div = max_item / item;
-        let bar_height = max_bar_height / div as u32;
-        draw_preset_rect_gradient(
-            img,
-            bar_width as u32,
-            bar_height as u32,
-            start_x,
-            start_y - bar_height,
-            preset,
-        );
-
-        start_x += bar_width + 30;
-    }
-
-    draw_text(
-        img,
-        &barchart.title,
-        10,
-        start_y as u32,
-        "Lato-Regular",
-        50.0,
-        &yellow,
-    );
+/// * `img` - Image to draw the histogram onto.
+/// * `chart` - Chart struct, which contains all data & meta-data about the chart.
+pub fn draw_histogram(img: &mut DynamicImage, chart: &Chart) {
+    draw_histogram_with_options(img, chart, &HistogramOptions::default());
 }
 
-// Draw vertical bars, either as a histogram or bar chart.
-// This is a private function, but may become public in the future.
-fn draw_vertical_bars(img: &mut DynamicImage, barchart: &Chart, chart_type: &str) {
-    let bar_gap = match chart_type {
-        "barchart" => 30,
-        "histogram" => 0,
-        _ => 30,
+/// Draw a histogram of the first series of a chart, treating its values as raw samples, with the
+/// bins and whether bars show counts or densities set by `options`. Samples which aren't finite are ignored.
+///
+/// ### Arguments
+/// * `img` - Image to draw the histogram onto.
+/// * `chart` - Chart struct, which contains all data & meta-data about the chart.
+/// * `options` - How samples are binned, and whether to show densities.
+pub fn draw_histogram_with_options(
+    img: &mut DynamicImage,
+    chart: &Chart,
+    options: &HistogramOptions,
+) {
+    let histogram = Histogram::new(chart.first_values(), options.binning);
+    let heights: Vec<f64> = match options.density {
+        true => histogram.densities(),
+        false => histogram.counts.iter().map(|count| *count as f64).collect(),
     };
-
-    let mut start_x: u32 = 20;
-    let start_y: u32 = barchart.height - 40;
-
-    let max_item = barchart.data.iter().max().unwrap();
-    let max_bar_height: u32 = barchart.height - 2 * (barchart.height / 10);
-    let num_bars: u32 = barchart.data.len() as u32;
-    println!("num_bars {}", barchart.data.len());
-    let bar_width: u32 = ((barchart.height / num_bars) as f32 * 0.8) as u32;
-
-    for item in &barchart.data {
-        let mut bar_height = 0;
-
-        if *item > 0 as u16 {
-            let div = max_item / item;
-            bar_height = max_bar_height / div as u32;
-        }
-
-        draw_solid_rect(
-            img,
-            &barchart.color,
-            bar_width as u32,
-            bar_height as u32,
-            start_x as i32,
-            (start_y - bar_height) as i32,
-        );
-        start_x += bar_width + bar_gap;
-    }
-
-    let yellow = Rgb {
-        r: 255,
-        g: 226,
-        b: 98,
+    let highest = heights.iter().copied().fold(0.0, f64::max);
+    let scale = chart.scale_for_range(Some((0.0, highest)));
+
+    draw_title(img, chart);
+    let numbers = NumberAxis::edges(&histogram.edges);
+    let plot = draw_oriented_axes(img, chart, false, &scale, Some(&numbers));
+    let color = match chart.series.first() {
+        Some(series) => Rgba::from(&series.color),
+        None => Rgba::from(&chart.style.palette_color(0)),
     };
 
-    draw_text(
-        img,
-        &barchart.title,
-        10,
-        start_y as u32,
-        "Lato-Regular",
-        50.0,
-        &yellow,
-    );
+    let baseline = plot.value_y(&scale, scale.baseline());
+    let bars: Vec<_> = heights
+        .iter()
+        .zip(histogram.edges.windows(2))
+        .filter(|(height, _)| **height > 0.0)
+        .map(|(height, edges)| {
+            let left = plot.value_x(&numbers.scale, edges[0]);
+            let right = plot.value_x(&numbers.scale, edges[1]);
+            let top = plot.value_y(&scale, height.min(scale.max));
+            // Leave a pixel between neighbouring bars, so each bin can be made out.
+            let width = match right - left {
+                width if width > 3.0 => width - 1.0,
+                width => width,
+            };
+            raster::rect(left, top, width, baseline - top)
+        })
+        .collect();
+    raster::fill_contours(img, &bars, color.into(), Quality::AntiAliased);
 }
 
 /// Draw a vertical barchart, where the bars are filled with a gradient.
@@ -151,101 +123,27 @@ fn draw_vertical_bars(img: &mut DynamicImage, barchart: &Chart, chart_type: &str
 /// * `img` - Image to draw the barchart onto.
 /// * `barchart` - Barchart struct, which contains all data & meta-data about the barchart.
 /// * `preset` - Preset name for the gradient. Can be: "pinkblue", "pastel_pink", "pastel_mauve", "lemongrass"
-pub fn draw_horizontal_gradient_barchart(img: &mut DynamicImage, barchart: &Chart, preset: &str) {
-    let start_x: u32 = 20;
-    let mut start_y: u32 = 20;
-
-    let max_item = barchart.data.iter().max().unwrap();
-    let max_bar_width: u32 = barchart.width - 2 * (barchart.width / 10);
-    let num_bars: u32 = barchart.data.len() as u32;
-    let bar_height: u32 = ((barchart.height / num_bars) as f32 * 0.8) as u32;
-
-    let yellow = Rgb {
-        r: 255,
-        g: 226,
-        b: 98,
-    };
-
-    let bar_gap = 30;
-    for item in &barchart.data {
-        let div = max_item / item;
-        let bar_width = max_bar_width / div as u32;
-        draw_preset_rect_gradient(
-            img,
-            bar_width as u32,
-            bar_height as u32,
-            start_x,
-            start_y,
-            preset,
-        );
-        start_y += bar_height + bar_gap;
-    }
-
-    draw_text(
+pub fn draw_vertical_gradient_barchart(img: &mut DynamicImage, barchart: &Chart, preset: &str) {
+    draw_bars(
         img,
-        &barchart.title,
-        10,
-        start_y as u32,
-        "Lato-Regular",
-        50.0,
-        &yellow,
+        barchart,
+        &BarOptions::default(),
+        BarFill::Gradient(preset),
     );
 }
 
-// Draw a horizontal chart, either as a histogram or as a barchart,
-// with horizontal bars.
-fn draw_horizontal_bars(img: &mut DynamicImage, barchart: &Chart, chart_type: &str) {
-    let bar_gap = match chart_type {
-        "barchart" => 30,
-        "histogram" => 0,
-        _ => 30,
-    };
-
-    let start_x: i32 = 20;
-    let mut start_y: i32 = 20;
-
-    let max_item = barchart.data.iter().max().unwrap();
-    let max_bar_width: u32 = barchart.width - 2 * (barchart.width / 10);
-    let num_bars: u32 = barchart.data.len() as u32;
-    let bar_height: u32 = ((barchart.height / num_bars) as f32 * 0.8) as u32;
-
-    let yellow = Rgb {
-        r: 255,
-        g: 226,
-        b: 98,
-    };
-
-    for item in &barchart.data {
-        let div = max_item / item;
-        let bar_width = max_bar_width / div as u32;
-        draw_solid_rect(
-            img,
-            &barchart.color,
-            bar_width as u32,
-            bar_height as u32,
-            start_x,
-            start_y,
-        );
-        start_y += (bar_height) as i32 + bar_gap;
-
-        if chart_type == "histogram" {
-            draw_line_segment_mut(
-                img,
-                (start_x as f32, start_y as f32),
-                ((start_x + bar_width as i32) as f32, start_y as f32),
-                Rgba([255u8, 255u8, 255u8, 255u8]),
-            )
-        }
-    }
-
-    draw_text(
+/// Draw a vertical barchart, where the bars are filled with a gradient.
+///
+/// ### Arguments
+/// * `img` - Image to draw the barchart onto.
+/// * `barchart` - Barchart struct, which contains all data & meta-data about the barchart.
+/// * `preset` - Preset name for the gradient. Can be: "pinkblue", "pastel_pink", "pastel_mauve", "lemongrass"
+pub fn draw_horizontal_gradient_barchart(img: &mut DynamicImage, barchart: &Chart, preset: &str) {
+    draw_bars(
         img,
-        &barchart.title,
-        10,
-        start_y as u32,
-        "Lato-Regular",
-        50.0,
-        &yellow,
+        barchart,
+        &BarOptions::horizontal(),
+        BarFill::Gradient(preset),
     );
 }
 
@@ -260,46 +158,11 @@ pub fn draw_vertical_image_barchart(
     bar_img: &DynamicImage,
     barchart: &Chart,
 ) {
-    let mut start_x: u32 = 20;
-    let start_y: u32 = barchart.height - 40;
-
-    let max_item = barchart.data.iter().max().unwrap();
-    let max_bar_height: u32 = barchart.height - 2 * (barchart.height / 10);
-    let num_bars: u32 = barchart.data.len() as u32;
-    let bar_width: u32 = ((barchart.height / num_bars) as f32 * 0.8) as u32;
-
-    let yellow = Rgb {
-        r: 255,
-        g: 226,
-        b: 98,
-    };
-
-    for item in &barchart.data {
-        let div = max_item / item;
-        let bar_height = max_bar_height / div as u32;
-
-        let sampling_filter = image::imageops::FilterType::Nearest;
-
-        let resized_img = image::DynamicImage::ImageRgba8(image::imageops::resize(
-            bar_img,
-            bar_width as u32,
-            bar_height as u32,
-            sampling_filter,
-        ));
-
-        image::imageops::overlay(img, &resized_img, start_x, start_y - bar_height);
-
-        start_x += bar_width + 30;
-    }
-
-    draw_text(
+    draw_bars(
         img,
-        &barchart.title,
-        10,
-        start_y as u32,
-        "Lato-Regular",
-        50.0,
-        &yellow,
+        barchart,
+        &BarOptions::default(),
+        BarFill::Image(bar_img),
     );
 }
 
@@ -314,215 +177,2972 @@ pub fn draw_horizontal_image_barchart(
     bar_img: &DynamicImage,
     barchart: &Chart,
 ) {
-    let start_x: u32 = 20;
-    let mut start_y: u32 = 20;
+    draw_bars(
+        img,
+        barchart,
+        &BarOptions::horizontal(),
+        BarFill::Image(bar_img),
+    );
+}
+
+/// Draw a barchart of every series in a chart, grouped side by side or stacked as set by `options`,
+/// with optional value labels and rounded corners. Bars take their series' colour, unless the
+/// series overrides the colour of individual values.
+///
+/// ### Arguments
+/// * `img` - Image to draw the barchart onto.
+/// * `barchart` - Chart struct, which contains all data & meta-data about the barchart.
+/// * `options` - Arrangement, orientation, spacing and labels of the bars.
+pub fn draw_barchart_with_options(img: &mut DynamicImage, barchart: &Chart, options: &BarOptions) {
+    draw_bars(img, barchart, options, BarFill::Solid);
+}
+
+/// Draw a barchart where the bars are filled with a gradient, arranged as set by `options`.
+///
+/// ### Arguments
+/// * `img` - Image to draw the barchart onto.
+/// * `barchart` - Chart struct, which contains all data & meta-data about the barchart.
+/// * `preset` - Preset name for the gradient. Can be: "pinkblue", "pastel_pink", "pastel_mauve", "lemongrass"
+/// * `options` - Arrangement, orientation, spacing and labels of the bars.
+pub fn draw_gradient_barchart_with_options(
+    img: &mut DynamicImage,
+    barchart: &Chart,
+    preset: &str,
+    options: &BarOptions,
+) {
+    draw_bars(img, barchart, options, BarFill::Gradient(preset));
+}
+
+/// Draw a barchart where each bar is denoted by an image, arranged as set by `options`.
+///
+/// ### Arguments
+/// * `img` - Image to draw the barchart onto.
+/// * `bar_img` - Image the bars should contain.
+/// * `barchart` - Chart struct, which contains all data & meta-data about the barchart.
+/// * `options` - Arrangement, orientation, spacing and labels of the bars.
+pub fn draw_image_barchart_with_options(
+    img: &mut DynamicImage,
+    bar_img: &DynamicImage,
+    barchart: &Chart,
+    options: &BarOptions,
+) {
+    draw_bars(img, barchart, options, BarFill::Image(bar_img));
+}
+
+// How the bars of a barchart are filled.
+enum BarFill<'a> {
+    Solid,
+    Gradient(&'a str),
+    Image(&'a DynamicImage),
+}
 
-    let max_item = barchart.data.iter().max().unwrap();
-    let max_bar_width: u32 = barchart.width - 2 * (barchart.width / 10);
-    let num_bars: u32 = barchart.data.len() as u32;
-    let bar_height: u32 = ((barchart.height / num_bars) as f32 * 0.8) as u32;
+// A bar, or a segment of a stacked bar, ready to be drawn.
+struct Bar {
+    series: usize,
+    category: usize,
+    // Values the bar runs between, and the value it's labelled with.
+    from: f64,
+    to: f64,
+    label: f64,
+}
 
-    let yellow = Rgb {
-        r: 255,
-        g: 226,
-        b: 98,
+// Draw the series of a chart as bars, either as a histogram or bar chart, with its title and axes.
+// Each category gets an equal share of the plot area, with `options.gap` of it left empty between them.
+fn draw_bars(img: &mut DynamicImage, chart: &Chart, options: &BarOptions, fill: BarFill) {
+    let series_count = match options.mode {
+        BarMode::Single => chart.series.len().min(1),
+        _ => chart.series.len(),
+    };
+    let count = chart.category_count();
+    let bars = arrange_bars(chart, options.mode, series_count, count);
+
+    // Leave room beyond the longest bars for labels outside them.
+    let range = bars.iter().fold(None, |range, bar| {
+        let (low, high) = (bar.from.min(bar.to), bar.from.max(bar.to));
+        match range {
+            None => Some((low, high)),
+            Some((min, max)) => Some((low.min(min), high.max(max))),
+        }
+    });
+    let scale = match (options.mode, range) {
+        (BarMode::PercentStacked, _) => Scale::nice(0.0, 100.0, chart.y_axis.max_ticks),
+        (_, Some((min, max))) if options.value_labels == ValueLabels::Outside => {
+            let headroom = (max - min) * 0.1;
+            let min = if min < 0.0 { min - headroom } else { min };
+            let max = if max > 0.0 { max + headroom } else { max };
+            chart.scale_for_range(Some((min, max)))
+        }
+        (_, range) => chart.scale_for_range(range),
     };
 
-    for item in &barchart.data {
-        let div = max_item / item;
-        let bar_width = max_bar_width / div as u32;
+    draw_title(img, chart);
+    let plot = draw_oriented_axes(img, chart, options.horizontal, &scale, None);
 
-        draw_image_as_bar(img, bar_img, bar_width, bar_height, start_x, start_y);
+    let axis_length = if options.horizontal {
+        plot.height
+    } else {
+        plot.width
+    };
+    let slot = axis_length / count.max(1) as f32;
+    let group = slot * (1.0 - options.gap.clamp(0.0, 0.95));
+    let (thickness, pitch) = match options.mode {
+        BarMode::Grouped => {
+            let bars_in_group = series_count.max(1) as f32;
+            let thickness = group / (bars_in_group + (bars_in_group - 1.0) * options.group_gap);
+            (thickness, thickness * (1.0 + options.group_gap))
+        }
+        _ => (group, 0.0),
+    };
+
+    let style = &chart.style;
+    let inside_style = ChartStyle {
+        text_color: options.label_color.clone(),
+        ..style.clone()
+    };
+    let gap = style.font_size * 0.4;
+    let stacked = matches!(options.mode, BarMode::Stacked | BarMode::PercentStacked);
+    let mut labels = vec![];
+
+    for bar in &bars {
+        let centre = if options.horizontal {
+            plot.category_y(bar.category, count)
+        } else {
+            plot.category_x(bar.category, count)
+        };
+        let offset = match options.mode {
+            BarMode::Grouped => (bar.series as f32 - (series_count as f32 - 1.0) / 2.0) * pitch,
+            _ => 0.0,
+        };
+        let across = (centre + offset - thickness / 2.0).round();
+        let thickness = thickness.round().max(1.0);
+
+        let (x, y, width, height) = if options.horizontal {
+            let (start, length) = span_extent(&scale, bar.from, bar.to, plot.width);
+            (plot.x + start, across, length, thickness)
+        } else {
+            let (start, length) = span_extent(&scale, bar.from, bar.to, plot.height);
+            (across, plot.bottom() - start - length, thickness, length)
+        };
+        if width < 1.0 || height < 1.0 {
+            continue;
+        }
+        let color = chart.series[bar.series].color_at(bar.category);
+        draw_bar(
+            img,
+            &fill,
+            color,
+            x,
+            y,
+            width,
+            height,
+            options.corner_radius,
+        );
 
-        start_y += bar_height + 30;
+        let label = match options.mode {
+            BarMode::PercentStacked => format!("{:.0}%", bar.label),
+            _ => format_value(bar.label),
+        };
+        match options.value_labels {
+            ValueLabels::None => {}
+            ValueLabels::Inside => {
+                // Labels which don't fit inside their bar are left out.
+                let (label_width, label_height) =
+                    text::measure_text(&label, &style.font, style.font_size);
+                if label_width as f32 + gap <= width && label_height as f32 <= height {
+                    let (label_x, label_y) = (x + width / 2.0, y + height / 2.0);
+                    labels.push((label, label_x, label_y, (0.5, 0.5), true));
+                }
+            }
+            ValueLabels::Outside if !stacked => {
+                let positive = bar.to >= bar.from;
+                let (label_x, label_y, anchor) = match (options.horizontal, positive) {
+                    (true, true) => (x + width + gap, y + height / 2.0, (0.0, 0.5)),
+                    (true, false) => (x - gap, y + height / 2.0, (1.0, 0.5)),
+                    (false, true) => (x + width / 2.0, y - gap, (0.5, 1.0)),
+                    (false, false) => (x + width / 2.0, y + height + gap, (0.5, 0.0)),
+                };
+                labels.push((label, label_x, label_y, anchor, false));
+            }
+            ValueLabels::Outside => {}
+        }
+    }
+
+    // Stacks are labelled with their total, beyond the end of the stack.
+    if stacked && options.value_labels == ValueLabels::Outside {
+        for category in 0..count {
+            let in_stack = || bars.iter().filter(|bar| bar.category == category);
+            let top = in_stack()
+                .map(|bar| bar.to.max(bar.from))
+                .fold(0.0, f64::max);
+            let total: f64 = chart
+                .series
+                .iter()
+                .filter_map(|series| series.values.get(category))
+                .filter(|value| value.is_finite())
+                .sum();
+            if in_stack().next().is_none() {
+                continue;
+            }
+            let centre = if options.horizontal {
+                plot.category_y(category, count)
+            } else {
+                plot.category_x(category, count)
+            };
+            let label = format_value(total);
+            let (label_x, label_y, anchor) = if options.horizontal {
+                (plot.value_x(&scale, top) + gap, centre, (0.0, 0.5))
+            } else {
+                (centre, plot.value_y(&scale, top) - gap, (0.5, 1.0))
+            };
+            labels.push((label, label_x, label_y, anchor, false));
+        }
+    }
+
+    for (label, x, y, anchor, inside) in labels {
+        let label_style = if inside { &inside_style } else { style };
+        draw_label(img, &label, label_style, x, y, anchor, 0.0);
+    }
+    if series_count > 1 {
+        draw_legend(img, chart, &plot);
+    }
+}
+
+// Work out the bars of a chart, and the values each one runs between.
+fn arrange_bars(chart: &Chart, mode: BarMode, series_count: usize, count: usize) -> Vec<Bar> {
+    let mut bars = vec![];
+    for category in 0..count {
+        // Stacks grow up from zero for positive values, and down from it for negative values.
+        let (mut above, mut below) = (0.0, 0.0);
+        let total: f64 = chart.series[..series_count]
+            .iter()
+            .filter_map(|series| series.values.get(category))
+            .filter(|value| value.is_finite())
+            .map(|value| value.abs())
+            .sum();
+
+        for (series, data) in chart.series[..series_count].iter().enumerate() {
+            let value = match data.values.get(category) {
+                Some(value) if value.is_finite() => *value,
+                _ => continue,
+            };
+            let bar = match mode {
+                // Bars grow from zero, which is clamped to the nearest end of the scale if it's out of range.
+                BarMode::Single | BarMode::Grouped => Bar {
+                    series,
+                    category,
+                    from: 0.0,
+                    to: value,
+                    label: value,
+                },
+                BarMode::Stacked | BarMode::PercentStacked => {
+                    let value = if mode == BarMode::PercentStacked && total > 0.0 {
+                        value / total * 100.0
+                    } else {
+                        value
+                    };
+                    let base = if value >= 0.0 { &mut above } else { &mut below };
+                    let from = *base;
+                    *base += value;
+                    Bar {
+                        series,
+                        category,
+                        from,
+                        to: *base,
+                        label: value,
+                    }
+                }
+            };
+            bars.push(bar);
+        }
+    }
+    bars
+}
+
+// Draw a single bar, filled with a colour, gradient or image, with optionally rounded corners.
+#[allow(clippy::too_many_arguments)]
+fn draw_bar(
+    img: &mut DynamicImage,
+    fill: &BarFill,
+    color: &Rgb,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    corner_radius: f32,
+) {
+    let radius = corner_radius.min(width / 2.0).min(height / 2.0).max(0.0);
+    let (pixel_width, pixel_height) = (width as u32, height as u32);
+    match fill {
+        BarFill::Solid if radius > 0.0 => {
+            let bar = raster::rounded_rect(x, y, width, height, radius);
+            raster::fill_contours(img, &[bar], Rgba::from(color).into(), Quality::AntiAliased);
+        }
+        BarFill::Solid => {
+            draw_solid_rect(img, color, pixel_width, pixel_height, x as i32, y as i32)
+        }
+        BarFill::Gradient(preset) if radius == 0.0 => draw_preset_rect_gradient(
+            img,
+            pixel_width,
+            pixel_height,
+            x as u32,
+            y as u32,
+            preset,
+            1.0,
+        ),
+        BarFill::Image(bar_img) if radius == 0.0 => {
+            draw_image_as_bar(img, bar_img, pixel_width, pixel_height, x as u32, y as u32)
+        }
+        _ => {
+            let mut bar = match fill {
+                BarFill::Image(bar_img) => {
+                    resize::resample(bar_img, pixel_width, pixel_height, Resampling::default())
+                }
+                BarFill::Gradient(preset) => {
+                    elements::create_gradient_preset(pixel_width, pixel_height, preset)
+                }
+                BarFill::Solid => unreachable!(),
+            };
+            clip_image(
+                &mut bar,
+                &Mask::rounded_rect(pixel_width, pixel_height, radius),
+            );
+            blend::composite(img, &bar, x as i32, y as i32, 1.0);
+        }
     }
-    draw_text(
-        img,
-        &barchart.title,
-        10,
-        start_y as u32,
-        "Lato-Regular",
-        50.0,
-        &yellow,
-    );
 }
 
-/// Draw a linechart, with a specified title and data.
+// Format a value for a label, with no more than two decimal places, and none if it's a whole number.
+pub(crate) fn format_value(value: f64) -> String {
+    let label = format!("{:.2}", value);
+    let label = label.trim_end_matches('0').trim_end_matches('.');
+    match label {
+        "-0" => "0".to_string(),
+        label => label.to_string(),
+    }
+}
+
+/// Draw a linechart, with a specified title and data. Each series is drawn as a line in its own colour,
+/// over axes labelled with the chart's categories and values, with a legend if there's more than one series.
 ///
-/// #### Arguments
+/// ### Arguments
 /// * `img` - Image to draw the linechart onto.
 /// * `chart` - Chart struct, which contains all data & meta-data about the barchart.
-pub fn draw_linechart(mut img: &mut DynamicImage, chart: &Chart) {
-    draw_labels(&mut img, chart);
-    let axis_len = chart.width as f32 * 0.8;
-    let y_origin = 20.0 + axis_len;
-
-    let x_inc = axis_len / chart.data.len() as f32;
+pub fn draw_linechart(img: &mut DynamicImage, chart: &Chart) {
+    draw_linechart_with_options(img, chart, &LineOptions::default());
+}
 
-    let mut start_x = 20.0;
-    let line_pixel = image::Rgba([255, 167, 90, 255]);
-    let max_item = chart.data.iter().max().unwrap();
+/// Draw a linechart and accentuate the points, with a specified title and data.
+///
+/// ### Arguments
+/// * `img` - Image to draw the linechart onto.
+/// * `chart` - Chart struct, which contains all data & meta-data about the chart.
+pub fn draw_linechart_points(img: &mut DynamicImage, chart: &Chart) {
+    let options = LineOptions {
+        marker: Marker::Circle,
+        ..LineOptions::default()
+    };
+    draw_linechart_with_options(img, chart, &options);
+}
 
-    let mut start_y = y_origin;
+/// Draw an area chart, where each series is drawn as a smooth line, with the area beneath it
+/// filled with a gradient which fades out towards the baseline.
+///
+/// ### Arguments
+/// * `img` - Image to draw the area chart onto.
+/// * `chart` - Chart struct, which contains all data & meta-data about the chart.
+pub fn draw_areachart(img: &mut DynamicImage, chart: &Chart) {
+    let options = LineOptions {
+        smoothing: Smoothing::Monotone,
+        area: AreaFill::Gradient(0.6),
+        ..LineOptions::default()
+    };
+    draw_linechart_with_options(img, chart, &options);
+}
 
-    for item in &chart.data {
-        let div: f32 = *max_item as f32 / *item as f32;
+/// Draw a linechart, with the thickness of its lines, smoothing, markers and area fill set by `options`.
+/// Lines are broken where a series has a missing value, such as `f64::NAN`.
+///
+/// ### Arguments
+/// * `img` - Image to draw the linechart onto.
+/// * `chart` - Chart struct, which contains all data & meta-data about the chart.
+/// * `options` - Line thickness, smoothing, markers and area fill.
+pub fn draw_linechart_with_options(img: &mut DynamicImage, chart: &Chart, options: &LineOptions) {
+    draw_title(img, chart);
+    let plot = draw_axes(img, chart);
+    let scale = chart.value_scale();
+    let count = chart.category_count();
+    let point = |i: usize, value: f64| (plot.category_x(i, count), plot.value_y(&scale, value));
+
+    let curves: Vec<Vec<Vec<(f32, f32)>>> = chart
+        .series
+        .iter()
+        .map(|series| curve_runs(&series.values, &point, options.smoothing))
+        .collect();
+
+    // Areas are drawn first, so that the lines and markers of every series sit on top of them.
+    let baseline = plot.value_y(&scale, scale.baseline());
+    if options.area != AreaFill::None {
+        for (series, runs) in chart.series.iter().zip(&curves) {
+            for curve in runs {
+                fill_area(img, curve, baseline, &series.color, options.area);
+            }
+        }
+    }
 
-        let y_dist = y_origin - (axis_len / div);
-        draw_line_segment_mut(
+    for (series, runs) in chart.series.iter().zip(&curves) {
+        raster::fill_contours(
             img,
-            (start_x as f32, start_y as f32),
-            (start_x + x_inc, y_dist),
-            line_pixel,
+            &line_contours(runs, options.thickness),
+            Rgba::from(&series.color).into(),
+            Quality::AntiAliased,
         );
-        start_x += x_inc;
-        start_y = y_dist;
     }
-}
-
-/// Draw a linechart and accentuate the points, with a specified title and data.
-// pub fn draw_linechart_points(mut img: &mut DynamicImage, chart: &Chart) {
-//     draw_labels(&mut img, chart);
-//     let axis_len = (chart.width as f32 * 0.8);
-//     let y_origin = 20.0 + axis_len;
 
-//     let x_inc = axis_len / chart.data.len() as f32;
+    for series in &chart.series {
+        for (i, value) in series.values.iter().enumerate() {
+            if !value.is_finite() {
+                continue;
+            }
+            let (x, y) = point(i, *value);
+            if let Some(marker) = marker_contour(options.marker, x, y, options.marker_size) {
+                raster::fill_contours(
+                    img,
+                    &[marker],
+                    Rgba::from(series.color_at(i)).into(),
+                    Quality::AntiAliased,
+                );
+            }
+        }
+    }
+    draw_legend(img, chart, &plot);
+}
 
-//     let mut start_x = 20.0;
-//     let line_pixel = image::Rgba([255, 167, 90, 255]);
-//     let max_item = chart.data.iter().max().unwrap();
+// Split values into runs of consecutive finite values, broken wherever a value is missing,
+// and work out the points along a curve through each run.
+fn curve_runs(
+    values: &[f64],
+    point: &dyn Fn(usize, f64) -> (f32, f32),
+    smoothing: Smoothing,
+) -> Vec<Vec<(f32, f32)>> {
+    values
+        .iter()
+        .enumerate()
+        .collect::<Vec<_>>()
+        .split(|(_, value)| !value.is_finite())
+        .filter(|run| !run.is_empty())
+        .map(|run| {
+            let points: Vec<(f32, f32)> = run.iter().map(|(i, value)| point(*i, **value)).collect();
+            smooth_curve(&points, smoothing)
+        })
+        .collect()
+}
 
-//     let mut start_y = y_origin;
+// Contours of lines along each run of a curve.
+fn line_contours(runs: &[Vec<(f32, f32)>], thickness: f32) -> Vec<raster::Contour> {
+    runs.iter()
+        .flat_map(|curve| match curve.len() {
+            // A value on its own is shown as a dot, as there's nothing to join it to.
+            1 => vec![raster::circle(curve[0].0, curve[0].1, thickness)],
+            _ => raster::polyline(curve, thickness),
+        })
+        .collect()
+}
 
-//     for item in &chart.data {
-//         let div: f32 = *max_item as f32 / *item as f32;
+// Number of straight segments each stretch of a smoothed curve is drawn with.
+const CURVE_STEPS: usize = 16;
 
-//         let y_dist = y_origin - (axis_len / div);
-//         draw_line_segment_mut(img, (start_x as f32, start_y as f32), (start_x + x_inc, y_dist), line_pixel);
+// Points along a line through a run of points, either straight between them or smoothed.
+fn smooth_curve(points: &[(f32, f32)], smoothing: Smoothing) -> Vec<(f32, f32)> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    match smoothing {
+        Smoothing::None => points.to_vec(),
+        Smoothing::CatmullRom => catmull_rom(points),
+        Smoothing::Monotone => monotone_curve(points),
+    }
+}
 
-//         // draw circle at end coordinate.
-//         // let circle_radius = ;
-//         // let white_pixel = ;
-//         draw_filled_circle_mut(img, (start_x + x_inc, y_dist), circle_radius, white_pixel);
+// Points along a Catmull-Rom spline, which passes through every point, with the ends of the line
+// treated as if the first and last points were repeated.
+fn catmull_rom(points: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    let last = points.len() - 1;
+    let mut curve = vec![points[0]];
+    for i in 0..last {
+        let p0 = points[i.saturating_sub(1)];
+        let (p1, p2) = (points[i], points[i + 1]);
+        let p3 = points[(i + 2).min(last)];
+        let spline = |a: f32, b: f32, c: f32, d: f32, t: f32| {
+            0.5 * (2.0 * b
+                + (c - a) * t
+                + (2.0 * a - 5.0 * b + 4.0 * c - d) * t * t
+                + (3.0 * b - a - 3.0 * c + d) * t * t * t)
+        };
+        for step in 1..=CURVE_STEPS {
+            let t = step as f32 / CURVE_STEPS as f32;
+            curve.push((
+                spline(p0.0, p1.0, p2.0, p3.0, t),
+                spline(p0.1, p1.1, p2.1, p3.1, t),
+            ));
+        }
+    }
+    curve
+}
 
-//         start_x += x_inc;
-//         start_y = y_dist;
-//     }
+// Points along a monotone cubic curve (Fritsch-Carlson), which passes through every point without
+// overshooting them, so it never rises above a peak or dips below a trough in the data.
+fn monotone_curve(points: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    let slopes: Vec<f32> = points
+        .windows(2)
+        .map(|pair| {
+            let dx = pair[1].0 - pair[0].0;
+            if dx.abs() < f32::EPSILON {
+                0.0
+            } else {
+                (pair[1].1 - pair[0].1) / dx
+            }
+        })
+        .collect();
+
+    // Tangents at each point average the slopes either side, and are flat at peaks and troughs.
+    let mut tangents = Vec::with_capacity(points.len());
+    tangents.push(slopes[0]);
+    for pair in slopes.windows(2) {
+        if pair[0] * pair[1] <= 0.0 {
+            tangents.push(0.0);
+        } else {
+            tangents.push((pair[0] + pair[1]) / 2.0);
+        }
+    }
+    tangents.push(slopes[slopes.len() - 1]);
+
+    // Limit the tangents so that the curve can't overshoot between points.
+    for (i, slope) in slopes.iter().enumerate() {
+        if *slope == 0.0 {
+            tangents[i] = 0.0;
+            tangents[i + 1] = 0.0;
+            continue;
+        }
+        let (a, b) = (tangents[i] / slope, tangents[i + 1] / slope);
+        let length = a * a + b * b;
+        if length > 9.0 {
+            let tau = 3.0 / length.sqrt();
+            tangents[i] = tau * a * slope;
+            tangents[i + 1] = tau * b * slope;
+        }
+    }
 
-// }
+    let mut curve = vec![points[0]];
+    for (i, pair) in points.windows(2).enumerate() {
+        let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+        let h = x1 - x0;
+        for step in 1..=CURVE_STEPS {
+            let t = step as f32 / CURVE_STEPS as f32;
+            let (t2, t3) = (t * t, t * t * t);
+            let y = (2.0 * t3 - 3.0 * t2 + 1.0) * y0
+                + (t3 - 2.0 * t2 + t) * h * tangents[i]
+                + (3.0 * t2 - 2.0 * t3) * y1
+                + (t3 - t2) * h * tangents[i + 1];
+            curve.push((x0 + t * h, y));
+        }
+    }
+    curve
+}
 
-// Draw labels onto the axes of a chart, typically for bar or line charts.
-fn draw_labels(img: &mut DynamicImage, chart: &Chart) {
-    draw_axes(img, chart);
-    let axis_len = chart.width as f32 * 0.8;
-    let x_inc = axis_len / chart.data.len() as f32;
-    let yellow = Rgb {
-        r: 150,
-        g: 150,
-        b: 30,
+// Fill the area between a line and the baseline, in a translucent colour, or with a gradient
+// which is strongest furthest from the baseline and fades out towards it.
+fn fill_area(
+    img: &mut DynamicImage,
+    curve: &[(f32, f32)],
+    baseline: f32,
+    color: &Rgb,
+    fill: AreaFill,
+) {
+    let (first, last) = match (curve.first(), curve.last()) {
+        (Some(first), Some(last)) if curve.len() > 1 => (*first, *last),
+        _ => return,
     };
-    let mut start_x = 20.0;
-
-    let start_y = 20.0 + (chart.width as f32 * 0.8);
-
-    for label in &chart.labels {
-        draw_text(
-            img,
-            label,
-            start_x as u32,
-            start_y as u32,
-            "Roboto-Regular",
-            30.0,
-            &yellow,
-        );
+    let mut outline = curve.to_vec();
+    outline.push((last.0, baseline));
+    outline.push((first.0, baseline));
+    let area = raster::polygon(&outline);
+
+    match fill {
+        AreaFill::None => {}
+        AreaFill::Solid(opacity) => {
+            let alpha = (opacity.clamp(0.0, 1.0) * 255.0).round() as u8;
+            let color = Rgba::new(color.r, color.g, color.b, alpha);
+            raster::fill_contours(img, &[area], color.into(), Quality::AntiAliased);
+        }
+        AreaFill::Gradient(opacity) => {
+            let mask = raster::rasterize(&[area], Quality::AntiAliased, img.width(), img.height());
+            if mask.is_empty() {
+                return;
+            }
+            let extent = curve
+                .iter()
+                .map(|(_, y)| (y - baseline).abs())
+                .fold(1.0, f32::max);
+            let layer = ImageBuffer::from_fn(mask.width, mask.height, |x, y| {
+                let distance = ((mask.y + y) as f32 + 0.5 - baseline).abs();
+                let alpha = mask.get(x, y) * opacity.clamp(0.0, 1.0) * (distance / extent).min(1.0);
+                image::Rgba([color.r, color.g, color.b, (alpha * 255.0).round() as u8])
+            });
+            let layer = DynamicImage::ImageRgba8(layer);
+            blend::composite(img, &layer, mask.x as i32, mask.y as i32, 1.0);
+        }
+    }
+}
 
-        start_x += x_inc;
+// Contour of a marker centred on a point, or `None` if there's no marker.
+fn marker_contour(marker: Marker, x: f32, y: f32, size: f32) -> Option<raster::Contour> {
+    let radius = size / 2.0;
+    match marker {
+        Marker::None => None,
+        Marker::Circle => Some(raster::circle(x, y, radius)),
+        Marker::Square => Some(raster::rect(x - radius, y - radius, size, size)),
+        // Diamonds and triangles are enlarged a little, so they look about as big as a square or circle.
+        Marker::Diamond => Some(raster::regular_polygon(x, y, radius * 1.3, 4, -PI / 2.0)),
+        Marker::Triangle => Some(raster::regular_polygon(x, y, radius * 1.3, 3, -PI / 2.0)),
     }
 }
 
-// Draw x and y-axes to the image, mainly for bar charts and line charts.
-fn draw_axes(img: &mut DynamicImage, chart: &Chart) {
-    let line_pixel = image::Rgba([255, 167, 90, 255]);
+/// Draw a scatter chart, with a marker at the x and y values of each point of each series,
+/// over axes which show numbers along both of them, with a legend if there's more than one series.
+/// Each series' x values are taken from its `x_values`, or its values' indices where they're missing.
+///
+/// ### Arguments
+/// * `img` - Image to draw the scatter chart onto.
+/// * `chart` - Chart struct, which contains all data & meta-data about the chart.
+pub fn draw_scatterchart(img: &mut DynamicImage, chart: &Chart) {
+    draw_scatterchart_with_options(img, chart, &ScatterOptions::default());
+}
 
-    let axis_len = chart.width as f32 * 0.8;
+/// Draw a bubble chart: a scatter chart where the area of each point's bubble is in proportion to
+/// its size, from each series' `sizes`.
+///
+/// ### Arguments
+/// * `img` - Image to draw the bubble chart onto.
+/// * `chart` - Chart struct, which contains all data & meta-data about the chart.
+pub fn draw_bubblechart(img: &mut DynamicImage, chart: &Chart) {
+    draw_scatterchart_with_options(img, chart, &ScatterOptions::bubbles());
+}
 
-    // Origin point
-    let start_x = 20.0;
-    let start_y = 20.0 + axis_len;
+/// Draw a scatter or bubble chart, with the markers and trend lines set by `options`.
+///
+/// ### Arguments
+/// * `img` - Image to draw the chart onto.
+/// * `chart` - Chart struct, which contains all data & meta-data about the chart.
+/// * `options` - Markers, their sizes and opacity, and whether to draw trend lines.
+pub fn draw_scatterchart_with_options(
+    img: &mut DynamicImage,
+    chart: &Chart,
+    options: &ScatterOptions,
+) {
+    draw_title(img, chart);
+    let plot = draw_scatter_axes(img, chart);
+    let (x_scale, y_scale) = (chart.x_scale(), chart.value_scale());
+    let point = |x: f64, y: f64| (plot.value_x(&x_scale, x), plot.value_y(&y_scale, y));
+    let alpha = (options.opacity.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    // Bubbles are sized relative to the largest across all series.
+    let largest = chart
+        .series
+        .iter()
+        .flat_map(|series| series.sizes.iter())
+        .filter(|size| size.is_finite())
+        .fold(0.0, |largest: f64, size| largest.max(*size));
+
+    for series in &chart.series {
+        let mut markers: Vec<(usize, f32)> = (0..series.values.len())
+            .map(|i| {
+                let size = match (options.bubbles, series.sizes.get(i)) {
+                    (true, Some(size)) if largest > 0.0 && size.is_finite() => {
+                        (size.max(0.0) / largest).sqrt() as f32 * options.marker_size
+                    }
+                    _ => options.marker_size,
+                };
+                (i, size)
+            })
+            .collect();
+        // Larger bubbles are drawn first, so they don't hide smaller ones.
+        markers.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        for (i, size) in markers {
+            let (x, y) = (series.x_at(i), series.values[i]);
+            if !x.is_finite() || !y.is_finite() {
+                continue;
+            }
+            let (x, y) = point(x, y);
+            if let Some(marker) = marker_contour(options.marker, x, y, size) {
+                let color = series.color_at(i);
+                raster::fill_contours(
+                    img,
+                    &[marker],
+                    Rgba::new(color.r, color.g, color.b, alpha).into(),
+                    Quality::AntiAliased,
+                );
+            }
+        }
 
-    // End point on y-axis
-    let end_y_yaxis: f32 = start_y - axis_len;
+        if options.trend_line {
+            if let Some(((x1, y1), (x2, y2))) = trend_line(&series.points()) {
+                let dashes = dashed_line(point(x1, y1), point(x2, y2), 2.0, 12.0);
+                raster::fill_contours(
+                    img,
+                    &dashes,
+                    Rgba::from(&series.color).into(),
+                    Quality::AntiAliased,
+                );
+            }
+        }
+    }
+    draw_legend(img, chart, &plot);
+}
 
-    // End point on x-axis
-    let end_x_xaxis: f32 = start_x + axis_len;
+/// Draw a sparkline: a small line chart without axes or labels, which can be placed inline next to
+/// text, such as on a KPI card. Values are scaled to fill the height of the sparkline, unless it has
+/// a fixed min or max.
+///
+/// ### Arguments
+/// * `img` - Image to draw the sparkline onto.
+/// * `sparkline` - The values, and the colour and style of the line.
+/// * `x` - X-coordinate of the left edge of the sparkline.
+/// * `y` - Y-coordinate of the top edge of the sparkline.
+/// * `width` - Width of the sparkline.
+/// * `height` - Height of the sparkline.
+pub fn draw_sparkline(
+    img: &mut DynamicImage,
+    sparkline: &Sparkline,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) {
+    let finite = sparkline.values.iter().filter(|value| value.is_finite());
+    let (data_min, data_max) = finite.fold((f64::INFINITY, f64::NEG_INFINITY), |range, value| {
+        (range.0.min(*value), range.1.max(*value))
+    });
+    if data_min > data_max {
+        return;
+    }
+    let min = sparkline.min.unwrap_or(data_min);
+    let max = sparkline.max.unwrap_or(data_max);
+    let scale = Scale::fixed(min, max, 2);
+
+    // Keep the line and the dot on the last value inside the sparkline's bounds.
+    let inset = match sparkline.mark_last {
+        true => sparkline.thickness * 1.5,
+        false => sparkline.thickness / 2.0,
+    };
+    let area = PlotArea {
+        x: x as f32 + inset,
+        y: y as f32 + inset,
+        width: (width as f32 - 2.0 * inset).max(1.0),
+        height: (height as f32 - 2.0 * inset).max(1.0),
+    };
+    let count = sparkline.values.len();
+    let point = |i: usize, value: f64| {
+        let x = match count {
+            1 => area.x + area.width / 2.0,
+            _ => area.x + area.width * i as f32 / (count - 1) as f32,
+        };
+        // A flat line sits in the middle of the sparkline, rather than along its bottom.
+        let y = match max - min {
+            range if range.abs() < f64::EPSILON => area.y + area.height / 2.0,
+            _ => area.value_y(&scale, value.clamp(min.min(max), max.max(min))),
+        };
+        (x, y)
+    };
 
-    // Draw y-axis
-    draw_line_segment_mut(
+    let runs = curve_runs(&sparkline.values, &point, sparkline.smoothing);
+    for curve in &runs {
+        fill_area(img, curve, area.bottom(), &sparkline.color, sparkline.area);
+    }
+    raster::fill_contours(
         img,
-        (start_x as f32, start_y as f32),
-        (start_x, end_y_yaxis),
-        line_pixel,
+        &line_contours(&runs, sparkline.thickness),
+        Rgba::from(&sparkline.color).into(),
+        Quality::AntiAliased,
     );
 
-    // Draw x-axis
-    draw_line_segment_mut(
-        img,
-        (start_x as f32, start_y as f32),
-        (end_x_xaxis, start_y),
-        line_pixel,
-    );
+    if sparkline.mark_last {
+        let last = sparkline.values.iter().rposition(|value| value.is_finite());
+        if let Some(i) = last {
+            let (x, y) = point(i, sparkline.values[i]);
+            raster::fill_contours(
+                img,
+                &[raster::circle(x, y, sparkline.thickness * 1.5)],
+                Rgba::from(&sparkline.color).into(),
+                Quality::AntiAliased,
+            );
+        }
+    }
 }
 
-// Draw an image as a bar component of a bar chart.
-// This can be used for custom bar colours or custom bar images within bar charts.
-fn draw_image_as_bar(
-    img: &mut DynamicImage,
-    bar_img: &DynamicImage,
-    bar_width: u32,
-    bar_height: u32,
-    start_x: u32,
-    start_y: u32,
-) {
-    let sampling_filter = image::imageops::FilterType::Nearest;
-    let resized_img = image::DynamicImage::ImageRgba8(image::imageops::resize(
-        bar_img,
-        bar_width as u32,
-        bar_height as u32,
-        sampling_filter,
-    ));
-    image::imageops::overlay(img, &resized_img, start_x, start_y);
+// The ends of a least-squares line of best fit through points, across the range of their x values,
+// or `None` if there are too few points, or they all have the same x value.
+fn trend_line(points: &[(f64, f64)]) -> Option<((f64, f64), (f64, f64))> {
+    if points.len() < 2 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    if variance.abs() < f64::EPSILON {
+        return None;
+    }
+    let slope = covariance / variance;
+    let fit = |x: f64| mean_y + slope * (x - mean_x);
+    let (min_x, max_x) = points
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |range, (x, _)| {
+            (range.0.min(*x), range.1.max(*x))
+        });
+    Some(((min_x, fit(min_x)), (max_x, fit(max_x))))
 }
 
-// STRUCTS
+// Contours of a dashed line, with dashes and the gaps between them both `dash` pixels long.
+fn dashed_line(
+    start: (f32, f32),
+    end: (f32, f32),
+    thickness: f32,
+    dash: f32,
+) -> Vec<raster::Contour> {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let length = (dx * dx + dy * dy).sqrt();
+    if length < f32::EPSILON {
+        return vec![];
+    }
+    let along = |distance: f32| {
+        let t = distance.min(length) / length;
+        (start.0 + dx * t, start.1 + dy * t)
+    };
+    let dashes = (length / (2.0 * dash)).ceil() as usize;
+    (0..dashes)
+        .map(|i| {
+            let from = i as f32 * 2.0 * dash;
+            raster::line(along(from), along(from + dash), thickness)
+        })
+        .collect()
+}
 
-/// Chart type, containing data, labels, and other metadata about a chart.
-#[derive(Debug)]
-pub struct Chart {
-    pub title: String,
-    pub color: Rgb,
-    pub data: Vec<u16>,
+/// Draw a pie chart of the first series of a chart, with a slice for each value, labelled with its
+/// percentage of the total. Slices are coloured from the chart's palette, and listed in a legend by label.
+///
+/// ### Arguments
+/// * `img` - Image to draw the pie chart onto.
+/// * `chart` - Chart struct, which contains all data & meta-data about the chart.
+pub fn draw_piechart(img: &mut DynamicImage, chart: &Chart) {
+    draw_piechart_with_options(img, chart, &PieOptions::default());
+}
+
+/// Draw a donut chart of the first series of a chart, which is a pie chart with a hole in the middle
+/// for a label, such as the total.
+///
+/// ### Arguments
+/// * `img` - Image to draw the donut chart onto.
+/// * `chart` - Chart struct, which contains all data & meta-data about the chart.
+/// * `centre_label` - Text in the middle of the donut.
+pub fn draw_donutchart(img: &mut DynamicImage, chart: &Chart, centre_label: &str) {
+    draw_piechart_with_options(img, chart, &PieOptions::donut(centre_label));
+}
+
+/// Draw a pie or donut chart of the first series of a chart, with exploded slices, labels and
+/// the size of the hole set by `options`. Negative values are drawn as empty slices.
+///
+/// ### Arguments
+/// * `img` - Image to draw the chart onto.
+/// * `chart` - Chart struct, which contains all data & meta-data about the chart.
+/// * `options` - Hole size, start angle, exploded slices and labels.
+pub fn draw_piechart_with_options(img: &mut DynamicImage, chart: &Chart, options: &PieOptions) {
+    draw_title(img, chart);
+    let style = &chart.style;
+    let content = content_area(chart);
+
+    let values: Vec<f64> = chart
+        .first_values()
+        .iter()
+        .map(|value| {
+            if value.is_finite() {
+                value.max(0.0)
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let total: f64 = values.iter().sum();
+    let colors: Vec<Rgb> = (0..values.len().max(chart.labels.len()))
+        .map(|i| style.palette_color(i))
+        .collect();
+
+    // The legend sits to the right of the pie, which is centred in the space left.
+    let entries: Vec<(&str, &Rgb)> = chart
+        .labels
+        .iter()
+        .zip(&colors)
+        .map(|(label, color)| (label.as_str(), color))
+        .collect();
+    let legend = if style.legend && !entries.is_empty() {
+        Some(legend_size(style, &entries))
+    } else {
+        None
+    };
+    let pie_width = content.width - legend.map_or(0.0, |(width, _)| width + style.padding);
+    let offset = if options.exploded.is_empty() {
+        0.0
+    } else {
+        options.explode_offset
+    };
+    let radius = (pie_width.min(content.height) / 2.0 / (1.0 + offset)).max(1.0);
+    let inner_radius = radius * options.inner_radius.clamp(0.0, 0.95);
+    let (cx, cy) = (
+        content.x + pie_width / 2.0,
+        content.y + content.height / 2.0,
+    );
+
+    let label_style = ChartStyle {
+        text_color: options.label_color.clone(),
+        ..style.clone()
+    };
+    let mut angle = options.start_angle;
+    for (i, value) in values.iter().enumerate() {
+        if total <= 0.0 || *value <= 0.0 {
+            continue;
+        }
+        let sweep = (value / total * 360.0) as f32;
+        let middle = raster_angle(angle + sweep / 2.0);
+        let (dx, dy) = (middle.cos(), middle.sin());
+        let shift = if options.exploded.contains(&i) {
+            options.explode_offset * radius
+        } else {
+            0.0
+        };
+        let (x, y) = (cx + dx * shift, cy + dy * shift);
+
+        let slice = raster::annular_sector(
+            x,
+            y,
+            inner_radius,
+            radius,
+            raster_angle(angle),
+            raster_angle(angle + sweep),
+        );
+        raster::fill_contours(
+            img,
+            &[slice],
+            Rgba::from(&colors[i]).into(),
+            Quality::AntiAliased,
+        );
+
+        // Slices too thin to hold their percentage are left unlabelled.
+        if options.percentages && sweep >= 14.0 {
+            let label_radius = if inner_radius > 0.0 {
+                (inner_radius + radius) / 2.0
+            } else {
+                radius * 0.65
+            };
+            let label = format!("{:.0}%", value / total * 100.0);
+            let (label_x, label_y) = (x + dx * label_radius, y + dy * label_radius);
+            draw_label(img, &label, &label_style, label_x, label_y, (0.5, 0.5), 0.0);
+        }
+        angle += sweep;
+    }
+
+    if let (Some(label), true) = (&options.centre_label, inner_radius > 0.0) {
+        let (width, height) = ((inner_radius * 1.4) as u32, (inner_radius * 0.6) as u32);
+        let centre_style = ChartStyle {
+            font: style.title_font.clone(),
+            font_size: text::fit_font_size(
+                label,
+                &style.title_font,
+                style.title_size * 2.0,
+                width,
+                height,
+            ),
+            text_color: style.title_color.clone(),
+            ..style.clone()
+        };
+        draw_label(img, label, &centre_style, cx, cy, (0.5, 0.5), 0.0);
+    }
+
+    if let Some((_, height)) = legend {
+        let x = cx + radius * (1.0 + offset) + style.padding;
+        draw_legend_entries(img, style, &entries, x, cy - height / 2.0);
+    }
+}
+
+/// Draw a progress ring: a circular track, filled clockwise from the top to show how far a value
+/// is through its range, with a label in the middle.
+///
+/// ### Arguments
+/// * `img` - Image to draw the ring onto.
+/// * `progress` - The value, its range, and the colours and label of the ring.
+/// * `x` - X-coordinate of the left edge of the ring.
+/// * `y` - Y-coordinate of the top edge of the ring.
+/// * `size` - Width and height of the ring.
+pub fn draw_progress_ring(
+    img: &mut DynamicImage,
+    progress: &RadialProgress,
+    x: u32,
+    y: u32,
+    size: u32,
+) {
+    let outer_radius = size as f32 / 2.0;
+    let thickness = outer_radius * progress.thickness.clamp(0.01, 1.0);
+    let radius = outer_radius - thickness / 2.0;
+    let (cx, cy) = (x as f32 + outer_radius, y as f32 + outer_radius);
+
+    let track = raster::arc(cx, cy, radius, thickness, 0.0, 2.0 * PI, false);
+    raster::fill_contours(
+        img,
+        &track,
+        progress.track_color.into(),
+        Quality::AntiAliased,
+    );
+    let fraction = progress.fraction() as f32;
+    if fraction > 0.0 {
+        let end = raster_angle(fraction * 360.0);
+        let filled = raster::arc(
+            cx,
+            cy,
+            radius,
+            thickness,
+            raster_angle(0.0),
+            end,
+            fraction < 1.0,
+        );
+        raster::fill_contours(
+            img,
+            &filled,
+            Rgba::from(&progress.color).into(),
+            Quality::AntiAliased,
+        );
+    }
+
+    let inner_radius = outer_radius - thickness;
+    let label = progress.label();
+    let (width, height) = ((inner_radius * 1.4) as u32, (inner_radius * 0.7) as u32);
+    let font_size = text::fit_font_size(&label, &progress.font, inner_radius * 0.6, width, height);
+    draw_label(
+        img,
+        &label,
+        &progress.label_style(font_size),
+        cx,
+        cy,
+        (0.5, 0.5),
+        0.0,
+    );
+}
+
+/// Draw a gauge: a semicircular track filled from left to right to show how far a value is through
+/// its range, with a needle pointing at the value, the value beneath it, and the ends of the range
+/// beneath each end of the track. The gauge is about two thirds as tall as it is wide.
+///
+/// ### Arguments
+/// * `img` - Image to draw the gauge onto.
+/// * `progress` - The value, its range, and the colours and label of the gauge.
+/// * `x` - X-coordinate of the left edge of the gauge.
+/// * `y` - Y-coordinate of the top edge of the gauge.
+/// * `width` - Width of the gauge.
+pub fn draw_gauge(img: &mut DynamicImage, progress: &RadialProgress, x: u32, y: u32, width: u32) {
+    let outer_radius = width as f32 / 2.0;
+    let thickness = outer_radius * progress.thickness.clamp(0.01, 1.0);
+    let radius = outer_radius - thickness / 2.0;
+    let (cx, cy) = (x as f32 + outer_radius, y as f32 + outer_radius);
+
+    let (start, end) = (raster_angle(-90.0), raster_angle(90.0));
+    let track = raster::arc(cx, cy, radius, thickness, start, end, false);
+    raster::fill_contours(
+        img,
+        &track,
+        progress.track_color.into(),
+        Quality::AntiAliased,
+    );
+    let fraction = progress.fraction() as f32;
+    let value_angle = raster_angle(-90.0 + fraction * 180.0);
+    if fraction > 0.0 {
+        let filled = raster::arc(cx, cy, radius, thickness, start, value_angle, false);
+        raster::fill_contours(
+            img,
+            &filled,
+            Rgba::from(&progress.color).into(),
+            Quality::AntiAliased,
+        );
+    }
+
+    let needle_length = radius - thickness * 0.75;
+    let hub = outer_radius * 0.06;
+    let tip = (
+        cx + needle_length * value_angle.cos(),
+        cy + needle_length * value_angle.sin(),
+    );
+    let needle = vec![
+        raster::line((cx, cy), tip, hub * 0.6),
+        raster::circle(cx, cy, hub),
+    ];
+    raster::fill_contours(
+        img,
+        &needle,
+        Rgba::from(&progress.text_color).into(),
+        Quality::AntiAliased,
+    );
+
+    let label_style = progress.label_style(outer_radius * 0.22);
+    draw_label(
+        img,
+        &progress.label(),
+        &label_style,
+        cx,
+        cy + hub * 2.0,
+        (0.5, 0.0),
+        0.0,
+    );
+    let scale = Scale::fixed(progress.min, progress.max, 2);
+    let range_style = progress.label_style(outer_radius * 0.1);
+    for (end_x, value) in [(cx - radius, progress.min), (cx + radius, progress.max)] {
+        let label = scale.label(value);
+        draw_label(img, &label, &range_style, end_x, cy + hub, (0.5, 0.0), 0.0);
+    }
+}
+
+/// Colours for charts which need one per category, such as the slices of a pie chart.
+///
+/// ### Arguments
+/// * `name` - Name of the palette. Can be: "default", "pastel", "vivid", "earth". Other names give the default palette.
+pub fn palette(name: &str) -> Vec<Rgb> {
+    let colors: &[(u8, u8, u8)] = match name {
+        "pastel" => &[
+            (161, 201, 244),
+            (255, 180, 130),
+            (141, 229, 161),
+            (255, 159, 155),
+            (208, 187, 255),
+            (222, 187, 155),
+            (250, 176, 228),
+            (255, 254, 163),
+        ],
+        "vivid" => &[
+            (230, 25, 75),
+            (60, 180, 75),
+            (255, 225, 25),
+            (67, 99, 216),
+            (245, 130, 49),
+            (145, 30, 180),
+            (70, 240, 240),
+            (240, 50, 230),
+        ],
+        "earth" => &[
+            (140, 81, 10),
+            (191, 129, 45),
+            (223, 194, 125),
+            (128, 205, 193),
+            (53, 151, 143),
+            (1, 102, 94),
+            (84, 48, 5),
+            (199, 234, 229),
+        ],
+        _ => &[
+            (78, 121, 167),
+            (242, 142, 43),
+            (225, 87, 89),
+            (118, 183, 178),
+            (89, 161, 79),
+            (237, 201, 72),
+            (176, 122, 161),
+            (255, 157, 167),
+        ],
+    };
+    colors.iter().map(|&(r, g, b)| Rgb { r, g, b }).collect()
+}
+
+// Convert an angle in degrees clockwise from the top, as charts measure them,
+// to radians clockwise from the positive x-axis, as `raster` does.
+fn raster_angle(degrees: f32) -> f32 {
+    (degrees - 90.0).to_radians()
+}
+
+/// Draw the axes of a chart, with tick marks, category labels along the x-axis, value labels along
+/// the y-axis, and optionally gridlines and axis titles, all in the chart's style.
+/// Category labels are rotated if they're too wide to sit side by side.
+///
+/// Returns the area inside the axes, where the chart's data should be plotted.
+///
+/// ### Arguments
+/// * `img` - Image to draw the axes onto.
+/// * `chart` - Chart struct, which contains all data & meta-data about the chart.
+pub fn draw_axes(img: &mut DynamicImage, chart: &Chart) -> PlotArea {
+    draw_oriented_axes(img, chart, false, &chart.value_scale(), None)
+}
+
+/// Draw the axes of a chart on their side, for horizontal bars, with category labels down the
+/// y-axis and value labels along the x-axis. The title of the chart's `x_axis` is drawn beside the
+/// categories, and the title of its `y_axis` beneath the values.
+///
+/// Returns the area inside the axes, where the chart's data should be plotted.
+///
+/// ### Arguments
+/// * `img` - Image to draw the axes onto.
+/// * `chart` - Chart struct, which contains all data & meta-data about the chart.
+pub fn draw_horizontal_axes(img: &mut DynamicImage, chart: &Chart) -> PlotArea {
+    draw_oriented_axes(img, chart, true, &chart.value_scale(), None)
+}
+
+/// Draw the axes of a scatter or bubble chart, with the numeric x values of its series along the
+/// x-axis, on the scale of the chart's `x_axis`, and their values up the y-axis.
+///
+/// Returns the area inside the axes, where the chart's data should be plotted.
+///
+/// ### Arguments
+/// * `img` - Image to draw the axes onto.
+/// * `chart` - Chart struct, which contains all data & meta-data about the chart.
+pub fn draw_scatter_axes(img: &mut DynamicImage, chart: &Chart) -> PlotArea {
+    let numbers = NumberAxis::from_scale(chart.x_scale());
+    draw_oriented_axes(img, chart, false, &chart.value_scale(), Some(&numbers))
+}
+
+// Draw the axes of a chart, with the categories along the x-axis, or down the y-axis if `horizontal`,
+// and values on the given scale along the other. If `numbers` is given, the x-axis shows those
+// numbers instead of categories.
+fn draw_oriented_axes(
+    img: &mut DynamicImage,
+    chart: &Chart,
+    horizontal: bool,
+    scale: &Scale,
+    numbers: Option<&NumberAxis>,
+) -> PlotArea {
+    let (plot, rotate_labels) = plot_area(chart, horizontal, scale, numbers);
+    let content = content_area(chart);
+    let style = &chart.style;
+    let scale = *scale;
+    let ticks = scale.ticks();
+    let count = chart.category_count();
+    let gap = style.font_size * 0.4;
+    let line_height = line_height(style);
+
+    // Lines are drawn through the middle of a pixel, so that they cover a single row or column.
+    let snap = |position: f32| position.floor() + 0.5;
+    let (left, bottom) = (snap(plot.x), snap(plot.bottom()));
+    let tick_position = |tick: f64| match horizontal {
+        true => snap(plot.value_x(&scale, tick)),
+        false => snap(plot.value_y(&scale, tick)),
+    };
+    // Position and label of each tick along the category axis.
+    let categories: Vec<(f32, Option<String>)> = match numbers {
+        Some(numbers) => {
+            // Numbers are labelled as often as they fit without colliding, rather than rotated.
+            let every = numbers.label_interval(plot.width, |label| {
+                text::measure_text(label, &style.font, style.font_size).0 as f32 + gap
+            });
+            numbers
+                .ticks
+                .iter()
+                .enumerate()
+                .map(|(i, (value, label))| {
+                    let label = Some(label.clone()).filter(|_| i % every == 0);
+                    (plot.value_x(&numbers.scale, *value), label)
+                })
+                .collect()
+        }
+        None => (0..count)
+            .map(|i| {
+                let position = match horizontal {
+                    true => plot.category_y(i, count),
+                    false => plot.category_x(i, count),
+                };
+                (position, chart.labels.get(i).cloned())
+            })
+            .collect(),
+    };
+    let x_tick = |x: f32| raster::line((x, bottom), (x, bottom + style.tick_length), 1.0);
+    let y_tick = |y: f32| raster::line((left - style.tick_length, y), (left, y), 1.0);
+
+    if style.gridlines {
+        let mut gridlines: Vec<_> = ticks
+            .iter()
+            .map(|tick| {
+                let position = tick_position(*tick);
+                match horizontal {
+                    true => raster::line((position, plot.y), (position, bottom), 1.0),
+                    false => raster::line((left, position), (plot.right(), position), 1.0),
+                }
+            })
+            .collect();
+        // Numbers along the x-axis get gridlines too, so points can be read off against them.
+        if numbers.is_some() {
+            for (position, _) in &categories {
+                let x = snap(*position);
+                gridlines.push(raster::line((x, plot.y), (x, bottom), 1.0));
+            }
+        }
+        raster::fill_contours(
+            img,
+            &gridlines,
+            style.grid_color.into(),
+            Quality::AntiAliased,
+        );
+    }
+
+    let mut lines = vec![
+        raster::line((left, plot.y), (left, bottom), 1.0),
+        raster::line((left, bottom), (plot.right(), bottom), 1.0),
+    ];
+    for tick in &ticks {
+        let position = tick_position(*tick);
+        lines.push(if horizontal {
+            x_tick(position)
+        } else {
+            y_tick(position)
+        });
+    }
+    for (position, _) in &categories {
+        let position = snap(*position);
+        lines.push(if horizontal {
+            y_tick(position)
+        } else {
+            x_tick(position)
+        });
+    }
+    let axis_color = Rgba::from(&style.axis_color);
+    raster::fill_contours(img, &lines, axis_color.into(), Quality::AntiAliased);
+
+    // Labels beside the y-axis end just short of its ticks, and labels below the x-axis start just below them.
+    let label_x = left - style.tick_length - gap;
+    let label_y = bottom + style.tick_length + gap;
+    for tick in &ticks {
+        let label = scale.label(*tick);
+        if horizontal {
+            let x = plot.value_x(&scale, *tick);
+            draw_label(img, &label, style, x, label_y, (0.5, 0.0), 0.0);
+        } else {
+            let y = plot.value_y(&scale, *tick);
+            draw_label(img, &label, style, label_x, y, (1.0, 0.5), 0.0);
+        }
+    }
+
+    for (position, label) in &categories {
+        let label = match label {
+            Some(label) => label,
+            None => continue,
+        };
+        if horizontal {
+            draw_label(img, label, style, label_x, *position, (1.0, 0.5), 0.0);
+        } else if rotate_labels {
+            // Rotated labels end at their tick, reading up towards it from the left.
+            let x = position + line_height * 0.35;
+            draw_label(img, label, style, x, label_y, (1.0, 0.0), -LABEL_ANGLE);
+        } else {
+            draw_label(img, label, style, *position, label_y, (0.5, 0.0), 0.0);
+        }
+    }
+
+    let (side_axis, bottom_axis) = match horizontal {
+        true => (&chart.x_axis, &chart.y_axis),
+        false => (&chart.y_axis, &chart.x_axis),
+    };
+    if let Some(title) = &bottom_axis.title {
+        let x = plot.x + plot.width / 2.0;
+        draw_label(img, title, style, x, content.bottom(), (0.5, 1.0), 0.0);
+    }
+    if let Some(title) = &side_axis.title {
+        let y = plot.y + plot.height / 2.0;
+        draw_label(img, title, style, content.x, y, (0.0, 0.5), -90.0);
+    }
+    plot
+}
+
+/// Draw a legend showing the name and colour of each series in a chart, either beside the chart's
+/// plot area or in its top-right corner, as the chart's style places it.
+/// Nothing is drawn if the chart has only one series, or if its style turns the legend off.
+///
+/// ### Arguments
+/// * `img` - Image to draw the legend onto.
+/// * `chart` - Chart struct, which contains all data & meta-data about the chart.
+/// * `plot` - Area inside the chart's axes, as returned by `draw_axes`.
+pub fn draw_legend(img: &mut DynamicImage, chart: &Chart, plot: &PlotArea) {
+    let style = &chart.style;
+    if !chart.shows_legend() {
+        return;
+    }
+    let entries = chart.legend_entries();
+    let gap = style.font_size * 0.4;
+    let (width, _) = legend_size(style, &entries);
+    let x = match style.legend_position {
+        LegendPosition::Right => content_area(chart).right() - width,
+        LegendPosition::Inside => plot.right() - width - gap,
+    };
+    draw_legend_entries(img, style, &entries, x, plot.y + gap);
+}
+
+// Size of a legend box listing the given entries.
+fn legend_size(style: &ChartStyle, entries: &[(&str, &Rgb)]) -> (f32, f32) {
+    let gap = style.font_size * 0.4;
+    let line_height = line_height(style);
+    let name_width = entries
+        .iter()
+        .map(|(name, _)| text::measure_text(name, &style.font, style.font_size).0)
+        .max()
+        .unwrap_or(0) as f32;
+    let width = gap * 3.0 + line_height * 0.6 + name_width;
+    let height = gap * 2.0 + line_height * entries.len() as f32;
+    (width, height)
+}
+
+// Draw a legend box with its top-left corner at `x`, `y`, with a swatch of colour beside each name.
+fn draw_legend_entries(
+    img: &mut DynamicImage,
+    style: &ChartStyle,
+    entries: &[(&str, &Rgb)],
+    x: f32,
+    y: f32,
+) {
+    let gap = style.font_size * 0.4;
+    let line_height = line_height(style);
+    let swatch = line_height * 0.6;
+    let (width, height) = legend_size(style, entries);
+    let (x, y) = (x.round(), y.round());
+    raster::fill_contours(
+        img,
+        &[raster::rect(x, y, width, height)],
+        style.legend_background.into(),
+        Quality::AntiAliased,
+    );
+    raster::fill_contours(
+        img,
+        &raster::rect_outline(x, y, width, height, 1.0),
+        Rgba::from(&style.axis_color).into(),
+        Quality::AntiAliased,
+    );
+
+    for (i, (name, color)) in entries.iter().enumerate() {
+        let row_y = y + gap + i as f32 * line_height;
+        let swatch_y = row_y + (line_height - swatch) / 2.0;
+        raster::fill_contours(
+            img,
+            &[raster::rect(x + gap, swatch_y, swatch, swatch)],
+            Rgba::from(*color).into(),
+            Quality::AntiAliased,
+        );
+        let name_x = x + gap * 2.0 + swatch;
+        draw_label(img, name, style, name_x, row_y, (0.0, 0.0), 0.0);
+    }
+}
+
+// Angle category labels are rotated by when they don't fit side by side, in degrees.
+const LABEL_ANGLE: f32 = 45.0;
+
+// The part of a chart left for its content, inside its padding and clear of its title.
+fn content_area(chart: &Chart) -> PlotArea {
+    let style = &chart.style;
+    let width = (chart.width as f32 - style.padding * 2.0).max(1.0);
+    let height = (chart.height as f32 - style.padding * 2.0).max(1.0);
+    let (x, y) = (
+        chart.x as f32 + style.padding,
+        chart.y as f32 + style.padding,
+    );
+    let title_height = title_height(chart, width);
+    let top = match style.title_position {
+        TitlePosition::Top => y + title_height,
+        _ => y,
+    };
+    PlotArea {
+        x,
+        y: top,
+        width,
+        height: (height - title_height).max(1.0),
+    }
+}
+
+// Height taken up by a chart's title, including the gap between it and the rest of the chart.
+fn title_height(chart: &Chart, width: f32) -> f32 {
+    let style = &chart.style;
+    if chart.title.is_empty() || style.title_position == TitlePosition::None {
+        return 0.0;
+    }
+    let lines = text::wrap_text(
+        &chart.title,
+        &style.title_font,
+        style.title_size,
+        width as u32,
+    );
+    let (_, line_height) = text::measure_text("0", &style.title_font, style.title_size);
+    (lines.len() as u32 * line_height) as f32 + style.title_size * 0.5
+}
+
+// Draw a chart's title above or below the rest of the chart, as its style places it.
+fn draw_title(img: &mut DynamicImage, chart: &Chart) {
+    let style = &chart.style;
+    let width = (chart.width as f32 - style.padding * 2.0).max(1.0);
+    let x = chart.x as f32 + style.padding;
+    let y = match style.title_position {
+        TitlePosition::None => return,
+        TitlePosition::Top => chart.y as f32 + style.padding,
+        TitlePosition::Bottom => {
+            let gap = style.title_size * 0.5;
+            (chart.y + chart.height) as f32 - style.padding - title_height(chart, width) + gap
+        }
+    };
+    text::draw_text_block(
+        img,
+        &chart.title,
+        x.max(0.0) as u32,
+        y.max(0.0) as u32,
+        width as u32,
+        &style.title_font,
+        style.title_size,
+        &style.title_color,
+        style.title_align,
+    );
+}
+
+// Work out where the axes of a chart go, with the categories down the y-axis if `horizontal`,
+// or `numbers` along the x-axis if they're given, leaving room for their labels and titles.
+// Returns the area inside the axes, and whether category labels need rotating so they don't collide.
+fn plot_area(
+    chart: &Chart,
+    horizontal: bool,
+    scale: &Scale,
+    numbers: Option<&NumberAxis>,
+) -> (PlotArea, bool) {
+    let content = content_area(chart);
+    let style = &chart.style;
+    let gap = style.font_size * 0.4;
+    let line_height = line_height(style);
+    let label_width =
+        |label: &str| text::measure_text(label, &style.font, style.font_size).0 as f32;
+    let title_space = |axis: &Axis| match axis.title {
+        Some(_) => line_height + gap,
+        None => 0.0,
+    };
+    let tick_labels: Vec<String> = scale
+        .ticks()
+        .iter()
+        .map(|tick| scale.label(*tick))
+        .collect();
+    let widest_tick = tick_labels
+        .iter()
+        .map(|label| label_width(label))
+        .fold(0.0, f32::max);
+    let category_labels: Vec<String> = match numbers {
+        Some(numbers) => numbers
+            .ticks
+            .iter()
+            .map(|(_, label)| label.clone())
+            .collect(),
+        None => chart.labels.clone(),
+    };
+    let widest_category = category_labels
+        .iter()
+        .map(|label| label_width(label))
+        .fold(0.0, f32::max);
+    let label_space = style.tick_length + gap;
+    // A legend beside the plot takes space from its right-hand side.
+    let content = match (chart.shows_legend(), style.legend_position) {
+        (true, LegendPosition::Right) => {
+            let (legend_width, _) = legend_size(style, &chart.legend_entries());
+            PlotArea {
+                width: (content.width - legend_width - style.padding).max(1.0),
+                ..content
+            }
+        }
+        _ => content,
+    };
+
+    if horizontal {
+        let left = content.x + title_space(&chart.x_axis) + widest_category + label_space;
+        // The last value label is centred on the end of the x-axis, so overhangs it by half its width.
+        let overhang = tick_labels
+            .last()
+            .map_or(0.0, |label| label_width(label) / 2.0);
+        let bottom = label_space + line_height + title_space(&chart.y_axis);
+        let plot = PlotArea {
+            x: left,
+            y: content.y,
+            width: (content.right() - overhang - left).max(1.0),
+            height: (content.height - bottom).max(1.0),
+        };
+        return (plot, false);
+    }
+
+    let left = content.x + title_space(&chart.y_axis) + widest_tick + label_space;
+    // The top and bottom tick labels are centred on the ends of the y-axis, so overhang it by half a line.
+    let top = content.y + line_height / 2.0;
+    // Numbers along the x-axis end with a label centred on its end, which overhangs it by half its width.
+    let overhang = match numbers {
+        Some(_) => category_labels
+            .last()
+            .map_or(0.0, |label| label_width(label) / 2.0),
+        None => 0.0,
+    };
+    let width = (content.right() - overhang - left).max(1.0);
+
+    let count = chart.category_count().max(1);
+    let rotate_labels = numbers.is_none() && widest_category + gap > width / count as f32;
+    let label_height = if rotate_labels {
+        frames::rotated_size(widest_category, line_height, LABEL_ANGLE).1
+    } else {
+        line_height
+    };
+    let bottom = label_space + label_height + title_space(&chart.x_axis);
+    let plot = PlotArea {
+        x: left,
+        y: top,
+        width,
+        height: (content.bottom() - bottom - top).max(1.0),
+    };
+    (plot, rotate_labels)
+}
+
+// Numbers shown along the x-axis of a chart in place of categories, such as for scatter charts
+// and histograms.
+struct NumberAxis {
+    // Scale which places values along the axis.
+    scale: Scale,
+    // Value and label of each tick.
+    ticks: Vec<(f64, String)>,
+}
+
+impl NumberAxis {
+    // Ticks at nice numbers along a scale.
+    fn from_scale(scale: Scale) -> NumberAxis {
+        let ticks = scale
+            .ticks()
+            .iter()
+            .map(|tick| (*tick, scale.label(*tick)))
+            .collect();
+        NumberAxis { scale, ticks }
+    }
+
+    // Ticks at the edges of a histogram's bins, from the first edge to the last.
+    fn edges(edges: &[f64]) -> NumberAxis {
+        let (first, last) = match (edges.first(), edges.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => (0.0, 1.0),
+        };
+        // Labels are rounded to the fewest decimal places which keep them within a few percent of a
+        // bin's width of the edge, so uneven edges like 101.8642 are shown as 102.
+        let narrowest = edges
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .fold(f64::INFINITY, f64::min);
+        let decimals = (0..=3)
+            .find(|decimals| {
+                let factor = 10f64.powi(*decimals);
+                edges
+                    .iter()
+                    .all(|edge| ((edge * factor).round() / factor - edge).abs() <= narrowest * 0.05)
+            })
+            .unwrap_or(3) as usize;
+        let ticks = edges
+            .iter()
+            .map(|edge| (*edge, format!("{:.*}", decimals, edge)))
+            .collect();
+        NumberAxis {
+            scale: Scale::fixed(first, last, 2),
+            ticks,
+        }
+    }
+
+    // How many ticks apart labels need to be so that they don't collide, along an axis of `length`
+    // pixels, given the space each label needs.
+    fn label_interval(&self, length: f32, label_space: impl Fn(&str) -> f32) -> usize {
+        let widest = self
+            .ticks
+            .iter()
+            .map(|(_, label)| label_space(label))
+            .fold(0.0, f32::max);
+        // Ticks are closest together where they're evenly spaced, so take the smallest gap between them.
+        let spacing = self
+            .ticks
+            .windows(2)
+            .map(|pair| (self.scale.fraction(pair[1].0) - self.scale.fraction(pair[0].0)).abs())
+            .fold(f64::INFINITY, f64::min) as f32
+            * length;
+        if spacing.is_finite() && spacing > 0.0 {
+            ((widest / spacing).ceil() as usize).max(1)
+        } else {
+            1
+        }
+    }
+}
+
+// Height of a line of text in a chart's style.
+fn line_height(style: &ChartStyle) -> f32 {
+    text::measure_text("0", &style.font, style.font_size).1 as f32
+}
+
+// Draw a single line of text in a chart's style, optionally rotated clockwise by `degrees`.
+// `anchor` is the point of the label's bounding box, as fractions of its width and height,
+// which is placed at `x`, `y`: (0.0, 0.0) for its top-left corner, or (0.5, 0.5) for its centre.
+fn draw_label(
+    img: &mut DynamicImage,
+    label: &str,
+    style: &ChartStyle,
+    x: f32,
+    y: f32,
+    anchor: (f32, f32),
+    degrees: f32,
+) {
+    let (width, height) = text::measure_text(label, &style.font, style.font_size);
+    if width == 0 {
+        return;
+    }
+    // Draw onto a transparent layer of the text's own colour, so it can be rotated before it's composited.
+    let color = &style.text_color;
+    let mut layer = DynamicImage::ImageRgba8(ImageBuffer::from_pixel(
+        width + 2,
+        height,
+        image::Rgba([color.r, color.g, color.b, 0]),
+    ));
+    text::draw_text_block(
+        &mut layer,
+        label,
+        0,
+        0,
+        width + 2,
+        &style.font,
+        style.font_size,
+        color,
+        TextAlign::Left,
+    );
+    let layer = if degrees == 0.0 {
+        layer
+    } else {
+        // Rotation never shrinks the canvas, so trim it to the rotated label's own bounds.
+        let rotated = frames::rotate(&layer, degrees);
+        let (bounds_width, bounds_height) =
+            frames::rotated_size(width as f32 + 2.0, height as f32, degrees);
+        let (bounds_width, bounds_height) = (
+            (bounds_width.ceil() as u32).min(rotated.width()),
+            (bounds_height.ceil() as u32).min(rotated.height()),
+        );
+        rotated.crop_imm(
+            (rotated.width() - bounds_width) / 2,
+            (rotated.height() - bounds_height) / 2,
+            bounds_width,
+            bounds_height,
+        )
+    };
+
+    let (width, height) = layer.dimensions();
+    let left = (x - anchor.0 * width as f32).round() as i32;
+    let top = (y - anchor.1 * height as f32).round() as i32;
+    blend::composite(img, &layer, left, top, 1.0);
+}
+
+// Draw an image as a bar component of a bar chart.
+// This can be used for custom bar colours or custom bar images within bar charts.
+fn draw_image_as_bar(
+    img: &mut DynamicImage,
+    bar_img: &DynamicImage,
+    bar_width: u32,
+    bar_height: u32,
+    start_x: u32,
+    start_y: u32,
+) {
+    let resized_img = resize::resample(
+        bar_img,
+        bar_width as u32,
+        bar_height as u32,
+        Resampling::default(),
+    );
+    blend::composite(img, &resized_img, start_x as i32, start_y as i32, 1.0);
+}
+
+// Position and length along an axis of a bar running between two values, measured from the start
+// of the axis, in pixels. Values beyond the ends of the scale are cut off at its ends.
+fn span_extent(scale: &Scale, from: f64, to: f64, length: f32) -> (f32, f32) {
+    if !from.is_finite() || !to.is_finite() {
+        return (0.0, 0.0);
+    }
+    let position = |value: f64| (scale.fraction(value).clamp(0.0, 1.0) as f32 * length).round();
+    let (start, end) = (position(from), position(to));
+    (start.min(end), (end - start).abs())
+}
+
+// STRUCTS
+
+/// A named series of values within a chart, drawn in its own colour.
+#[derive(Debug, Clone)]
+pub struct Series {
+    pub name: String,
+    pub values: Vec<f64>,
+    pub color: Rgb,
+    /// Colours for individual values, overriding the series' colour where set, such as to highlight a bar.
+    pub colors: Vec<Option<Rgb>>,
+    /// X value of each value, for scatter and bubble charts. Values without one are placed at their index.
+    pub x_values: Vec<f64>,
+    /// Size of each value, for bubble charts. Bubbles' areas are in proportion to their sizes.
+    pub sizes: Vec<f64>,
+}
+
+impl Series {
+    /// Create a new series.
+    pub fn new(name: &str, values: Vec<f64>, color: Rgb) -> Series {
+        Series {
+            name: name.to_string(),
+            values,
+            color,
+            colors: vec![],
+            x_values: vec![],
+            sizes: vec![],
+        }
+    }
+
+    /// The x value of the value at `index`: its own x value if it has one, or else its index.
+    pub fn x_at(&self, index: usize) -> f64 {
+        self.x_values.get(index).copied().unwrap_or(index as f64)
+    }
+
+    /// The points of the series as x and y values, skipping any which aren't finite.
+    pub fn points(&self) -> Vec<(f64, f64)> {
+        self.values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (self.x_at(i), *value))
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .collect()
+    }
+
+    /// The colour of the value at `index`: its own colour if it has one, or else the series' colour.
+    pub fn color_at(&self, index: usize) -> &Rgb {
+        match self.colors.get(index) {
+            Some(Some(color)) => color,
+            _ => &self.color,
+        }
+    }
+}
+
+/// Settings for the value axis of a chart.
+#[derive(Debug, Clone)]
+pub struct Axis {
+    /// Lowest value on the axis. If not set, it's chosen from the data.
+    pub min: Option<f64>,
+    /// Highest value on the axis. If not set, it's chosen from the data.
+    pub max: Option<f64>,
+    /// Whether the axis always includes zero, so that bars and areas have a baseline.
+    pub include_zero: bool,
+    /// Most ticks to place along the axis. Fewer are used if that gives nicer numbers.
+    pub max_ticks: usize,
+    /// Title drawn alongside the axis.
+    pub title: Option<String>,
+}
+
+impl Default for Axis {
+    fn default() -> Axis {
+        Axis {
+            min: None,
+            max: None,
+            include_zero: true,
+            max_ticks: 6,
+            title: None,
+        }
+    }
+}
+
+impl Axis {
+    /// The scale of the axis for data covering `range`, which honours the axis' fixed min or max
+    /// and whether it includes zero.
+    pub fn scale_for_range(&self, range: Option<(f64, f64)>) -> Scale {
+        let (data_min, data_max) = range.unwrap_or((0.0, 1.0));
+        let (data_min, data_max) = if self.include_zero {
+            (data_min.min(0.0), data_max.max(0.0))
+        } else {
+            (data_min, data_max)
+        };
+
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => Scale::fixed(min, max, self.max_ticks),
+            (Some(min), None) => {
+                let nice = Scale::nice(min, data_max.max(min), self.max_ticks);
+                Scale::fixed(min, nice.max, self.max_ticks)
+            }
+            (None, Some(max)) => {
+                let nice = Scale::nice(data_min.min(max), max, self.max_ticks);
+                Scale::fixed(nice.min, max, self.max_ticks)
+            }
+            (None, None) => Scale::nice(data_min, data_max, self.max_ticks),
+        }
+    }
+}
+
+/// Most ticks a scale can have, so that a tiny step can't exhaust memory.
+const MAX_TICKS: usize = 1000;
+
+/// A linear scale, which maps values between `min` and `max` to positions along an axis,
+/// with ticks every `step`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scale {
+    pub min: f64,
+    pub max: f64,
+    pub step: f64,
+}
+
+impl Scale {
+    /// Create a scale covering `min` to `max`, extended outwards to "nice" numbers such as
+    /// 0, 25, 50, 75, 100, with at most `max_ticks` ticks.
+    pub fn nice(min: f64, max: f64, max_ticks: usize) -> Scale {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+        let (min, max) = if (max - min).abs() < f64::EPSILON {
+            // Give a single value some room, either side of it.
+            let margin = if min == 0.0 { 1.0 } else { min.abs() * 0.1 };
+            (min - margin, max + margin)
+        } else {
+            (min, max)
+        };
+
+        let intervals = max_ticks.max(2) as f64 - 1.0;
+        let range = nice_number(max - min, false);
+        let step = nice_number(range / intervals, true);
+        let scale = Scale {
+            min: (min / step).floor() * step,
+            max: (max / step).ceil() * step,
+            step,
+        };
+        // Ranges too wide to extend to nice numbers keep their ends, with a tick at each.
+        let finite = [max - min, range, scale.min, scale.max];
+        if finite.iter().all(|value| value.is_finite()) {
+            scale
+        } else {
+            Scale {
+                min,
+                max,
+                step: max - min,
+            }
+        }
+    }
+
+    /// Create a scale covering exactly `min` to `max`, with ticks at nice numbers within it.
+    pub fn fixed(min: f64, max: f64, max_ticks: usize) -> Scale {
+        let nice = Scale::nice(min, max, max_ticks);
+        Scale {
+            min,
+            max,
+            step: nice.step,
+        }
+    }
+
+    /// The values of each tick along the scale.
+    /// Scales with more than 1000 ticks have their step widened to a multiple of itself.
+    pub fn ticks(&self) -> Vec<f64> {
+        let count = ((self.max - self.min) / self.step).floor() + 1.0;
+        let step = self.step * (count / MAX_TICKS as f64).ceil().max(1.0);
+        let first = (self.min / step - 1e-9).ceil();
+        let last = (self.max / step + 1e-9).floor();
+        if !(step > 0.0 && step.is_finite() && (last - first).is_finite()) {
+            // There's no usable step, so only the ends of the scale are marked.
+            let mut ends = vec![self.min, self.max];
+            ends.retain(|end| end.is_finite());
+            ends.dedup();
+            return ends;
+        }
+        (0..=(last - first) as i64)
+            .map(|i| {
+                let tick = (first + i as f64) * step;
+                // Avoid values such as 0.30000000000000004 from the multiplication.
+                (tick / step).round() * step
+            })
+            .collect()
+    }
+
+    /// How far along the scale a value lies, from 0.0 at `min` to 1.0 at `max`.
+    /// Values outside the scale give fractions outside that range.
+    pub fn fraction(&self, value: f64) -> f64 {
+        let range = self.max - self.min;
+        if range.abs() < f64::EPSILON {
+            0.0
+        } else {
+            (value - self.min) / range
+        }
+    }
+
+    /// Format a value for a label on the scale, with as many decimal places as the step between ticks needs.
+    pub fn label(&self, value: f64) -> String {
+        let decimals = if self.step > 0.0 && self.step < 1.0 {
+            (-self.step.log10().floor()) as usize
+        } else {
+            0
+        };
+        let label = format!("{:.*}", decimals, value);
+        // Values which round to zero shouldn't be labelled "-0".
+        match label.strip_prefix('-') {
+            Some(digits) if digits.chars().all(|c| c == '0' || c == '.') => digits.to_string(),
+            _ => label,
+        }
+    }
+
+    /// The value bars and areas grow from: zero if it's within the scale, or else the end nearest zero.
+    pub fn baseline(&self) -> f64 {
+        0.0f64.clamp(self.min, self.max)
+    }
+}
+
+/// Find a "nice" number close to `value`: 1, 2 or 5 times a power of ten (or 1, 2, 5 and 10 when
+/// rounding up). If `round` is true the nearest is chosen, otherwise the smallest which isn't less than `value`.
+pub fn nice_number(value: f64, round: bool) -> f64 {
+    if value <= 0.0 || !value.is_finite() {
+        return 1.0;
+    }
+    let exponent = value.log10().floor();
+    let power = 10f64.powf(exponent);
+    let fraction = value / power;
+    let nice = if round {
+        match fraction {
+            f if f < 1.5 => 1.0,
+            f if f < 3.0 => 2.0,
+            f if f < 7.0 => 5.0,
+            _ => 10.0,
+        }
+    } else {
+        match fraction {
+            f if f <= 1.0 => 1.0,
+            f if f <= 2.0 => 2.0,
+            f if f <= 5.0 => 5.0,
+            _ => 10.0,
+        }
+    };
+    nice * power
+}
+
+/// How the series of a barchart are arranged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BarMode {
+    /// Only the first series is drawn, with one bar per category.
+    Single,
+    /// Each category has a bar for every series, side by side.
+    Grouped,
+    /// Each category has one bar, made of the values of every series stacked end to end.
+    /// Positive values stack up from zero, and negative values down from it.
+    Stacked,
+    /// Stacked bars, where each value is shown as a percentage of its category's total.
+    PercentStacked,
+}
+
+/// Where the values of a barchart's bars are written.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueLabels {
+    None,
+    /// In the middle of each bar, or each segment of a stacked bar, if the value fits.
+    Inside,
+    /// Just beyond the end of each bar, or of each stack for stacked bars, where the stack's total is written.
+    Outside,
+}
+
+/// Options for the arrangement, spacing and labelling of bars in a barchart.
+#[derive(Debug, Clone)]
+pub struct BarOptions {
+    pub mode: BarMode,
+    /// Whether bars grow from left to right, rather than from bottom to top.
+    pub horizontal: bool,
+    /// Space between categories, as a fraction of the space given to each category.
+    pub gap: f32,
+    /// Space between the bars of a group, as a fraction of the width of each bar.
+    pub group_gap: f32,
+    /// Radius of the corners of each bar, in pixels.
+    pub corner_radius: f32,
+    pub value_labels: ValueLabels,
+    /// Colour of value labels written inside bars. Labels outside bars use the chart's text colour.
+    pub label_color: Rgb,
+}
+
+impl Default for BarOptions {
+    fn default() -> BarOptions {
+        BarOptions {
+            mode: BarMode::Single,
+            horizontal: false,
+            gap: 0.3,
+            group_gap: 0.1,
+            corner_radius: 0.0,
+            value_labels: ValueLabels::None,
+            label_color: Rgb {
+                r: 255,
+                g: 255,
+                b: 255,
+            },
+        }
+    }
+}
+
+impl BarOptions {
+    /// Options for bars arranged with the given mode.
+    pub fn new(mode: BarMode) -> BarOptions {
+        BarOptions {
+            mode,
+            ..BarOptions::default()
+        }
+    }
+
+    /// Options for a single series of horizontal bars.
+    pub fn horizontal() -> BarOptions {
+        BarOptions {
+            horizontal: true,
+            ..BarOptions::default()
+        }
+    }
+}
+
+/// How the samples of a histogram are divided into bins.
+/// No histogram has more than 1000 bins, however far apart its samples are.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Binning {
+    /// Bins of a fixed width, with edges at multiples of the width. A width which would give too
+    /// many bins is multiplied until it doesn't.
+    Width(f64),
+    /// A fixed number of bins of equal width, from the lowest sample to the highest.
+    Count(usize),
+    /// Sturges' rule: log2(n) + 1 bins for n samples, which suits data with a roughly normal spread.
+    Sturges,
+    /// The Freedman-Diaconis rule: bins 2 × IQR / ∛n wide, which copes well with outliers.
+    /// Falls back to Sturges' rule if the bins would be too many or have no width.
+    FreedmanDiaconis,
+}
+
+/// Samples divided into bins, for drawing as a histogram.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// Edges of the bins, in order, with one more edge than there are bins.
+    /// Each bin includes its lower edge, and the last also includes its upper edge.
+    pub edges: Vec<f64>,
+    /// Number of samples in each bin.
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Divide samples into bins. Samples which aren't finite are ignored.
+    pub fn new(samples: &[f64], binning: Binning) -> Histogram {
+        let mut samples: Vec<f64> = samples
+            .iter()
+            .copied()
+            .filter(|sample| sample.is_finite())
+            .collect();
+        if samples.is_empty() {
+            return Histogram {
+                edges: vec![],
+                counts: vec![],
+            };
+        }
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let edges = bin_edges(&samples, binning);
+        let last = edges.len() - 2;
+        let mut counts = vec![0; last + 1];
+        for sample in &samples {
+            let bin = edges.partition_point(|edge| edge <= sample).max(1) - 1;
+            counts[bin.min(last)] += 1;
+        }
+        Histogram { edges, counts }
+    }
+
+    /// Total number of samples across all bins.
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Density of each bin: its share of the samples divided by its width, so that the areas of
+    /// all the bins add up to 1.
+    pub fn densities(&self) -> Vec<f64> {
+        let total = self.total() as f64;
+        self.counts
+            .iter()
+            .zip(self.edges.windows(2))
+            .map(|(count, edges)| {
+                let width = edges[1] - edges[0];
+                if total > 0.0 && width > 0.0 {
+                    *count as f64 / (total * width)
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+}
+
+// Most bins a histogram can have, so that widely spread samples can't exhaust memory.
+const MAX_BINS: usize = 1000;
+
+// Edges of bins for sorted, finite samples.
+fn bin_edges(samples: &[f64], binning: Binning) -> Vec<f64> {
+    let (min, max) = (samples[0], samples[samples.len() - 1]);
+    // Samples which are all the same get a single bin around them.
+    if (max - min).abs() < f64::EPSILON {
+        return vec![min - 0.5, max + 0.5];
+    }
+    let n = samples.len() as f64;
+    let sturges = n.log2().ceil() as usize + 1;
+    let count = match binning {
+        Binning::Width(width) if width > 0.0 && width.is_finite() => {
+            // Too narrow a width is widened by a whole multiple, so edges stay on multiples of it.
+            let bins = ((max - min) / width).floor() + 2.0;
+            let width = width * (bins / (MAX_BINS - 2) as f64).ceil().max(1.0);
+            if width.is_finite() {
+                let start = (min / width).floor() * width;
+                let count = ((max - start) / width).floor() as usize + 1;
+                return (0..=count).map(|i| start + i as f64 * width).collect();
+            }
+            sturges
+        }
+        Binning::Width(_) | Binning::Sturges => sturges,
+        Binning::Count(count) => count.clamp(1, MAX_BINS),
+        Binning::FreedmanDiaconis => {
+            let iqr = quantile(samples, 0.75) - quantile(samples, 0.25);
+            let bins = ((max - min) / (2.0 * iqr / n.cbrt())).ceil();
+            // Without a spread in the middle of the data, or with outliers far beyond it, fall
+            // back to Sturges' rule.
+            if bins.is_finite() && bins <= MAX_BINS as f64 {
+                bins.max(1.0) as usize
+            } else {
+                sturges
+            }
+        }
+    };
+    let width = (max - min) / count as f64;
+    (0..=count)
+        .map(|i| match i {
+            // The last edge is exactly the highest sample, so rounding can't leave it outside.
+            i if i == count => max,
+            i => min + i as f64 * width,
+        })
+        .collect()
+}
+
+// Value below which a fraction `q` of sorted samples lie, interpolating between samples.
+fn quantile(samples: &[f64], q: f64) -> f64 {
+    let position = q * (samples.len() - 1) as f64;
+    let (below, above) = (position.floor() as usize, position.ceil() as usize);
+    let t = position - below as f64;
+    samples[below] * (1.0 - t) + samples[above] * t
+}
+
+/// Options for how a histogram's samples are binned and shown.
+#[derive(Debug, Clone)]
+pub struct HistogramOptions {
+    pub binning: Binning,
+    /// Whether bars show each bin's density, so their areas add up to 1, rather than its count.
+    pub density: bool,
+}
+
+impl Default for HistogramOptions {
+    fn default() -> HistogramOptions {
+        HistogramOptions {
+            binning: Binning::Sturges,
+            density: false,
+        }
+    }
+}
+
+/// How the lines of a linechart are drawn between data points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Smoothing {
+    /// Straight lines between points.
+    None,
+    /// A Catmull-Rom spline through every point, which is smooth but can overshoot the data.
+    CatmullRom,
+    /// A monotone cubic curve through every point, which is smooth and never overshoots the data.
+    Monotone,
+}
+
+/// Shape of the marker drawn at each data point of a linechart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Marker {
+    None,
+    Circle,
+    Square,
+    Diamond,
+    Triangle,
+}
+
+/// How the area between each line of a linechart and its baseline is filled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AreaFill {
+    None,
+    /// The line's colour, with an opacity between 0.0 and 1.0.
+    Solid(f32),
+    /// The line's colour, with an opacity between 0.0 and 1.0 furthest from the baseline,
+    /// fading out towards the baseline.
+    Gradient(f32),
+}
+
+/// Options for the lines, markers and areas of a linechart.
+#[derive(Debug, Clone)]
+pub struct LineOptions {
+    /// Thickness of each line, in pixels.
+    pub thickness: f32,
+    pub smoothing: Smoothing,
+    pub marker: Marker,
+    /// Width of each marker, in pixels.
+    pub marker_size: f32,
+    pub area: AreaFill,
+}
+
+impl Default for LineOptions {
+    fn default() -> LineOptions {
+        LineOptions {
+            thickness: 3.0,
+            smoothing: Smoothing::None,
+            marker: Marker::None,
+            marker_size: 12.0,
+            area: AreaFill::None,
+        }
+    }
+}
+
+/// Options for the markers and trend lines of scatter and bubble charts.
+#[derive(Debug, Clone)]
+pub struct ScatterOptions {
+    pub marker: Marker,
+    /// Width of each marker, in pixels. For bubbles, the width of the largest bubble.
+    pub marker_size: f32,
+    /// Opacity of the markers, between 0.0 and 1.0, so points which overlap can still be seen.
+    pub opacity: f32,
+    /// Whether to size markers by each series' `sizes`, as bubbles.
+    pub bubbles: bool,
+    /// Whether to draw a dashed line of best fit through each series.
+    pub trend_line: bool,
+}
+
+impl Default for ScatterOptions {
+    fn default() -> ScatterOptions {
+        ScatterOptions {
+            marker: Marker::Circle,
+            marker_size: 14.0,
+            opacity: 0.85,
+            bubbles: false,
+            trend_line: false,
+        }
+    }
+}
+
+impl ScatterOptions {
+    /// Options for a bubble chart, with large translucent circles.
+    pub fn bubbles() -> ScatterOptions {
+        ScatterOptions {
+            marker_size: 80.0,
+            opacity: 0.6,
+            bubbles: true,
+            ..ScatterOptions::default()
+        }
+    }
+}
+
+/// A series of values drawn as a sparkline: a small line chart without axes or labels.
+#[derive(Debug, Clone)]
+pub struct Sparkline {
+    pub values: Vec<f64>,
+    pub color: Rgb,
+    /// Thickness of the line, in pixels.
+    pub thickness: f32,
+    pub smoothing: Smoothing,
+    /// How the area beneath the line is filled.
+    pub area: AreaFill,
+    /// Whether to draw a dot on the last value, to pick out the latest figure.
+    pub mark_last: bool,
+    /// Value at the bottom of the sparkline. If not set, the lowest value is at the bottom.
+    pub min: Option<f64>,
+    /// Value at the top of the sparkline. If not set, the highest value is at the top.
+    pub max: Option<f64>,
+}
+
+impl Sparkline {
+    /// Create a new sparkline of values, scaled to fill its height.
+    pub fn new(values: Vec<f64>, color: Rgb) -> Sparkline {
+        Sparkline {
+            values,
+            color,
+            thickness: 3.0,
+            smoothing: Smoothing::None,
+            area: AreaFill::None,
+            mark_last: true,
+            min: None,
+            max: None,
+        }
+    }
+}
+
+/// Options for pie and donut charts.
+#[derive(Debug, Clone)]
+pub struct PieOptions {
+    /// Radius of the hole in the middle, as a fraction of the chart's radius. 0.0 gives a pie chart.
+    pub inner_radius: f32,
+    /// Angle the first slice starts at, in degrees clockwise from the top.
+    pub start_angle: f32,
+    /// Indexes of slices which are pulled out from the centre.
+    pub exploded: Vec<usize>,
+    /// How far exploded slices are pulled out, as a fraction of the chart's radius.
+    pub explode_offset: f32,
+    /// Whether to label each slice with its percentage of the total.
+    pub percentages: bool,
+    pub label_color: Rgb,
+    /// Text in the middle of a donut chart.
+    pub centre_label: Option<String>,
+}
+
+impl Default for PieOptions {
+    fn default() -> PieOptions {
+        PieOptions {
+            inner_radius: 0.0,
+            start_angle: 0.0,
+            exploded: vec![],
+            explode_offset: 0.1,
+            percentages: true,
+            label_color: Rgb {
+                r: 255,
+                g: 255,
+                b: 255,
+            },
+            centre_label: None,
+        }
+    }
+}
+
+impl PieOptions {
+    /// Options for a donut chart, with a label in its middle.
+    pub fn donut(centre_label: &str) -> PieOptions {
+        PieOptions {
+            inner_radius: 0.55,
+            centre_label: Some(centre_label.to_string()),
+            ..PieOptions::default()
+        }
+    }
+}
+
+/// A value shown as a fraction of its range, drawn as a progress ring or a gauge.
+#[derive(Debug, Clone)]
+pub struct RadialProgress {
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+    pub color: Rgb,
+    /// Colour of the unfilled part of the track.
+    pub track_color: Rgba,
+    /// Thickness of the track, as a fraction of its radius.
+    pub thickness: f32,
+    /// Label for the value. If not set, the value is shown as a percentage of its range.
+    pub label: Option<String>,
+    /// Font for the label, such as "Roboto-Bold".
+    pub font: String,
+    pub text_color: Rgb,
+}
+
+impl RadialProgress {
+    /// Create a new progress value, for a range starting at zero.
+    pub fn new(value: f64, max: f64, color: Rgb) -> RadialProgress {
+        RadialProgress {
+            value,
+            min: 0.0,
+            max,
+            color,
+            track_color: Rgba::new(128, 128, 128, 60),
+            thickness: 0.2,
+            label: None,
+            font: "Roboto-Bold".to_string(),
+            text_color: Rgb {
+                r: 230,
+                g: 230,
+                b: 230,
+            },
+        }
+    }
+
+    /// How far the value is through its range, from 0.0 to 1.0.
+    pub fn fraction(&self) -> f64 {
+        let range = self.max - self.min;
+        if range.abs() < f64::EPSILON || !self.value.is_finite() {
+            0.0
+        } else {
+            ((self.value - self.min) / range).clamp(0.0, 1.0)
+        }
+    }
+
+    // The label, or else the value as a percentage.
+    fn label(&self) -> String {
+        match &self.label {
+            Some(label) => label.clone(),
+            None => format!("{:.0}%", self.fraction() * 100.0),
+        }
+    }
+
+    // A chart style for drawing the progress's text at a given size.
+    fn label_style(&self, font_size: f32) -> ChartStyle {
+        ChartStyle {
+            font: self.font.clone(),
+            font_size,
+            text_color: self.text_color.clone(),
+            ..ChartStyle::default()
+        }
+    }
+}
+
+/// The rectangle inside a chart's axes, in which its data is plotted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlotArea {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl PlotArea {
+    /// X-coordinate of the right edge of the area.
+    pub fn right(&self) -> f32 {
+        self.x + self.width
+    }
+
+    /// Y-coordinate of the bottom edge of the area.
+    pub fn bottom(&self) -> f32 {
+        self.y + self.height
+    }
+
+    /// X-coordinate of the centre of a category, when `count` categories are spread evenly across the area.
+    pub fn category_x(&self, index: usize, count: usize) -> f32 {
+        self.x + (index as f32 + 0.5) * self.width / count.max(1) as f32
+    }
+
+    /// Y-coordinate of the centre of a category, when `count` categories are spread evenly down the area.
+    pub fn category_y(&self, index: usize, count: usize) -> f32 {
+        self.y + (index as f32 + 0.5) * self.height / count.max(1) as f32
+    }
+
+    /// X-coordinate of a value on a scale, which runs from the left of the area to the right.
+    pub fn value_x(&self, scale: &Scale, value: f64) -> f32 {
+        self.x + scale.fraction(value) as f32 * self.width
+    }
+
+    /// Y-coordinate of a value on a scale, which runs from the bottom of the area up to the top.
+    pub fn value_y(&self, scale: &Scale, value: f64) -> f32 {
+        self.bottom() - scale.fraction(value) as f32 * self.height
+    }
+}
+
+/// Where a chart's title is drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TitlePosition {
+    /// The title isn't drawn.
+    None,
+    /// Above the chart, with the rest of the chart beneath it.
+    Top,
+    /// Below the chart, with the rest of the chart above it.
+    Bottom,
+}
+
+/// Where a chart's legend is drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LegendPosition {
+    /// Beside the plot area, on the right, which is narrowed to make room.
+    Right,
+    /// Inside the top-right corner of the plot area, over the chart's data.
+    Inside,
+}
+
+/// Fonts, colours and other settings for the axes, labels and legend of a chart.
+#[derive(Debug, Clone)]
+pub struct ChartStyle {
+    /// Font for tick labels, axis titles and the legend, such as "Roboto-Regular".
+    pub font: String,
+    pub font_size: f32,
+    pub text_color: Rgb,
+    pub axis_color: Rgb,
+    /// Whether to draw lines across the plot at each tick on the value axis.
+    pub gridlines: bool,
+    pub grid_color: Rgba,
+    /// Length of the tick marks along each axis, in pixels.
+    pub tick_length: f32,
+    /// Whether to draw a legend, for charts with more than one series.
+    pub legend: bool,
+    pub legend_position: LegendPosition,
+    pub legend_background: Rgba,
+    /// Space between the edges of the chart and anything drawn in it, in pixels.
+    pub padding: f32,
+    /// Font for the chart's title, such as "Roboto-Bold".
+    pub title_font: String,
+    pub title_size: f32,
+    pub title_color: Rgb,
+    pub title_position: TitlePosition,
+    pub title_align: TextAlign,
+    /// Colours for charts which colour each category differently, such as pie charts.
+    pub palette: Vec<Rgb>,
+}
+
+impl Default for ChartStyle {
+    fn default() -> ChartStyle {
+        ChartStyle {
+            font: "Roboto-Regular".to_string(),
+            font_size: 24.0,
+            text_color: Rgb {
+                r: 200,
+                g: 200,
+                b: 200,
+            },
+            axis_color: Rgb {
+                r: 140,
+                g: 140,
+                b: 140,
+            },
+            gridlines: true,
+            grid_color: Rgba::new(128, 128, 128, 60),
+            tick_length: 8.0,
+            legend: true,
+            legend_position: LegendPosition::Right,
+            legend_background: Rgba::new(0, 0, 0, 0),
+            padding: 20.0,
+            title_font: "Roboto-Bold".to_string(),
+            title_size: 40.0,
+            title_color: Rgb {
+                r: 230,
+                g: 230,
+                b: 230,
+            },
+            title_position: TitlePosition::Top,
+            title_align: TextAlign::Left,
+            palette: palette("default"),
+        }
+    }
+}
+
+impl ChartStyle {
+    /// Colour for the category at `index`, from the palette. Colours are reused if there are more
+    /// categories than colours.
+    pub fn palette_color(&self, index: usize) -> Rgb {
+        match self.palette.len() {
+            0 => self.axis_color.clone(),
+            len => self.palette[index % len].clone(),
+        }
+    }
+}
+
+/// Chart type, containing data, labels, and other metadata about a chart.
+#[derive(Debug, Clone)]
+pub struct Chart {
+    pub title: String,
+    /// Series of values to be drawn. Most charts draw one bar or point per value.
+    pub series: Vec<Series>,
+    /// Label for each value along the category axis.
     pub labels: Vec<String>,
+    /// X-coordinate of the chart's left edge, within the image it's drawn onto.
+    pub x: u32,
+    /// Y-coordinate of the chart's top edge, within the image it's drawn onto.
+    pub y: u32,
     pub height: u32,
     pub width: u32,
+    /// Title of the category axis. For charts of numeric x values, also their range and ticks.
+    pub x_axis: Axis,
+    /// Range, ticks and title of the value axis.
+    pub y_axis: Axis,
+    /// Fonts and colours of the axes, labels and legend.
+    pub style: ChartStyle,
 }
 
 impl Chart {
-    /// Create a new chart.
+    /// Create a new chart, with a single series of data named after the chart's title.
     pub fn new(
         title: String,
         color: Rgb,
-        data: Vec<u16>,
+        data: Vec<f64>,
         labels: Vec<String>,
         height: u32,
         width: u32,
     ) -> Chart {
-        return Chart {
-            title: title,
-            color: color,
-            data: data,
-            labels: labels,
-            width: width,
-            height: height,
+        let series = vec![Series::new(&title, data, color)];
+        Chart {
+            title,
+            series,
+            labels,
+            x: 0,
+            y: 0,
+            width,
+            height,
+            x_axis: Axis::default(),
+            y_axis: Axis::default(),
+            style: ChartStyle::default(),
+        }
+    }
+
+    /// Place the chart within a rectangle of the image it's drawn onto.
+    /// The chart's title, axes and data are all drawn inside this rectangle.
+    pub fn set_bounds(&mut self, x: u32, y: u32, width: u32, height: u32) {
+        self.x = x;
+        self.y = y;
+        self.width = width;
+        self.height = height;
+    }
+
+    /// Add another series of data to the chart.
+    pub fn add_series(&mut self, name: &str, values: Vec<f64>, color: Rgb) {
+        self.series.push(Series::new(name, values, color));
+    }
+
+    /// Add a series of points with x and y values, for scatter and bubble charts.
+    pub fn add_points(&mut self, name: &str, points: &[(f64, f64)], color: Rgb) {
+        let mut series = Series::new(name, points.iter().map(|(_, y)| *y).collect(), color);
+        series.x_values = points.iter().map(|(x, _)| *x).collect();
+        self.series.push(series);
+    }
+
+    /// The smallest and largest finite values across all series, or `None` if there are none.
+    pub fn value_range(&self) -> Option<(f64, f64)> {
+        self.series
+            .iter()
+            .flat_map(|series| series.values.iter())
+            .filter(|value| value.is_finite())
+            .fold(None, |range, &value| match range {
+                None => Some((value, value)),
+                Some((min, max)) => Some((value.min(min), value.max(max))),
+            })
+    }
+
+    /// The scale of the value axis, which covers all of the data unless the axis has a fixed min or max.
+    pub fn value_scale(&self) -> Scale {
+        self.scale_for_range(self.value_range())
+    }
+
+    /// The scale of the value axis for data covering `range`, such as the totals of stacked bars,
+    /// which honours the axis' fixed min or max and whether it includes zero.
+    pub fn scale_for_range(&self, range: Option<(f64, f64)>) -> Scale {
+        self.y_axis.scale_for_range(range)
+    }
+
+    /// The smallest and largest finite x values across all series, or `None` if there are none.
+    pub fn x_range(&self) -> Option<(f64, f64)> {
+        self.series.iter().flat_map(|series| series.points()).fold(
+            None,
+            |range, (x, _)| match range {
+                None => Some((x, x)),
+                Some((min, max)) => Some((x.min(min), x.max(max))),
+            },
+        )
+    }
+
+    /// The scale of the x-axis for charts of numeric x values, such as scatter charts, which covers
+    /// all of the data unless the `x_axis` has a fixed min or max.
+    pub fn x_scale(&self) -> Scale {
+        self.x_axis.scale_for_range(self.x_range())
+    }
+
+    /// The number of values in the longest series.
+    pub fn point_count(&self) -> usize {
+        self.series
+            .iter()
+            .map(|series| series.values.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    // The number of categories along the x-axis: one per label, or per value of the longest series.
+    fn category_count(&self) -> usize {
+        self.point_count().max(self.labels.len())
+    }
+
+    // Whether a legend is drawn for the chart's series.
+    fn shows_legend(&self) -> bool {
+        self.style.legend && self.series.len() > 1
+    }
+
+    // The name and colour of each series, for a legend.
+    fn legend_entries(&self) -> Vec<(&str, &Rgb)> {
+        self.series
+            .iter()
+            .map(|series| (series.name.as_str(), &series.color))
+            .collect()
+    }
+
+    // Values of the first series, for charts which only draw a single series.
+    fn first_values(&self) -> &[f64] {
+        self.series
+            .first()
+            .map(|series| series.values.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Check that a histogram holds every sample, and that its bins run in order.
+    fn assert_well_formed(histogram: &Histogram, samples: usize) {
+        assert_eq!(histogram.edges.len(), histogram.counts.len() + 1);
+        assert!(histogram.counts.len() <= MAX_BINS);
+        assert_eq!(histogram.total(), samples);
+        assert!(histogram.edges.windows(2).all(|edges| edges[0] < edges[1]));
+    }
+
+    #[test]
+    fn width_bins_have_edges_at_multiples_of_the_width() {
+        let histogram = Histogram::new(&[0.5, 1.5, 2.5, 3.9], Binning::Width(1.0));
+        assert_eq!(histogram.edges, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(histogram.counts, vec![1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn narrow_width_bins_are_widened() {
+        let samples: Vec<f64> = (0..=100).map(|i| i as f64 / 100.0).collect();
+        let histogram = Histogram::new(&samples, Binning::Width(1e-12));
+        assert_well_formed(&histogram, samples.len());
+        assert!(histogram.edges[0] <= 0.0);
+        assert!(*histogram.edges.last().unwrap() >= 1.0);
+    }
+
+    #[test]
+    fn count_bins_are_equal_and_capped() {
+        let samples = [0.0, 1.0, 2.0, 3.0, 4.0, 8.0];
+        let histogram = Histogram::new(&samples, Binning::Count(4));
+        assert_eq!(histogram.edges, vec![0.0, 2.0, 4.0, 6.0, 8.0]);
+        assert_eq!(histogram.counts, vec![2, 2, 1, 1]);
+
+        let histogram = Histogram::new(&samples, Binning::Count(usize::MAX));
+        assert_well_formed(&histogram, samples.len());
+        assert_eq!(histogram.counts.len(), MAX_BINS);
+    }
+
+    #[test]
+    fn sturges_bins() {
+        let samples: Vec<f64> = (0..100).map(|i| i as f64).collect();
+        let histogram = Histogram::new(&samples, Binning::Sturges);
+        // ⌈log2(100)⌉ + 1
+        assert_eq!(histogram.counts.len(), 8);
+        assert_well_formed(&histogram, samples.len());
+    }
+
+    #[test]
+    fn freedman_diaconis_bins() {
+        let samples: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+        let histogram = Histogram::new(&samples, Binning::FreedmanDiaconis);
+        // 999 / (2 × 499.5 / ∛1000), rounded up
+        assert_eq!(histogram.counts.len(), 10);
+        assert_well_formed(&histogram, samples.len());
+    }
+
+    #[test]
+    fn freedman_diaconis_falls_back_to_sturges_for_outliers() {
+        let mut samples: Vec<f64> = (0..100).map(|i| i as f64 * 1e-9).collect();
+        samples.push(1e3);
+        let histogram = Histogram::new(&samples, Binning::FreedmanDiaconis);
+        let sturges = Histogram::new(&samples, Binning::Sturges);
+        assert_eq!(histogram, sturges);
+        assert_well_formed(&histogram, samples.len());
+    }
+
+    #[test]
+    fn densities_sum_to_one() {
+        let samples = [1.0, 2.0, 2.5, 3.0, 7.0, 7.5, 9.0, 12.0];
+        for binning in [
+            Binning::Width(2.5),
+            Binning::Count(3),
+            Binning::Sturges,
+            Binning::FreedmanDiaconis,
+        ]
+        .iter()
+        {
+            let histogram = Histogram::new(&samples, *binning);
+            let area: f64 = histogram
+                .densities()
+                .iter()
+                .zip(histogram.edges.windows(2))
+                .map(|(density, edges)| density * (edges[1] - edges[0]))
+                .sum();
+            assert!((area - 1.0).abs() < 1e-9, "{:?} gave {}", binning, area);
+        }
+    }
+
+    #[test]
+    fn equal_samples_share_one_bin() {
+        for binning in [
+            Binning::Width(1.0),
+            Binning::Count(5),
+            Binning::Sturges,
+            Binning::FreedmanDiaconis,
+        ]
+        .iter()
+        {
+            let histogram = Histogram::new(&[5.0, 5.0, 5.0], *binning);
+            assert_eq!(histogram.edges, vec![4.5, 5.5]);
+            assert_eq!(histogram.counts, vec![3]);
+        }
+    }
+
+    #[test]
+    fn non_finite_samples_are_ignored() {
+        let histogram = Histogram::new(&[f64::NAN, f64::INFINITY], Binning::Sturges);
+        assert!(histogram.counts.is_empty());
+        let histogram = Histogram::new(&[1.0, f64::NAN, 2.0], Binning::Count(2));
+        assert_eq!(histogram.counts, vec![1, 1]);
+    }
+
+    #[test]
+    fn nice_numbers_are_one_two_or_five_times_a_power_of_ten() {
+        assert_eq!(nice_number(0.7, false), 1.0);
+        assert_eq!(nice_number(1.2, false), 2.0);
+        assert_eq!(nice_number(3.0, false), 5.0);
+        assert_eq!(nice_number(6.0, false), 10.0);
+        assert_eq!(nice_number(120.0, false), 200.0);
+        assert_eq!(nice_number(1.4, true), 1.0);
+        assert_eq!(nice_number(2.9, true), 2.0);
+        assert_eq!(nice_number(6.9, true), 5.0);
+        assert_eq!(nice_number(7.0, true), 10.0);
+        assert_eq!(nice_number(25.0, true), 20.0);
+        for &value in &[0.0, -5.0, f64::INFINITY, f64::NAN] {
+            assert_eq!(nice_number(value, true), 1.0);
+        }
+    }
+
+    #[test]
+    fn nice_scales_extend_to_nice_numbers() {
+        let scale = Scale::nice(0.0, 97.0, 5);
+        assert_eq!((scale.min, scale.max, scale.step), (0.0, 100.0, 20.0));
+        assert_eq!(scale.ticks(), vec![0.0, 20.0, 40.0, 60.0, 80.0, 100.0]);
+    }
+
+    #[test]
+    fn nice_scales_cover_negative_and_reversed_ranges() {
+        let scale = Scale::nice(-23.0, 47.0, 5);
+        assert_eq!((scale.min, scale.max, scale.step), (-40.0, 60.0, 20.0));
+        assert_eq!(scale.ticks(), vec![-40.0, -20.0, 0.0, 20.0, 40.0, 60.0]);
+        assert_eq!(Scale::nice(47.0, -23.0, 5), scale);
+    }
+
+    #[test]
+    fn zero_width_scales_are_widened() {
+        let scale = Scale::nice(0.0, 0.0, 5);
+        assert_eq!(scale.ticks(), vec![-1.0, -0.5, 0.0, 0.5, 1.0]);
+
+        let scale = Scale::nice(5.0, 5.0, 5);
+        assert!(scale.min < 5.0 && scale.max > 5.0);
+        let ticks = scale.ticks();
+        assert!(ticks.len() >= 2 && ticks.len() <= 7);
+        assert!(ticks.iter().any(|&tick| (tick - 5.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn overflowing_ranges_keep_their_ends() {
+        assert_eq!(
+            Scale::nice(-f64::MAX, f64::MAX, 5).ticks(),
+            vec![-f64::MAX, f64::MAX]
+        );
+        assert_eq!(Scale::nice(0.0, f64::MAX, 5).ticks(), vec![0.0, f64::MAX]);
+        let ticks = Scale::nice(0.0, f64::INFINITY, 5).ticks();
+        assert!(ticks.iter().all(|tick| tick.is_finite()));
+        assert!(Scale::nice(f64::NAN, 1.0, 5).ticks().len() <= 2);
+    }
+
+    #[test]
+    fn ticks_are_capped() {
+        let scale = Scale {
+            min: 0.0,
+            max: 1e9,
+            step: 1.0,
+        };
+        let ticks = scale.ticks();
+        assert!(ticks.len() <= MAX_TICKS + 1);
+        assert!(ticks.iter().all(|tick| tick.fract() == 0.0));
+
+        let scale = Scale {
+            min: 0.0,
+            max: 10.0,
+            step: 0.0,
         };
+        assert_eq!(scale.ticks(), vec![0.0, 10.0]);
     }
 }
@@ -1,11 +1,10 @@
 //! Add shapes and other elements to images.
 
+use crate::blend::{self, BlendMode};
+use crate::raster::{self, Quality};
 use crate::text::draw_text;
-use crate::Rgb;
-use image::{DynamicImage, GenericImage, GenericImageView, Rgba, RgbaImage};
-use imageproc::drawing::*;
-use imageproc::point::Point;
-use imageproc::rect::Rect;
+use crate::{Rgb, Rgba};
+use image::{DynamicImage, GenericImage, GenericImageView, RgbaImage};
 use palette::encoding::pixel::Pixel;
 use palette::{FromColor, Gradient, Lch, LinSrgba, Srgba};
 // use crate::helpers;
@@ -16,85 +15,143 @@ use wasm_bindgen::prelude::*;
 ///
 /// # Arguments
 /// * `img` - A mutable ref to a DynamicImage.
-/// * `background_color` - Rgb color of rectangle.
+/// * `background_color` - Rgb or Rgba color of rectangle. Transparent colors are blended with the pixels underneath.
 /// * `width` - u32 - Desired width of rectangle.
 /// * `height` - u32 - Desired height of rectangle.
 /// * `x_pos` - X-coordinate of top corner of rectangle on `img`
 /// * `y_pos` - y-coordinate of top corner of rectangle on `img`
 pub fn draw_solid_rect(
     img: &mut DynamicImage,
-    background_color: &Rgb,
+    background_color: impl Into<Rgba>,
     width: u32,
     height: u32,
     x_pos: i32,
     y_pos: i32,
 ) {
-    draw_filled_rect_mut(
+    draw_solid_rect_with_quality(
         img,
-        Rect::at(x_pos, y_pos).of_size(width, height),
-        Rgba([
-            background_color.r,
-            background_color.g,
-            background_color.b,
-            255u8,
-        ]),
+        background_color,
+        width,
+        height,
+        x_pos,
+        y_pos,
+        Quality::AntiAliased,
     );
 }
 
-/// Draw an opaque rectangle, where the opacity is set to a certain u8 value.
+/// Draw a solid rectangle, choosing whether its edges are anti-aliased.
 ///
+/// Edges only differ between qualities when the rectangle is positioned or sized in fractions of a pixel,
+/// such as after scaling.
+///
+/// # Arguments
 /// * `img` - A mutable ref to a DynamicImage.
-/// * `background_color` - Rgb color of rectangle.
-/// * `opacity` - The opacity of the rectangle.
+/// * `background_color` - Rgb or Rgba color of rectangle. Transparent colors are blended with the pixels underneath.
+/// * `width` - u32 - Desired width of rectangle.
+/// * `height` - u32 - Desired height of rectangle.
+/// * `x_pos` - X-coordinate of top corner of rectangle on `img`
+/// * `y_pos` - y-coordinate of top corner of rectangle on `img`
+/// * `quality` - `Quality::AntiAliased` for smooth edges, or `Quality::Fast` for hard edges.
+pub fn draw_solid_rect_with_quality(
+    img: &mut DynamicImage,
+    background_color: impl Into<Rgba>,
+    width: u32,
+    height: u32,
+    x_pos: i32,
+    y_pos: i32,
+    quality: Quality,
+) {
+    fill_rect(
+        img,
+        background_color.into(),
+        width,
+        height,
+        x_pos,
+        y_pos,
+        quality,
+    );
+}
+
+/// Draw a translucent rectangle, which is blended with the pixels underneath.
+///
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `background_color` - Rgb or Rgba color of rectangle.
+/// * `opacity` - The opacity of the rectangle, from 0 (invisible) to 255. This is combined with the color's own alpha.
 /// * `width` - u32 - Desired width of rectangle.
 /// * `height` - u32 - Desired height of rectangle.
 /// * `x_pos` - X-coordinate of top corner of rectangle on `img`
 /// * `y_pos` - y-coordinate of top corner of rectangle on `img`
 pub fn draw_opaque_rect(
     img: &mut DynamicImage,
-    background_color: &Rgb,
+    background_color: impl Into<Rgba>,
     opacity: u8,
     width: u32,
     height: u32,
     x_pos: i32,
     y_pos: i32,
 ) {
-    draw_filled_rect_mut(
+    draw_opaque_rect_with_quality(
         img,
-        Rect::at(x_pos, y_pos).of_size(width, height),
-        Rgba([
-            background_color.r,
-            background_color.g,
-            background_color.b,
-            opacity,
-        ]),
+        background_color,
+        opacity,
+        width,
+        height,
+        x_pos,
+        y_pos,
+        Quality::AntiAliased,
     );
 }
 
-/// Draw a triangle.
+/// Draw a translucent rectangle, choosing whether its edges are anti-aliased.
 ///
-/// /// * `img` - A mutable ref to a DynamicImage.
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `background_color` - Rgb or Rgba color of rectangle.
+/// * `opacity` - The opacity of the rectangle, from 0 (invisible) to 255. This is combined with the color's own alpha.
+/// * `width` - u32 - Desired width of rectangle.
+/// * `height` - u32 - Desired height of rectangle.
+/// * `x_pos` - X-coordinate of top corner of rectangle on `img`
+/// * `y_pos` - y-coordinate of top corner of rectangle on `img`
+/// * `quality` - `Quality::AntiAliased` for smooth edges, or `Quality::Fast` for hard edges.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_opaque_rect_with_quality(
+    img: &mut DynamicImage,
+    background_color: impl Into<Rgba>,
+    opacity: u8,
+    width: u32,
+    height: u32,
+    x_pos: i32,
+    y_pos: i32,
+    quality: Quality,
+) {
+    let mut color = background_color.into();
+    color.a = (color.a as u32 * opacity as u32 / 255) as u8;
+    fill_rect(img, color, width, height, x_pos, y_pos, quality);
+}
+
+/// Draw a triangle, with anti-aliased edges.
+///
+/// * `img` - A mutable ref to a DynamicImage.
 /// * `triangle` - Triangle struct.
 pub fn draw_triangle(img: &mut DynamicImage, triangle: Triangle) {
-    let point = Point::new(triangle.x1, triangle.y1);
-    let point2 = Point::new(triangle.x2, triangle.y2);
-    let point3 = Point::new(triangle.x3, triangle.y3);
-
-    let points = vec![point, point2, point3];
+    draw_triangle_with_quality(img, triangle, Quality::AntiAliased);
+}
 
-    draw_polygon_mut(
-        img,
-        points.as_slice(),
-        Rgba([
-            triangle.background_color.r,
-            triangle.background_color.g,
-            triangle.background_color.b,
-            255u8,
-        ]),
-    );
+/// Draw a triangle, choosing whether its edges are anti-aliased.
+///
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `triangle` - Triangle struct.
+/// * `quality` - `Quality::AntiAliased` for smooth edges, or `Quality::Fast` for hard edges.
+pub fn draw_triangle_with_quality(img: &mut DynamicImage, triangle: Triangle, quality: Quality) {
+    let points = [
+        (triangle.x1 as f32, triangle.y1 as f32),
+        (triangle.x2 as f32, triangle.y2 as f32),
+        (triangle.x3 as f32, triangle.y3 as f32),
+    ];
+
+    draw_polygon(img, &points, triangle.background_color, quality);
 }
 
-/// Draw an equilateral triangle.
+/// Draw an equilateral triangle, with anti-aliased edges.
 ///
 /// Not represented by a Triangle struct, because all sides are equal, and only one value should be entered.
 ///
@@ -107,32 +164,145 @@ pub fn draw_equilateral_triangle(
     side_len: u32,
     x_pos: i32,
     y_pos: i32,
-    background_color: &Rgb,
+    background_color: impl Into<Rgba>,
 ) {
-    let point = Point::new(x_pos, y_pos);
-    let point2 = Point::new(x_pos + side_len as i32, y_pos);
-    let point3 = Point::new(x_pos + (side_len / 2) as i32, y_pos * 3);
-
-    let points = vec![point, point2, point3];
-
-    draw_polygon_mut(
+    draw_equilateral_triangle_with_quality(
         img,
-        points.as_slice(),
-        Rgba([
-            background_color.r,
-            background_color.g,
-            background_color.b,
-            255u8,
-        ]),
+        side_len,
+        x_pos,
+        y_pos,
+        background_color,
+        Quality::AntiAliased,
     );
 }
 
+/// Draw an equilateral triangle, choosing whether its edges are anti-aliased.
+///
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `side_len` - Side of the equilateral triangle, which will constitute all 3 sides.
+/// * `x_pos` - X-coordinate of top point of triangle on `img`
+/// * `y_pos` - y-coordinate of top point of triangle on `img`
+/// * `quality` - `Quality::AntiAliased` for smooth edges, or `Quality::Fast` for hard edges.
+pub fn draw_equilateral_triangle_with_quality(
+    img: &mut DynamicImage,
+    side_len: u32,
+    x_pos: i32,
+    y_pos: i32,
+    background_color: impl Into<Rgba>,
+    quality: Quality,
+) {
+    let points = [
+        (x_pos as f32, y_pos as f32),
+        ((x_pos + side_len as i32) as f32, y_pos as f32),
+        ((x_pos + (side_len / 2) as i32) as f32, (y_pos * 3) as f32),
+    ];
+
+    draw_polygon(img, &points, background_color, quality);
+}
+
+/// Draw a filled polygon through a series of sub-pixel points.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `points` - Corners of the polygon, in order.
+/// * `background_color` - Rgb or Rgba color of the polygon.
+/// * `quality` - `Quality::AntiAliased` for smooth edges, or `Quality::Fast` for hard edges.
+pub fn draw_polygon(
+    img: &mut DynamicImage,
+    points: &[(f32, f32)],
+    background_color: impl Into<Rgba>,
+    quality: Quality,
+) {
+    let contour = raster::polygon(points);
+    raster::fill_contours(img, &[contour], background_color.into().into(), quality);
+}
+
+/// Draw a straight line of a given thickness.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `start` - Sub-pixel coordinates of the start of the line.
+/// * `end` - Sub-pixel coordinates of the end of the line.
+/// * `thickness` - Thickness of the line in pixels.
+/// * `color` - Rgb or Rgba color of the line.
+/// * `quality` - `Quality::AntiAliased` for smooth edges, or `Quality::Fast` for hard edges.
+pub fn draw_line(
+    img: &mut DynamicImage,
+    start: (f32, f32),
+    end: (f32, f32),
+    thickness: f32,
+    color: impl Into<Rgba>,
+    quality: Quality,
+) {
+    let contour = raster::line(start, end, thickness);
+    raster::fill_contours(img, &[contour], color.into().into(), quality);
+}
+
+/// Draw a line passing through a series of points, with rounded joins.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `points` - Sub-pixel coordinates the line passes through, in order.
+/// * `thickness` - Thickness of the line in pixels.
+/// * `color` - Rgb or Rgba color of the line.
+/// * `quality` - `Quality::AntiAliased` for smooth edges, or `Quality::Fast` for hard edges.
+pub fn draw_polyline(
+    img: &mut DynamicImage,
+    points: &[(f32, f32)],
+    thickness: f32,
+    color: impl Into<Rgba>,
+    quality: Quality,
+) {
+    let contours = raster::polyline(points, thickness);
+    raster::fill_contours(img, &contours, color.into().into(), quality);
+}
+
+/// Draw a filled circle.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `centre` - Sub-pixel coordinates of the centre of the circle.
+/// * `radius` - Radius of the circle.
+/// * `background_color` - Rgb or Rgba color of the circle.
+/// * `quality` - `Quality::AntiAliased` for smooth edges, or `Quality::Fast` for hard edges.
+pub fn draw_circle(
+    img: &mut DynamicImage,
+    centre: (f32, f32),
+    radius: f32,
+    background_color: impl Into<Rgba>,
+    quality: Quality,
+) {
+    let contour = raster::circle(centre.0, centre.1, radius);
+    raster::fill_contours(img, &[contour], background_color.into().into(), quality);
+}
+
+/// Draw a filled ellipse.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `centre` - Sub-pixel coordinates of the centre of the ellipse.
+/// * `radius_x` - Horizontal radius of the ellipse.
+/// * `radius_y` - Vertical radius of the ellipse.
+/// * `background_color` - Rgb or Rgba color of the ellipse.
+/// * `quality` - `Quality::AntiAliased` for smooth edges, or `Quality::Fast` for hard edges.
+pub fn draw_ellipse(
+    img: &mut DynamicImage,
+    centre: (f32, f32),
+    radius_x: f32,
+    radius_y: f32,
+    background_color: impl Into<Rgba>,
+    quality: Quality,
+) {
+    let contour = raster::ellipse(centre.0, centre.1, radius_x, radius_y);
+    raster::fill_contours(img, &[contour], background_color.into().into(), quality);
+}
+
 /// Draw a solid rectangle with text placed in-centre.
 ///
 /// # Arguments
 /// * `img` - A mutable ref to a DynamicImage.
 /// * `text` - Text to be placed inside the rectangle.
-/// * `background_color` - Rgb color of rectangle.
+/// * `background_color` - Rgb or Rgba color of rectangle.
 /// * `width` - u32 - Desired width of rectangle.
 /// * `height` - u32 - Desired height of rectangle.
 /// * `x_pos` - X-coordinate of top corner of rectangle on `img`
@@ -140,7 +310,7 @@ pub fn draw_equilateral_triangle(
 pub fn draw_rect_text(
     img: &mut DynamicImage,
     text: &str,
-    background_color: &Rgb,
+    background_color: impl Into<Rgba>,
     height: u32,
     width: u32,
     x_pos: i32,
@@ -148,7 +318,7 @@ pub fn draw_rect_text(
 ) {
     draw_solid_rect(
         img,
-        &background_color,
+        background_color,
         height as u32,
         width as u32,
         x_pos,
@@ -196,10 +366,18 @@ pub fn draw_rect_text(
 /// * `height` - u32 - Desired height of gradient rectangle.
 /// * `x_pos` - X-coordinate of top corner of rectangle on `img`
 /// * `y_pos` - y-coordinate of top corner of rectangle on `img`
-pub fn draw_gradient_rect(img: &mut DynamicImage, height: u32, width: u32, x_pos: u32, y_pos: u32) {
+/// * `opacity` - Opacity of the rectangle, between 0.0 and 1.0.
+pub fn draw_gradient_rect(
+    img: &mut DynamicImage,
+    height: u32,
+    width: u32,
+    x_pos: u32,
+    y_pos: u32,
+    opacity: f32,
+) {
     let rect = create_gradient(width, height);
 
-    image::imageops::overlay(img, &rect, x_pos, y_pos);
+    blend::composite(img, &rect, x_pos as i32, y_pos as i32, opacity);
 }
 
 /// Preset: Draw a gradient rectangle filled with a gradient.
@@ -211,6 +389,7 @@ pub fn draw_gradient_rect(img: &mut DynamicImage, height: u32, width: u32, x_pos
 /// * `x_pos` - X-coordinate of top corner of rectangle on `img`
 /// * `y_pos` - y-coordinate of top corner of rectangle on `img`
 /// * `preset_name` - Name of the preset. Examples include "lemongrass", "pink_blue", "pastel_pink", "pastel_mauve"
+/// * `opacity` - Opacity of the rectangle, between 0.0 and 1.0.
 pub fn draw_preset_rect_gradient(
     img: &mut DynamicImage,
     width: u32,
@@ -218,51 +397,49 @@ pub fn draw_preset_rect_gradient(
     x_pos: u32,
     y_pos: u32,
     preset_name: &str,
+    opacity: f32,
 ) {
     let rect = create_gradient_preset(width, height, preset_name);
 
-    image::imageops::overlay(img, &rect, x_pos, y_pos);
+    blend::composite(img, &rect, x_pos as i32, y_pos as i32, opacity);
 }
 
 /// Draw two rectangles stacked on each other, for added depth.
 ///
 /// ### Arguments
 /// * `img` - A mutable ref to a DynamicImage.
-/// * `background_color1`: Rgb color of first rectangle.
-/// * `background_color2` : Rgb color of second rectangle.
+/// * `background_color1`: Rgb or Rgba color of first rectangle.
+/// * `background_color2` : Rgb or Rgba color of second rectangle.
 /// * `width` - u32 - Desired width of gradient rectangle.
 /// * `height` - u32 - Desired height of gradient rectangle.
 /// * `x_pos` - X-coordinate of top corner of rectangle on `img`
 /// * `y_pos` - y-coordinate of top corner of rectangle on `img`
 pub fn draw_stacked_rect(
     img: &mut DynamicImage,
-    background_color1: &Rgb,
-    background_color2: &Rgb,
+    background_color1: impl Into<Rgba>,
+    background_color2: impl Into<Rgba>,
     width: u32,
     height: u32,
     x_pos: i32,
     y_pos: i32,
 ) {
-    draw_filled_rect_mut(
+    fill_rect(
         img,
-        Rect::at(x_pos, y_pos).of_size(width, height),
-        Rgba([
-            background_color1.r,
-            background_color1.g,
-            background_color1.b,
-            255u8,
-        ]),
+        background_color1.into(),
+        width,
+        height,
+        x_pos,
+        y_pos,
+        Quality::AntiAliased,
     );
-
-    draw_filled_rect_mut(
+    fill_rect(
         img,
-        Rect::at(x_pos + 10, y_pos + 10).of_size(width, height),
-        Rgba([
-            background_color2.r,
-            background_color2.g,
-            background_color2.b,
-            255u8,
-        ]),
+        background_color2.into(),
+        width,
+        height,
+        x_pos + 10,
+        y_pos + 10,
+        Quality::AntiAliased,
     );
 }
 
@@ -270,30 +447,59 @@ pub fn draw_stacked_rect(
 ///
 /// ### Arguments
 /// * `img` - A mutable ref to a DynamicImage.
-/// * `background_color1`: Rgb color of all borders.
+/// * `background_color1`: Rgb or Rgba color of all borders.
 /// * `width` - u32 - Desired width of rectangle.
 /// * `height` - u32 - Desired height of rectangle.
 /// * `x_pos` - X-coordinate of top corner of rectangle on `img`
 /// * `y_pos` - y-coordinate of top corner of rectangle on `img`
 pub fn draw_stacked_borders(
     img: &mut DynamicImage,
-    background_color: &Rgb,
+    background_color: impl Into<Rgba>,
+    width: u32,
+    height: u32,
+    x_pos: i32,
+    y_pos: i32,
+) {
+    draw_stacked_borders_with_quality(
+        img,
+        background_color,
+        width,
+        height,
+        x_pos,
+        y_pos,
+        Quality::AntiAliased,
+    );
+}
+
+/// Draw multiple borders stacked on each other, choosing whether their edges are anti-aliased.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `background_color1`: Rgb or Rgba color of all borders.
+/// * `width` - u32 - Desired width of rectangle.
+/// * `height` - u32 - Desired height of rectangle.
+/// * `x_pos` - X-coordinate of top corner of rectangle on `img`
+/// * `y_pos` - y-coordinate of top corner of rectangle on `img`
+/// * `quality` - `Quality::AntiAliased` for smooth edges, or `Quality::Fast` for hard edges.
+pub fn draw_stacked_borders_with_quality(
+    img: &mut DynamicImage,
+    background_color: impl Into<Rgba>,
     width: u32,
     height: u32,
     mut x_pos: i32,
     mut y_pos: i32,
+    quality: Quality,
 ) {
+    let color = background_color.into();
     for _ in 0..3 {
-        draw_hollow_rect_mut(
-            img,
-            Rect::at(x_pos, y_pos).of_size(width, height),
-            Rgba([
-                background_color.r,
-                background_color.g,
-                background_color.b,
-                255u8,
-            ]),
+        let border = raster::rect_outline(
+            x_pos as f32,
+            y_pos as f32,
+            width as f32,
+            height as f32,
+            1.0,
         );
+        raster::fill_contours(img, &border, color.into(), quality);
 
         x_pos -= 40;
         y_pos += 40;
@@ -304,30 +510,30 @@ pub fn draw_stacked_borders(
 ///
 /// ### Arguments
 /// * `img` - A mutable ref to a DynamicImage.
-/// * `background_color1`: Rgb color of first rectangle.
-/// * `background_color2` : Rgb color of second rectangle.
+/// * `background_color1`: Rgb or Rgba color of first rectangle.
+/// * `background_color2` : Rgb or Rgba color of second rectangle.
 /// * `width` - u32 - Desired width of gradient rectangle.
 /// * `height` - u32 - Desired height of gradient rectangle.
 /// * `x_pos` - X-coordinate of top corner of rectangle on `img`
 /// * `y_pos` - y-coordinate of top corner of rectangle on `img`
 pub fn draw_inline_border_rect(
     img: &mut DynamicImage,
-    background_color: &Rgb,
-    background_color2: &Rgb,
+    background_color: impl Into<Rgba>,
+    background_color2: impl Into<Rgba>,
     width: u32,
     height: u32,
     x_pos: i32,
     y_pos: i32,
 ) {
-    draw_filled_rect_mut(
+    let background_color2 = background_color2.into();
+    fill_rect(
         img,
-        Rect::at(x_pos, y_pos).of_size(width, height),
-        Rgba([
-            background_color.r,
-            background_color.g,
-            background_color.b,
-            255u8,
-        ]),
+        background_color.into(),
+        width,
+        height,
+        x_pos,
+        y_pos,
+        Quality::AntiAliased,
     );
 
     // Draw border
@@ -336,15 +542,14 @@ pub fn draw_inline_border_rect(
     let horizontal_width = width - (2 * inset) as u32;
     let _vertical_height = height - (2 * inset) as u32;
 
-    draw_filled_rect_mut(
+    fill_rect(
         img,
-        Rect::at(x_pos + inset, y_pos + inset).of_size(horizontal_width, 10),
-        Rgba([
-            background_color2.r,
-            background_color2.g,
-            background_color2.b,
-            255u8,
-        ]),
+        background_color2,
+        horizontal_width,
+        10,
+        x_pos + inset,
+        y_pos + inset,
+        Quality::AntiAliased,
     );
 
     // draw_filled_rect_mut(img,
@@ -352,15 +557,14 @@ pub fn draw_inline_border_rect(
     //     Rgba([background_color2.r, background_color2.g,
     //     background_color2.b, 255u8]));
 
-    draw_filled_rect_mut(
+    fill_rect(
         img,
-        Rect::at(x_pos + inset, 550).of_size(horizontal_width, 10),
-        Rgba([
-            background_color2.r,
-            background_color2.g,
-            background_color2.b,
-            255u8,
-        ]),
+        background_color2,
+        horizontal_width,
+        10,
+        x_pos + inset,
+        550,
+        Quality::AntiAliased,
     );
 
     // draw_filled_rect_mut(img,
@@ -371,6 +575,85 @@ pub fn draw_inline_border_rect(
     // Rect::at(x_pos + INSET, y_pos + INSET).of_size(width - (2 * INSET) as u32, height - (2 * INSET) as u32),
 }
 
+/// Draw a group of elements with a shared opacity.
+///
+/// The elements are drawn onto a transparent layer the size of `img`, which is then
+/// blended onto `img` as a whole, so overlapping elements within the group don't show through each other.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `opacity` - Opacity of the whole group, between 0.0 and 1.0.
+/// * `draw` - Closure which draws the group's elements onto the layer it is given.
+///
+/// ### Example
+/// ```ignore
+/// draw_group(&mut img, 0.5, |layer| {
+///     draw_solid_rect(layer, &blue, 200, 100, 20, 20);
+///     draw_circle(layer, (120.0, 70.0), 40.0, &white, Quality::AntiAliased);
+/// });
+/// ```
+pub fn draw_group<F>(img: &mut DynamicImage, opacity: f32, draw: F)
+where
+    F: FnOnce(&mut DynamicImage),
+{
+    draw_blended_group(img, BlendMode::Normal, opacity, draw);
+}
+
+/// Draw a group of elements, which is blended onto the image with a blend mode.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `mode` - Blend mode, such as `BlendMode::Multiply` or `BlendMode::Screen`.
+/// * `opacity` - Opacity of the whole group, between 0.0 and 1.0.
+/// * `draw` - Closure which draws the group's elements onto the layer it is given.
+pub fn draw_blended_group<F>(img: &mut DynamicImage, mode: BlendMode, opacity: f32, draw: F)
+where
+    F: FnOnce(&mut DynamicImage),
+{
+    let mut layer = DynamicImage::new_rgba8(img.width(), img.height());
+    draw(&mut layer);
+    blend::blend(img, &layer, 0, 0, mode, opacity);
+}
+
+/// Lay a solid colour over an entire image with a blend mode, such as a multiply or screen tint over a photo.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `color` - Rgb or Rgba color of the overlay.
+/// * `mode` - Blend mode, such as `BlendMode::Multiply` or `BlendMode::Screen`.
+/// * `opacity` - Opacity of the overlay, between 0.0 and 1.0.
+pub fn draw_color_overlay(
+    img: &mut DynamicImage,
+    color: impl Into<Rgba>,
+    mode: BlendMode,
+    opacity: f32,
+) {
+    let color: image::Rgba<u8> = color.into().into();
+    for y in 0..img.height() {
+        for x in 0..img.width() {
+            let pixel = blend::blend_pixel(img.get_pixel(x, y), color, mode, opacity);
+            img.put_pixel(x, y, pixel);
+        }
+    }
+}
+
+/// Lay a preset gradient over an entire image with a blend mode.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `preset_name` - Name of the preset. Examples include "lemongrass", "pinkblue", "pink_pastel", "mauve_pastel"
+/// * `mode` - Blend mode, such as `BlendMode::Multiply` or `BlendMode::Screen`.
+/// * `opacity` - Opacity of the overlay, between 0.0 and 1.0.
+pub fn draw_gradient_overlay(
+    img: &mut DynamicImage,
+    preset_name: &str,
+    mode: BlendMode,
+    opacity: f32,
+) {
+    let gradient = create_gradient_preset(img.width(), img.height(), preset_name);
+    blend::blend(img, &gradient, 0, 0, mode, opacity);
+}
+
 /// Create a gradient element in the shape of a Rect.
 ///
 /// Returns a DynamicImage.
@@ -458,6 +741,20 @@ pub fn create_gradient_preset(width: u32, height: u32, name: &str) -> DynamicIma
     return rgba_img;
 }
 
+// Fill a rectangle, blending its colour with the pixels underneath.
+fn fill_rect(
+    img: &mut DynamicImage,
+    color: Rgba,
+    width: u32,
+    height: u32,
+    x_pos: i32,
+    y_pos: i32,
+    quality: Quality,
+) {
+    let contour = raster::rect(x_pos as f32, y_pos as f32, width as f32, height as f32);
+    raster::fill_contours(img, &[contour], color.into(), quality);
+}
+
 // #[wasm_bindgen]
 // pub fn draw_dyn_rect(image: &mut DynamicImage, background_color: &Rgb, height: u32, width: u32, x_pos: i32, y_pos: i32) {
 //     let mut image = image.to_rgba();
@@ -481,7 +778,7 @@ pub fn create_gradient_preset(width: u32, height: u32, name: &str) -> DynamicIma
 #[wasm_bindgen]
 #[derive(Debug)]
 pub struct Triangle {
-    background_color: Rgb,
+    background_color: Rgba,
     pub x1: i32,
     pub y1: i32,
     pub x2: i32,
@@ -502,20 +799,44 @@ impl Triangle {
         x3: i32,
         y3: i32,
     ) -> Triangle {
-        return Triangle {
-            background_color: background_color,
-            x1: x1,
-            y1: y1,
-            x2: x2,
-            y2: y2,
-            x3: x3,
-            y3: y3,
-        };
+        Triangle::new_with_alpha(Rgba::from(background_color), x1, y1, x2, y2, x3, y3)
+    }
+
+    /// Create a new Triangle, with specified co-ordinates for its 3 points, and a
+    /// background color which may be transparent.
+    pub fn new_with_alpha(
+        background_color: Rgba,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        x3: i32,
+        y3: i32,
+    ) -> Triangle {
+        Triangle {
+            background_color,
+            x1,
+            y1,
+            x2,
+            y2,
+            x3,
+            y3,
+        }
     }
 
-    /// Get the background colour of the Triangle.
+    /// Get the background colour of the Triangle, without its alpha.
     pub fn background_color(self) -> Rgb {
-        return self.background_color;
+        let color = self.background_color;
+        Rgb {
+            r: color.r,
+            g: color.g,
+            b: color.b,
+        }
+    }
+
+    /// Get the background colour of the Triangle, including its alpha.
+    pub fn background_rgba(self) -> Rgba {
+        self.background_color
     }
 
     /// Get the x1 co-ordinate of the Triangle.
@@ -538,3 +859,37 @@ impl Triangle {
         return self.y2;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Count the pixels which are partly covered, i.e. neither untouched nor fully opaque.
+    fn partial_pixels(img: &DynamicImage) -> usize {
+        img.pixels()
+            .filter(|(_, _, pixel)| pixel[3] > 0 && pixel[3] < 255)
+            .count()
+    }
+
+    #[test]
+    fn fast_triangles_have_hard_edges() {
+        let white = Rgba::new(255, 255, 255, 255);
+        let triangle = || Triangle::new_with_alpha(white, 2, 2, 30, 5, 9, 28);
+        let mut smooth = DynamicImage::new_rgba8(32, 32);
+        draw_triangle_with_quality(&mut smooth, triangle(), Quality::AntiAliased);
+        let mut fast = DynamicImage::new_rgba8(32, 32);
+        draw_triangle_with_quality(&mut fast, triangle(), Quality::Fast);
+
+        assert!(partial_pixels(&smooth) > 0);
+        assert_eq!(partial_pixels(&fast), 0);
+    }
+
+    #[test]
+    fn fast_stacked_borders_have_hard_edges() {
+        let white = Rgba::new(255, 255, 255, 255);
+        let mut fast = DynamicImage::new_rgba8(160, 160);
+        draw_stacked_borders_with_quality(&mut fast, white, 60, 40, 90, 10, Quality::Fast);
+        assert_eq!(partial_pixels(&fast), 0);
+        assert!(fast.pixels().any(|(_, _, pixel)| pixel[3] == 255));
+    }
+}
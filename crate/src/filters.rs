@@ -0,0 +1,371 @@
+//! Filters and colour adjustments for treating images, such as backgrounds before text is drawn onto them.
+//!
+//! Each filter can be applied on its own, or several can be chained in a pipeline with `apply_filters`.
+//!
+//! ### Example
+//! ```ignore
+//! // Darken and soften a busy photo so that text drawn over it stays readable.
+//! apply_filters(
+//!     &mut img,
+//!     &[Filter::GaussianBlur(3.0), Filter::Brightness(-0.25), Filter::Vignette(0.6)],
+//! );
+//! ```
+
+use crate::{helpers, PhotonImage, Rgb};
+use image::DynamicImage;
+use std::f32::consts::PI;
+
+/// A filter or colour adjustment which can be applied to an image.
+#[derive(Debug, Clone)]
+pub enum Filter {
+    /// Gaussian blur with the given standard deviation, in pixels.
+    GaussianBlur(f32),
+    /// Box blur with the given radius, in pixels.
+    BoxBlur(u32),
+    /// Brighten (positive) or darken (negative) the image, from -1.0 to 1.0.
+    Brightness(f32),
+    /// Increase (positive) or decrease (negative) the contrast, from -1.0 to 1.0.
+    Contrast(f32),
+    /// Increase (positive) or decrease (negative) the saturation, from -1.0 (grayscale) upwards.
+    Saturation(f32),
+    /// Rotate the hue of each pixel by the given number of degrees.
+    HueRotate(f32),
+    /// Convert to grayscale.
+    Grayscale,
+    /// Apply a warm, brown-toned sepia effect.
+    Sepia,
+    /// Map the shadows of the image to the first colour and the highlights to the second.
+    Duotone(Rgb, Rgb),
+    /// Darken the edges of the image, with a strength from 0.0 to 1.0.
+    Vignette(f32),
+    /// Mix a colour into the image, by an amount from 0.0 to 1.0.
+    Tint(Rgb, f32),
+}
+
+/// Apply a single filter to an image.
+pub fn apply_filter(img: &mut DynamicImage, filter: &Filter) {
+    match filter {
+        Filter::GaussianBlur(sigma) => gaussian_blur(img, *sigma),
+        Filter::BoxBlur(radius) => box_blur(img, *radius),
+        Filter::Brightness(amount) => brightness(img, *amount),
+        Filter::Contrast(amount) => contrast(img, *amount),
+        Filter::Saturation(amount) => saturation(img, *amount),
+        Filter::HueRotate(degrees) => hue_rotate(img, *degrees),
+        Filter::Grayscale => grayscale(img),
+        Filter::Sepia => sepia(img),
+        Filter::Duotone(shadows, highlights) => duotone(img, shadows, highlights),
+        Filter::Vignette(strength) => vignette(img, *strength),
+        Filter::Tint(color, amount) => tint(img, color, *amount),
+    }
+}
+
+/// Apply a series of filters to an image, in order.
+pub fn apply_filters(img: &mut DynamicImage, filters: &[Filter]) {
+    for filter in filters {
+        apply_filter(img, filter);
+    }
+}
+
+/// Apply a series of filters to a PhotonImage, in order.
+pub fn apply_filters_photon(photon_img: &mut PhotonImage, filters: &[Filter]) {
+    let mut img = helpers::dyn_image_from_raw(photon_img);
+    apply_filters(&mut img, filters);
+    photon_img.raw_pixels = img.to_rgba8().into_raw();
+}
+
+/// Blur an image with a Gaussian blur.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `sigma` - Standard deviation of the blur, in pixels. Larger values give a stronger blur.
+pub fn gaussian_blur(img: &mut DynamicImage, sigma: f32) {
+    if sigma <= 0.0 {
+        return;
+    }
+    *img = img.blur(sigma);
+}
+
+/// Blur an image with a box blur, where each pixel becomes the average of its neighbours.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `radius` - Number of neighbouring pixels on each side to average over.
+pub fn box_blur(img: &mut DynamicImage, radius: u32) {
+    if radius == 0 {
+        return;
+    }
+    let mut buffer = img.to_rgba8();
+    let (width, height) = buffer.dimensions();
+    if width == 0 || height == 0 {
+        return;
+    }
+
+    // The blur is separable, so blur each row and then each column.
+    for y in 0..height {
+        let row: Vec<[u8; 4]> = (0..width).map(|x| buffer.get_pixel(x, y).0).collect();
+        for (x, pixel) in box_average(&row, radius).into_iter().enumerate() {
+            buffer.put_pixel(x as u32, y, image::Rgba(pixel));
+        }
+    }
+    for x in 0..width {
+        let column: Vec<[u8; 4]> = (0..height).map(|y| buffer.get_pixel(x, y).0).collect();
+        for (y, pixel) in box_average(&column, radius).into_iter().enumerate() {
+            buffer.put_pixel(x, y as u32, image::Rgba(pixel));
+        }
+    }
+    *img = DynamicImage::ImageRgba8(buffer);
+}
+
+/// Brighten or darken an image.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `amount` - From -1.0 (black) to 1.0 (white), where 0.0 leaves the image unchanged.
+pub fn brightness(img: &mut DynamicImage, amount: f32) {
+    map_colors(img, |c| [c[0] + amount, c[1] + amount, c[2] + amount]);
+}
+
+/// Adjust the contrast of an image.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `amount` - From -1.0 (flat grey) to 1.0, where 0.0 leaves the image unchanged.
+pub fn contrast(img: &mut DynamicImage, amount: f32) {
+    // Map -1.0..1.0 onto a multiplier, so that positive amounts rise steeply.
+    let factor = if amount >= 0.0 {
+        1.0 / (1.0 - amount.min(0.99))
+    } else {
+        1.0 + amount.max(-1.0)
+    };
+    map_colors(img, |c| {
+        [
+            (c[0] - 0.5) * factor + 0.5,
+            (c[1] - 0.5) * factor + 0.5,
+            (c[2] - 0.5) * factor + 0.5,
+        ]
+    });
+}
+
+/// Adjust the saturation of an image.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `amount` - -1.0 gives a grayscale image, 0.0 leaves the image unchanged, and positive values increase saturation.
+pub fn saturation(img: &mut DynamicImage, amount: f32) {
+    let factor = 1.0 + amount.max(-1.0);
+    map_colors(img, |c| {
+        let lum = luminance(c);
+        [
+            lum + (c[0] - lum) * factor,
+            lum + (c[1] - lum) * factor,
+            lum + (c[2] - lum) * factor,
+        ]
+    });
+}
+
+/// Rotate the hue of an image, shifting every colour around the colour wheel.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `degrees` - Angle to rotate the hue by.
+pub fn hue_rotate(img: &mut DynamicImage, degrees: f32) {
+    let (sin, cos) = (degrees * PI / 180.0).sin_cos();
+    // Hue rotation matrix, which keeps the luminance of each colour constant.
+    let m = [
+        [
+            0.213 + cos * 0.787 - sin * 0.213,
+            0.715 - cos * 0.715 - sin * 0.715,
+            0.072 - cos * 0.072 + sin * 0.928,
+        ],
+        [
+            0.213 - cos * 0.213 + sin * 0.143,
+            0.715 + cos * 0.285 + sin * 0.140,
+            0.072 - cos * 0.072 - sin * 0.283,
+        ],
+        [
+            0.213 - cos * 0.213 - sin * 0.787,
+            0.715 - cos * 0.715 + sin * 0.715,
+            0.072 + cos * 0.928 + sin * 0.072,
+        ],
+    ];
+    map_colors(img, |c| apply_matrix(&m, c));
+}
+
+/// Convert an image to grayscale, keeping its alpha channel.
+pub fn grayscale(img: &mut DynamicImage) {
+    map_colors(img, |c| {
+        let lum = luminance(c);
+        [lum, lum, lum]
+    });
+}
+
+/// Apply a sepia tone to an image.
+pub fn sepia(img: &mut DynamicImage) {
+    let m = [
+        [0.393, 0.769, 0.189],
+        [0.349, 0.686, 0.168],
+        [0.272, 0.534, 0.131],
+    ];
+    map_colors(img, |c| apply_matrix(&m, c));
+}
+
+/// Apply a duotone effect, mapping the dark areas of the image to one colour and the light areas to another.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `shadows` - Rgb color used for the darkest parts of the image.
+/// * `highlights` - Rgb color used for the lightest parts of the image.
+pub fn duotone(img: &mut DynamicImage, shadows: &Rgb, highlights: &Rgb) {
+    let dark = rgb_to_floats(shadows);
+    let light = rgb_to_floats(highlights);
+    map_colors(img, |c| {
+        let t = luminance(c);
+        [
+            dark[0] + (light[0] - dark[0]) * t,
+            dark[1] + (light[1] - dark[1]) * t,
+            dark[2] + (light[2] - dark[2]) * t,
+        ]
+    });
+}
+
+/// Darken the edges of an image, drawing the eye towards its centre.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `strength` - From 0.0 (no effect) to 1.0 (black corners).
+pub fn vignette(img: &mut DynamicImage, strength: f32) {
+    let mut buffer = img.to_rgba8();
+    let (width, height) = buffer.dimensions();
+    let (cx, cy) = (width as f32 / 2.0, height as f32 / 2.0);
+    let max_dist = (cx * cx + cy * cy).sqrt();
+    let strength = strength.clamp(0.0, 1.0);
+
+    for (x, y, pixel) in buffer.enumerate_pixels_mut() {
+        let (dx, dy) = (x as f32 + 0.5 - cx, y as f32 + 0.5 - cy);
+        let dist = (dx * dx + dy * dy).sqrt() / max_dist;
+        // Leave the centre untouched, then fall off smoothly towards the corners.
+        let t = ((dist - 0.4) / 0.6).clamp(0.0, 1.0);
+        let factor = 1.0 - strength * t * t * (3.0 - 2.0 * t);
+        for c in 0..3 {
+            pixel[c] = (pixel[c] as f32 * factor).round() as u8;
+        }
+    }
+    *img = DynamicImage::ImageRgba8(buffer);
+}
+
+/// Tint an image by mixing a colour into every pixel.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `color` - Rgb color of the tint.
+/// * `amount` - From 0.0 (no effect) to 1.0 (solid colour).
+pub fn tint(img: &mut DynamicImage, color: &Rgb, amount: f32) {
+    let tint = rgb_to_floats(color);
+    let amount = amount.clamp(0.0, 1.0);
+    map_colors(img, |c| {
+        [
+            c[0] + (tint[0] - c[0]) * amount,
+            c[1] + (tint[1] - c[1]) * amount,
+            c[2] + (tint[2] - c[2]) * amount,
+        ]
+    });
+}
+
+// Apply a function to the colour of each pixel, with channels between 0.0 and 1.0.
+// Alpha is left untouched, and results are clamped back into range.
+fn map_colors<F>(img: &mut DynamicImage, f: F)
+where
+    F: Fn([f32; 3]) -> [f32; 3],
+{
+    let mut buffer = img.to_rgba8();
+    for pixel in buffer.pixels_mut() {
+        let color = f([
+            pixel[0] as f32 / 255.0,
+            pixel[1] as f32 / 255.0,
+            pixel[2] as f32 / 255.0,
+        ]);
+        for c in 0..3 {
+            pixel[c] = (color[c] * 255.0).round().clamp(0.0, 255.0) as u8;
+        }
+    }
+    *img = DynamicImage::ImageRgba8(buffer);
+}
+
+fn luminance(c: [f32; 3]) -> f32 {
+    0.2126 * c[0] + 0.7152 * c[1] + 0.0722 * c[2]
+}
+
+fn apply_matrix(m: &[[f32; 3]; 3], c: [f32; 3]) -> [f32; 3] {
+    [
+        m[0][0] * c[0] + m[0][1] * c[1] + m[0][2] * c[2],
+        m[1][0] * c[0] + m[1][1] * c[1] + m[1][2] * c[2],
+        m[2][0] * c[0] + m[2][1] * c[1] + m[2][2] * c[2],
+    ]
+}
+
+fn rgb_to_floats(color: &Rgb) -> [f32; 3] {
+    [
+        color.r as f32 / 255.0,
+        color.g as f32 / 255.0,
+        color.b as f32 / 255.0,
+    ]
+}
+
+// Average each pixel in a line with its neighbours, clamping at the ends of the line.
+fn box_average(line: &[[u8; 4]], radius: u32) -> Vec<[u8; 4]> {
+    if line.is_empty() {
+        return vec![];
+    }
+    let len = line.len() as i64;
+    // Past the length of the line, a wider window only adds more copies of its end pixels, so the
+    // radius is clamped to keep the running sums from overflowing.
+    let radius = (radius as i64).min(len);
+    let window = (2 * radius + 1) as u32;
+    let mut sums = [0u32; 4];
+    let at = |i: i64| line[i.clamp(0, len - 1) as usize];
+
+    for i in -radius..=radius {
+        for (sum, value) in sums.iter_mut().zip(at(i).iter()) {
+            *sum += *value as u32;
+        }
+    }
+
+    let mut averaged = Vec::with_capacity(line.len());
+    for i in 0..len {
+        let mut pixel = [0u8; 4];
+        for (value, sum) in pixel.iter_mut().zip(sums.iter()) {
+            *value = ((sum + window / 2) / window) as u8;
+        }
+        averaged.push(pixel);
+
+        // Slide the window along by one pixel.
+        let (leaving, entering) = (at(i - radius), at(i + radius + 1));
+        for (c, sum) in sums.iter_mut().enumerate() {
+            *sum = *sum + entering[c] as u32 - leaving[c] as u32;
+        }
+    }
+    averaged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn box_blur_skips_empty_images() {
+        let mut img = DynamicImage::new_rgba8(0, 5);
+        box_blur(&mut img, 3);
+        assert_eq!(img.to_rgba8().dimensions(), (0, 5));
+    }
+
+    #[test]
+    fn box_blur_clamps_huge_radius() {
+        let mut img = DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(
+            4,
+            3,
+            image::Rgba([200, 100, 50, 255]),
+        ));
+        box_blur(&mut img, u32::MAX);
+        assert_eq!(img.to_rgba8().get_pixel(2, 1).0, [200, 100, 50, 255]);
+    }
+}
@@ -0,0 +1,783 @@
+//! Flowcharts and process diagrams: boxes of text joined by arrows.
+//!
+//! A `Flowchart` holds a list of nodes and the edges between them. Nodes can be positioned by hand,
+//! or `layout` can arrange them automatically into layers, so that each edge points from one layer
+//! to a later one. Nodes and connectors can also be drawn on their own with `draw_node` and `draw_connector`.
+//!
+//! ### Example
+//! ```ignore
+//! let mut chart = Flowchart::new(Direction::TopDown);
+//! chart.add_node("start", "Start", NodeShape::Ellipse);
+//! chart.add_node("check", "Is the image large enough?", NodeShape::Diamond);
+//! chart.add_node("resize", "Resize", NodeShape::Rounded);
+//! chart.add_edge("start", "check", None);
+//! chart.add_edge("check", "resize", Some("No"));
+//! chart.layout();
+//! draw_flowchart(&mut img, &chart, 40, 40);
+//! ```
+
+use crate::raster::{self, Contour, Quality};
+use crate::text::{self, TextAlign};
+use crate::{Rgb, Rgba};
+use image::DynamicImage;
+
+/// Draw a flowchart, with its top-left corner at `x`, `y`. Edges are drawn first, so that their ends
+/// tuck underneath the nodes they join. Edges which point up loop round to the left of the nodes,
+/// by up to 120 pixels, so leave room for them there.
+///
+/// ### Arguments
+/// * `img` - Image to draw the flowchart onto.
+/// * `flowchart` - Nodes and edges of the flowchart, positioned relative to its top-left corner.
+/// * `x` - X-coordinate of the left edge of the flowchart.
+/// * `y` - Y-coordinate of the top edge of the flowchart.
+pub fn draw_flowchart(img: &mut DynamicImage, flowchart: &Flowchart, x: u32, y: u32) {
+    let nodes: Vec<Node> = flowchart
+        .nodes
+        .iter()
+        .map(|node| Node {
+            x: node.x + x as f32,
+            y: node.y + y as f32,
+            ..node.clone()
+        })
+        .collect();
+    let find = |id: &str| nodes.iter().find(|node| node.id == id);
+
+    for edge in &flowchart.edges {
+        if let (Some(from), Some(to)) = (find(&edge.from), find(&edge.to)) {
+            draw_connector(img, from, to, edge);
+        }
+    }
+    for node in &nodes {
+        draw_node(img, node);
+    }
+}
+
+/// Draw a node: a shape with a border, and its label centred inside it.
+/// The label is wrapped to fit the shape, and shrunk if it's too long to fit at the node's font size.
+///
+/// ### Arguments
+/// * `img` - Image to draw the node onto.
+/// * `node` - Node to draw, at its own position.
+pub fn draw_node(img: &mut DynamicImage, node: &Node) {
+    let style = &node.style;
+    raster::fill_contours(
+        img,
+        &[node.contour(0.0)],
+        style.fill.into(),
+        Quality::AntiAliased,
+    );
+    if style.border_width > 0.0 {
+        // The border is the shape with a smaller copy of it, wound the other way, cut out of it.
+        let mut inner = node.contour(style.border_width);
+        inner.reverse();
+        raster::fill_contours(
+            img,
+            &[node.contour(0.0), inner],
+            style.border_color.into(),
+            Quality::AntiAliased,
+        );
+    }
+
+    let (width, height) = node.text_area();
+    if node.label.is_empty() || width < 1.0 || height < 1.0 {
+        return;
+    }
+    let (width, height) = (width as u32, height as u32);
+    let font_size = text::fit_font_size(&node.label, &style.font, style.font_size, width, height);
+    let lines = text::wrap_text(&node.label, &style.font, font_size, width).len();
+    let line_height = text::measure_text(&node.label, &style.font, font_size).1;
+    let block_height = line_height * lines as u32;
+    let (cx, cy) = node.centre();
+    text::draw_text_block(
+        img,
+        &node.label,
+        (cx - width as f32 / 2.0).max(0.0).round() as u32,
+        (cy - block_height as f32 / 2.0).max(0.0).round() as u32,
+        width,
+        &style.font,
+        font_size,
+        &style.text_color,
+        TextAlign::Centre,
+    );
+}
+
+/// Draw a connector between two nodes, routed as the edge's style sets, with an arrowhead where it
+/// meets `to` and the edge's label, if it has one, halfway along it. Orthogonal and curved connectors
+/// which point up, such as loops back to an earlier step, run round the left of the nodes.
+///
+/// ### Arguments
+/// * `img` - Image to draw the connector onto.
+/// * `from` - Node the connector starts from.
+/// * `to` - Node the connector points to.
+/// * `edge` - Label and style of the connector.
+pub fn draw_connector(img: &mut DynamicImage, from: &Node, to: &Node, edge: &Edge) {
+    let style = &edge.style;
+    let mut path = connector_path(from, to, style.routing);
+    if path.len() < 2 {
+        return;
+    }
+    let color = style.color.into();
+    let label_point = point_along(&path, 0.5);
+
+    if style.arrow_size > 0.0 {
+        let tip = path[path.len() - 1];
+        let (dx, dy) = direction(path[path.len() - 2], tip);
+        let size = style.arrow_size;
+        let base = (tip.0 - dx * size, tip.1 - dy * size);
+        let (nx, ny) = (-dy * size * 0.45, dx * size * 0.45);
+        let head = raster::polygon(&[tip, (base.0 + nx, base.1 + ny), (base.0 - nx, base.1 - ny)]);
+        raster::fill_contours(img, &[head], color, Quality::AntiAliased);
+        // The line stops inside the arrowhead, so its square end doesn't poke out of the tip.
+        let last = path.len() - 1;
+        path[last] = (tip.0 - dx * size * 0.5, tip.1 - dy * size * 0.5);
+    }
+    raster::fill_contours(
+        img,
+        &raster::polyline(&path, style.thickness),
+        color,
+        Quality::AntiAliased,
+    );
+
+    if let Some(label) = &edge.label {
+        draw_edge_label(img, label, style, label_point);
+    }
+}
+
+// Draw an edge's label centred on a point, on a rounded background if the edge's style has one.
+fn draw_edge_label(img: &mut DynamicImage, label: &str, style: &EdgeStyle, point: (f32, f32)) {
+    let (width, height) = text::measure_text(label, &style.font, style.font_size);
+    let (width, height) = (width as f32, height as f32);
+    let padding = style.font_size * 0.4;
+    if let Some(background) = style.label_background {
+        let pill = raster::rounded_rect(
+            point.0 - width / 2.0 - padding,
+            point.1 - height / 2.0 - padding / 2.0,
+            width + padding * 2.0,
+            height + padding,
+            (height + padding) / 2.0,
+        );
+        raster::fill_contours(img, &[pill], background.into(), Quality::AntiAliased);
+    }
+    text::draw_text_block(
+        img,
+        label,
+        (point.0 - width / 2.0).max(0.0).round() as u32,
+        (point.1 - height / 2.0).max(0.0).round() as u32,
+        width.ceil() as u32 + 1,
+        &style.font,
+        style.font_size,
+        &style.text_color,
+        TextAlign::Centre,
+    );
+}
+
+// Number of straight segments a curved connector is drawn with.
+const CURVE_STEPS: usize = 24;
+
+// Points along a connector from one node to another, from the edge of `from` to the edge of `to`.
+fn connector_path(from: &Node, to: &Node, routing: Routing) -> Vec<(f32, f32)> {
+    let (from_centre, to_centre) = (from.centre(), to.centre());
+    if routing == Routing::Straight {
+        return vec![
+            from.boundary_towards(to_centre),
+            to.boundary_towards(from_centre),
+        ];
+    }
+
+    // Orthogonal and curved connectors leave and enter nodes through the middle of a side,
+    // running vertically if the nodes are further apart vertically than horizontally.
+    let vertical_gap = (to.y - from.bottom()).max(from.y - to.bottom());
+    let horizontal_gap = (to.x - from.right()).max(from.x - to.right());
+    let vertical = vertical_gap >= horizontal_gap;
+
+    // Connectors pointing up, such as loops back to an earlier step, would cut through the nodes
+    // between, so they leave and enter the nodes' left sides and loop round beside them.
+    if vertical && to.bottom() <= from.y {
+        let start = (from.x, from_centre.1);
+        let end = (to.x, to_centre.1);
+        let gap = (from.y - to.bottom()).clamp(20.0, 60.0);
+        let side = from.x.min(to.x) - gap;
+        if routing == Routing::Curved {
+            // A curve's control points are further out than the furthest point it reaches.
+            return cubic_bezier(start, (side - gap, start.1), (side - gap, end.1), end);
+        }
+        return vec![start, (side, start.1), (side, end.1), end];
+    }
+    let (start, end) = match (
+        vertical,
+        to_centre.1 >= from_centre.1,
+        to_centre.0 >= from_centre.0,
+    ) {
+        (true, true, _) => ((from_centre.0, from.bottom()), (to_centre.0, to.y)),
+        (true, false, _) => ((from_centre.0, from.y), (to_centre.0, to.bottom())),
+        (false, _, true) => ((from.right(), from_centre.1), (to.x, to_centre.1)),
+        (false, _, false) => ((from.x, from_centre.1), (to.right(), to_centre.1)),
+    };
+
+    match routing {
+        Routing::Curved => {
+            // Control points continue straight out of each side, halfway towards the other node.
+            let (c1, c2) = if vertical {
+                let middle = (start.1 + end.1) / 2.0;
+                ((start.0, middle), (end.0, middle))
+            } else {
+                let middle = (start.0 + end.0) / 2.0;
+                ((middle, start.1), (middle, end.1))
+            };
+            cubic_bezier(start, c1, c2, end)
+        }
+        _ => {
+            // Aligned nodes are joined directly, and others with a dog-leg halfway between them.
+            if vertical && (start.0 - end.0).abs() < 0.5
+                || !vertical && (start.1 - end.1).abs() < 0.5
+            {
+                return vec![start, end];
+            }
+            if vertical {
+                let middle = (start.1 + end.1) / 2.0;
+                vec![start, (start.0, middle), (end.0, middle), end]
+            } else {
+                let middle = (start.0 + end.0) / 2.0;
+                vec![start, (middle, start.1), (middle, end.1), end]
+            }
+        }
+    }
+}
+
+// Points along a cubic Bézier curve from `start` to `end`, pulled towards two control points.
+fn cubic_bezier(
+    start: (f32, f32),
+    c1: (f32, f32),
+    c2: (f32, f32),
+    end: (f32, f32),
+) -> Vec<(f32, f32)> {
+    (0..=CURVE_STEPS)
+        .map(|step| {
+            let t = step as f32 / CURVE_STEPS as f32;
+            let u = 1.0 - t;
+            let bezier = |a: f32, b: f32, c: f32, d: f32| {
+                u * u * u * a + 3.0 * u * u * t * b + 3.0 * u * t * t * c + t * t * t * d
+            };
+            (
+                bezier(start.0, c1.0, c2.0, end.0),
+                bezier(start.1, c1.1, c2.1, end.1),
+            )
+        })
+        .collect()
+}
+
+// Unit vector pointing from one point to another.
+fn direction(from: (f32, f32), to: (f32, f32)) -> (f32, f32) {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length = (dx * dx + dy * dy).sqrt();
+    if length < f32::EPSILON {
+        (0.0, 1.0)
+    } else {
+        (dx / length, dy / length)
+    }
+}
+
+// The point a fraction of the way along a path, by length.
+fn point_along(path: &[(f32, f32)], fraction: f32) -> (f32, f32) {
+    let length = |a: (f32, f32), b: (f32, f32)| ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)).sqrt();
+    let total: f32 = path.windows(2).map(|pair| length(pair[0], pair[1])).sum();
+    let mut remaining = total * fraction;
+    for pair in path.windows(2) {
+        let segment = length(pair[0], pair[1]);
+        if segment > 0.0 && remaining <= segment {
+            let t = remaining / segment;
+            return (
+                pair[0].0 + (pair[1].0 - pair[0].0) * t,
+                pair[0].1 + (pair[1].1 - pair[0].1) * t,
+            );
+        }
+        remaining -= segment;
+    }
+    path.last().copied().unwrap_or((0.0, 0.0))
+}
+
+/// Shape of a node's box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NodeShape {
+    Rect,
+    /// A rectangle with rounded corners.
+    Rounded,
+    /// A diamond, for decisions.
+    Diamond,
+    /// An ellipse, for the start and end of a process.
+    Ellipse,
+}
+
+/// Colours, border and font of a node.
+#[derive(Debug, Clone)]
+pub struct NodeStyle {
+    pub fill: Rgba,
+    pub border_color: Rgba,
+    /// Width of the border, drawn inside the node's edges. A width of 0 draws no border.
+    pub border_width: f32,
+    /// Radius of the corners of rounded nodes.
+    pub corner_radius: f32,
+    /// Font for the label, such as "Roboto-Regular".
+    pub font: String,
+    /// Largest font size for the label. Labels which don't fit at this size are shrunk.
+    pub font_size: f32,
+    pub text_color: Rgb,
+    /// Space between the label and the node's edges.
+    pub padding: f32,
+}
+
+impl Default for NodeStyle {
+    fn default() -> NodeStyle {
+        NodeStyle {
+            fill: Rgba::new(52, 73, 94, 255),
+            border_color: Rgba::new(236, 240, 241, 255),
+            border_width: 2.0,
+            corner_radius: 16.0,
+            font: "Roboto-Regular".to_string(),
+            font_size: 28.0,
+            text_color: Rgb {
+                r: 236,
+                g: 240,
+                b: 241,
+            },
+            padding: 14.0,
+        }
+    }
+}
+
+/// A box of text within a flowchart.
+#[derive(Debug, Clone)]
+pub struct Node {
+    /// Name used to refer to the node from edges. It isn't drawn.
+    pub id: String,
+    pub label: String,
+    pub shape: NodeShape,
+    /// X-coordinate of the node's left edge.
+    pub x: f32,
+    /// Y-coordinate of the node's top edge.
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub style: NodeStyle,
+}
+
+impl Node {
+    /// Create a new node, at the origin, in the default style.
+    pub fn new(id: &str, label: &str, shape: NodeShape) -> Node {
+        Node {
+            id: id.to_string(),
+            label: label.to_string(),
+            shape,
+            x: 0.0,
+            y: 0.0,
+            width: 240.0,
+            height: 100.0,
+            style: NodeStyle::default(),
+        }
+    }
+
+    /// X-coordinate of the node's right edge.
+    pub fn right(&self) -> f32 {
+        self.x + self.width
+    }
+
+    /// Y-coordinate of the node's bottom edge.
+    pub fn bottom(&self) -> f32 {
+        self.y + self.height
+    }
+
+    /// The centre of the node.
+    pub fn centre(&self) -> (f32, f32) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
+    /// The point where a line from the node's centre towards `point` crosses the edge of its shape.
+    pub fn boundary_towards(&self, point: (f32, f32)) -> (f32, f32) {
+        let (cx, cy) = self.centre();
+        let (dx, dy) = (point.0 - cx, point.1 - cy);
+        let (half_width, half_height) = (self.width / 2.0, self.height / 2.0);
+        if dx.abs() < f32::EPSILON && dy.abs() < f32::EPSILON {
+            return (cx, cy);
+        }
+        // How far along the line the edge is, as a multiple of (dx, dy).
+        let t = match self.shape {
+            NodeShape::Rect | NodeShape::Rounded => {
+                let tx = if dx == 0.0 {
+                    f32::INFINITY
+                } else {
+                    half_width / dx.abs()
+                };
+                let ty = if dy == 0.0 {
+                    f32::INFINITY
+                } else {
+                    half_height / dy.abs()
+                };
+                tx.min(ty)
+            }
+            NodeShape::Diamond => 1.0 / (dx.abs() / half_width + dy.abs() / half_height),
+            NodeShape::Ellipse => {
+                1.0 / ((dx / half_width).powi(2) + (dy / half_height).powi(2)).sqrt()
+            }
+        };
+        (cx + dx * t, cy + dy * t)
+    }
+
+    // Contour of the node's shape, shrunk by `inset` pixels on every side.
+    fn contour(&self, inset: f32) -> Contour {
+        let (cx, cy) = self.centre();
+        let (half_width, half_height) = (self.width / 2.0 - inset, self.height / 2.0 - inset);
+        if half_width <= 0.0 || half_height <= 0.0 {
+            return vec![];
+        }
+        match self.shape {
+            NodeShape::Rect => raster::rect(
+                cx - half_width,
+                cy - half_height,
+                half_width * 2.0,
+                half_height * 2.0,
+            ),
+            NodeShape::Rounded => raster::rounded_rect(
+                cx - half_width,
+                cy - half_height,
+                half_width * 2.0,
+                half_height * 2.0,
+                (self.style.corner_radius - inset).max(0.0),
+            ),
+            NodeShape::Diamond => {
+                // Shrinking a diamond by the same amount on every side scales it about its centre.
+                let (w, h) = (self.width / 2.0, self.height / 2.0);
+                let scale = (1.0 - inset * (w * w + h * h).sqrt() / (w * h)).max(0.0);
+                let (w, h) = (w * scale, h * scale);
+                raster::polygon(&[(cx, cy - h), (cx + w, cy), (cx, cy + h), (cx - w, cy)])
+            }
+            NodeShape::Ellipse => raster::ellipse(cx, cy, half_width, half_height),
+        }
+    }
+
+    // Width and height of the box centred in the node which its label fits within.
+    fn text_area(&self) -> (f32, f32) {
+        let padding = self.style.padding * 2.0;
+        match self.shape {
+            NodeShape::Rect | NodeShape::Rounded => (self.width - padding, self.height - padding),
+            // The largest box inside a diamond is half its width and height, which already leaves
+            // plenty of room around the label, so it isn't padded.
+            NodeShape::Diamond => (self.width / 2.0, self.height / 2.0),
+            // The largest box of the same proportions inside an ellipse is 1/√2 of its size.
+            NodeShape::Ellipse => (self.width * 0.707, self.height * 0.707),
+        }
+    }
+}
+
+/// How a connector is routed between two nodes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Routing {
+    /// A straight line between the nodes' edges, aimed at their centres.
+    Straight,
+    /// Horizontal and vertical lines, joining the middles of the nodes' sides.
+    Orthogonal,
+    /// A smooth curve, joining the middles of the nodes' sides.
+    Curved,
+}
+
+/// Colour, routing and label font of a connector.
+#[derive(Debug, Clone)]
+pub struct EdgeStyle {
+    pub color: Rgba,
+    /// Thickness of the line, in pixels.
+    pub thickness: f32,
+    /// Length of the arrowhead, in pixels. A length of 0 draws no arrowhead.
+    pub arrow_size: f32,
+    pub routing: Routing,
+    /// Font for the label, such as "Roboto-Regular".
+    pub font: String,
+    pub font_size: f32,
+    pub text_color: Rgb,
+    /// Colour of the rounded box behind the label, so it stands out from the line. If not set,
+    /// the label is drawn straight over the line.
+    pub label_background: Option<Rgba>,
+}
+
+impl Default for EdgeStyle {
+    fn default() -> EdgeStyle {
+        EdgeStyle {
+            color: Rgba::new(189, 195, 199, 255),
+            thickness: 3.0,
+            arrow_size: 16.0,
+            routing: Routing::Orthogonal,
+            font: "Roboto-Regular".to_string(),
+            font_size: 22.0,
+            text_color: Rgb {
+                r: 236,
+                g: 240,
+                b: 241,
+            },
+            label_background: Some(Rgba::new(30, 30, 30, 255)),
+        }
+    }
+}
+
+/// A connector from one node of a flowchart to another.
+#[derive(Debug, Clone)]
+pub struct Edge {
+    /// Id of the node the edge starts from.
+    pub from: String,
+    /// Id of the node the edge points to.
+    pub to: String,
+    pub label: Option<String>,
+    pub style: EdgeStyle,
+}
+
+impl Edge {
+    /// Create a new edge between two nodes, in the default style.
+    pub fn new(from: &str, to: &str, label: Option<&str>) -> Edge {
+        Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            label: label.map(|label| label.to_string()),
+            style: EdgeStyle::default(),
+        }
+    }
+}
+
+/// The direction a flowchart's layers run in when it's laid out automatically.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    /// Each layer is below the one before, with edges pointing down.
+    TopDown,
+    /// Each layer is to the right of the one before, with edges pointing right.
+    LeftRight,
+}
+
+/// Nodes and the edges between them, which can be laid out automatically.
+#[derive(Debug, Clone)]
+pub struct Flowchart {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+    pub direction: Direction,
+    /// Space between neighbouring nodes within a layer, in pixels.
+    pub node_gap: f32,
+    /// Space between layers, in pixels.
+    pub layer_gap: f32,
+    /// Style given to nodes as they're added.
+    pub node_style: NodeStyle,
+    /// Style given to edges as they're added.
+    pub edge_style: EdgeStyle,
+}
+
+impl Flowchart {
+    /// Create a new, empty flowchart.
+    pub fn new(direction: Direction) -> Flowchart {
+        Flowchart {
+            nodes: vec![],
+            edges: vec![],
+            direction,
+            node_gap: 60.0,
+            layer_gap: 90.0,
+            node_style: NodeStyle::default(),
+            edge_style: EdgeStyle::default(),
+        }
+    }
+
+    /// Add a node in the flowchart's node style, returning it so that it can be sized or styled.
+    pub fn add_node(&mut self, id: &str, label: &str, shape: NodeShape) -> &mut Node {
+        let mut node = Node::new(id, label, shape);
+        node.style = self.node_style.clone();
+        self.nodes.push(node);
+        self.nodes.last_mut().unwrap()
+    }
+
+    /// Add an edge between two nodes in the flowchart's edge style, returning it so that it can be styled.
+    pub fn add_edge(&mut self, from: &str, to: &str, label: Option<&str>) -> &mut Edge {
+        let mut edge = Edge::new(from, to, label);
+        edge.style = self.edge_style.clone();
+        self.edges.push(edge);
+        self.edges.last_mut().unwrap()
+    }
+
+    /// The node with the given id, if there is one.
+    pub fn node(&self, id: &str) -> Option<&Node> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    /// Width and height of the area covered by the flowchart's nodes, measured from its origin.
+    pub fn size(&self) -> (f32, f32) {
+        self.nodes.iter().fold((0.0, 0.0), |(width, height), node| {
+            (width.max(node.right()), height.max(node.bottom()))
+        })
+    }
+
+    /// Position every node automatically, in layers running in the flowchart's direction.
+    ///
+    /// Each node is placed in the layer after the furthest node with an edge to it, so edges point
+    /// from one layer to a later one, other than any which loop back. Nodes within each layer are
+    /// ordered to keep connected nodes close and reduce crossing edges, and each layer is centred.
+    pub fn layout(&mut self) {
+        let count = self.nodes.len();
+        let index = |id: &str| self.nodes.iter().position(|node| node.id == id);
+        let links: Vec<(usize, usize)> = self
+            .edges
+            .iter()
+            .filter_map(|edge| Some((index(&edge.from)?, index(&edge.to)?)))
+            .filter(|(from, to)| from != to)
+            .collect();
+        let forward = forward_links(count, &links);
+
+        // Layer each node by the longest path to it, visiting nodes in topological order.
+        let mut layer_of = vec![0; count];
+        for node in topological_order(count, &forward) {
+            for &(from, to) in &forward {
+                if from == node {
+                    layer_of[to] = layer_of[to].max(layer_of[from] + 1);
+                }
+            }
+        }
+        let layer_count = layer_of.iter().max().map_or(0, |last| last + 1);
+        let mut layers: Vec<Vec<usize>> = vec![vec![]; layer_count];
+        for (node, layer) in layer_of.iter().enumerate() {
+            layers[*layer].push(node);
+        }
+        order_layers(&mut layers, &links);
+
+        // Sizes of a node along the layers, and across them.
+        let direction = self.direction;
+        let breadth = |node: &Node| match direction {
+            Direction::TopDown => node.width,
+            Direction::LeftRight => node.height,
+        };
+        let depth = |node: &Node| match direction {
+            Direction::TopDown => node.height,
+            Direction::LeftRight => node.width,
+        };
+        let layer_breadth = |layer: &[usize]| {
+            layer.iter().map(|i| breadth(&self.nodes[*i])).sum::<f32>()
+                + self.node_gap * layer.len().saturating_sub(1) as f32
+        };
+        let widest = layers
+            .iter()
+            .map(|layer| layer_breadth(layer))
+            .fold(0.0, f32::max);
+
+        let mut positions = vec![(0.0, 0.0); count];
+        let mut layer_start = 0.0;
+        for layer in &layers {
+            let layer_depth = layer
+                .iter()
+                .map(|i| depth(&self.nodes[*i]))
+                .fold(0.0, f32::max);
+            let mut along = (widest - layer_breadth(layer)) / 2.0;
+            for &i in layer {
+                let node = &self.nodes[i];
+                let across = layer_start + (layer_depth - depth(node)) / 2.0;
+                positions[i] = match self.direction {
+                    Direction::TopDown => (along, across),
+                    Direction::LeftRight => (across, along),
+                };
+                along += breadth(node) + self.node_gap;
+            }
+            layer_start += layer_depth + self.layer_gap;
+        }
+        for (node, (x, y)) in self.nodes.iter_mut().zip(positions) {
+            node.x = x;
+            node.y = y;
+        }
+    }
+}
+
+// Links which don't loop back to a node they came from, found with a depth-first search:
+// a link to a node which is still being visited would close a loop, so is left out.
+fn forward_links(count: usize, links: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    // 0 is unvisited, 1 is being visited, 2 is done.
+    let mut state = vec![0u8; count];
+    let mut forward = vec![];
+    for root in 0..count {
+        if state[root] != 0 {
+            continue;
+        }
+        // Each entry is a node, and how many of its links have been followed so far.
+        let mut stack = vec![(root, 0)];
+        state[root] = 1;
+        while let Some((node, next)) = stack.pop() {
+            let outgoing: Vec<&(usize, usize)> =
+                links.iter().filter(|(from, _)| *from == node).collect();
+            match outgoing.get(next) {
+                Some(&&(from, to)) => {
+                    stack.push((node, next + 1));
+                    match state[to] {
+                        0 => {
+                            forward.push((from, to));
+                            state[to] = 1;
+                            stack.push((to, 0));
+                        }
+                        2 => forward.push((from, to)),
+                        _ => {}
+                    }
+                }
+                None => state[node] = 2,
+            }
+        }
+    }
+    forward
+}
+
+// Nodes in an order where every node comes after all of the nodes linking to it.
+// The links mustn't contain any loops.
+fn topological_order(count: usize, links: &[(usize, usize)]) -> Vec<usize> {
+    let mut incoming = vec![0; count];
+    for (_, to) in links {
+        incoming[*to] += 1;
+    }
+    let mut ready: Vec<usize> = (0..count).rev().filter(|i| incoming[*i] == 0).collect();
+    let mut order = vec![];
+    while let Some(node) = ready.pop() {
+        order.push(node);
+        for &(from, to) in links {
+            if from == node {
+                incoming[to] -= 1;
+                if incoming[to] == 0 {
+                    ready.push(to);
+                }
+            }
+        }
+    }
+    order
+}
+
+// Reorder the nodes within each layer so that each sits near the average position of the nodes
+// it's linked to in the neighbouring layer, sweeping down then up through the layers a few times.
+fn order_layers(layers: &mut [Vec<usize>], links: &[(usize, usize)]) {
+    let neighbours = |node: usize| {
+        links.iter().filter_map(move |&(from, to)| match node {
+            n if n == from => Some(to),
+            n if n == to => Some(from),
+            _ => None,
+        })
+    };
+    for sweep in 0..4 {
+        let order: Vec<usize> = match sweep % 2 {
+            0 => (1..layers.len()).collect(),
+            _ => (0..layers.len().saturating_sub(1)).rev().collect(),
+        };
+        for i in order {
+            let fixed = match sweep % 2 {
+                0 => &layers[i - 1],
+                _ => &layers[i + 1],
+            };
+            let position = |node: usize| fixed.iter().position(|n| *n == node);
+            let mut keyed: Vec<(f32, usize)> = layers[i]
+                .iter()
+                .enumerate()
+                .map(|(current, &node)| {
+                    let positions: Vec<usize> = neighbours(node).filter_map(position).collect();
+                    // Nodes without neighbours in the other layer keep their place.
+                    let key = match positions.len() {
+                        0 => current as f32,
+                        n => positions.iter().sum::<usize>() as f32 / n as f32,
+                    };
+                    (key, node)
+                })
+                .collect();
+            keyed.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+            layers[i] = keyed.into_iter().map(|(_, node)| node).collect();
+        }
+    }
+}
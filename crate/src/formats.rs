@@ -0,0 +1,214 @@
+//! Sizes and safe areas of social media formats, such as Instagram stories or YouTube thumbnails.
+//!
+//! The built-in formats are available through a `FormatRegistry`, which can be extended with custom formats.
+//!
+//! ### Example
+//! ```ignore
+//! let mut registry = FormatRegistry::new();
+//! registry.add(SocialFormat::new("newsletter_header", 600, 200, SafeArea::uniform(20)));
+//! let format = registry.get("newsletter_header")?;
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use wasm_bindgen::JsValue;
+
+/// Margins around the edge of a format which may be covered by the platform's interface,
+/// such as profile pictures, captions or buttons. Important content should be kept inside them.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SafeArea {
+    pub top: u32,
+    pub right: u32,
+    pub bottom: u32,
+    pub left: u32,
+}
+
+impl SafeArea {
+    /// Create a safe area from its margins, in the same order as CSS: top, right, bottom, left.
+    pub fn new(top: u32, right: u32, bottom: u32, left: u32) -> SafeArea {
+        SafeArea {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// Create a safe area with the same margin on each side.
+    pub fn uniform(margin: u32) -> SafeArea {
+        SafeArea::new(margin, margin, margin, margin)
+    }
+
+    /// A safe area with no margins, where the whole format is visible.
+    pub fn none() -> SafeArea {
+        SafeArea::default()
+    }
+}
+
+/// A social media format, with its size in pixels and safe area.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SocialFormat {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub safe_area: SafeArea,
+}
+
+impl SocialFormat {
+    /// Create a new format.
+    ///
+    /// ### Arguments
+    /// * `name` - Name used to look up the format, such as "instagram_story".
+    /// * `width` - Width of the format in pixels.
+    /// * `height` - Height of the format in pixels.
+    /// * `safe_area` - Margins which may be covered by the platform's interface.
+    pub fn new(name: &str, width: u32, height: u32, safe_area: SafeArea) -> SocialFormat {
+        SocialFormat {
+            name: name.to_string(),
+            width,
+            height,
+            safe_area,
+        }
+    }
+
+    /// The region inside the safe area, as `(x, y, width, height)`.
+    pub fn safe_rect(&self) -> (u32, u32, u32, u32) {
+        let area = &self.safe_area;
+        let x = area.left.min(self.width);
+        let y = area.top.min(self.height);
+        let width = self.width.saturating_sub(area.left + area.right);
+        let height = self.height.saturating_sub(area.top + area.bottom);
+        (x, y, width, height)
+    }
+
+    /// The width of the format divided by its height.
+    pub fn aspect_ratio(&self) -> f32 {
+        self.width as f32 / self.height as f32
+    }
+}
+
+/// Error returned when a social media format can't be found.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatError {
+    /// No format has been registered with this name.
+    Unknown(String),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FormatError::Unknown(name) => write!(f, "unknown social media format: {}", name),
+        }
+    }
+}
+
+impl Error for FormatError {}
+
+impl From<FormatError> for JsValue {
+    fn from(err: FormatError) -> JsValue {
+        JsValue::from_str(&err.to_string())
+    }
+}
+
+/// A collection of social media formats, which can be looked up by name.
+#[derive(Debug, Clone)]
+pub struct FormatRegistry {
+    formats: Vec<SocialFormat>,
+}
+
+impl FormatRegistry {
+    /// Create a registry containing the built-in formats.
+    pub fn new() -> FormatRegistry {
+        FormatRegistry {
+            formats: builtin_formats(),
+        }
+    }
+
+    /// Create a registry with no formats.
+    pub fn empty() -> FormatRegistry {
+        FormatRegistry { formats: vec![] }
+    }
+
+    /// Add a format to the registry, replacing any existing format with the same name.
+    pub fn add(&mut self, format: SocialFormat) {
+        match self.formats.iter_mut().find(|f| f.name == format.name) {
+            Some(existing) => *existing = format,
+            None => self.formats.push(format),
+        }
+    }
+
+    /// Look up a format by name.
+    pub fn get(&self, name: &str) -> Result<&SocialFormat, FormatError> {
+        self.formats
+            .iter()
+            .find(|f| f.name == name)
+            .ok_or_else(|| FormatError::Unknown(name.to_string()))
+    }
+
+    /// All formats in the registry, in the order they were added.
+    pub fn formats(&self) -> &[SocialFormat] {
+        &self.formats
+    }
+}
+
+impl Default for FormatRegistry {
+    fn default() -> FormatRegistry {
+        FormatRegistry::new()
+    }
+}
+
+/// Look up one of the built-in formats by name.
+pub fn social_format(name: &str) -> Result<SocialFormat, FormatError> {
+    builtin_formats()
+        .into_iter()
+        .find(|f| f.name == name)
+        .ok_or_else(|| FormatError::Unknown(name.to_string()))
+}
+
+/// The built-in social media formats.
+///
+/// Safe areas are approximate, as platforms change their interfaces over time.
+pub fn builtin_formats() -> Vec<SocialFormat> {
+    vec![
+        // Instagram
+        SocialFormat::new("instagram_post", 1080, 1080, SafeArea::none()),
+        SocialFormat::new("instagram_portrait", 1080, 1350, SafeArea::none()),
+        SocialFormat::new("instagram_story", 1080, 1920, SafeArea::new(250, 0, 250, 0)),
+        SocialFormat::new(
+            "instagram_reel",
+            1080,
+            1920,
+            SafeArea::new(220, 120, 420, 60),
+        ),
+        // Facebook
+        SocialFormat::new("fb_post", 940, 788, SafeArea::none()),
+        SocialFormat::new("fb_ad", 1200, 628, SafeArea::none()),
+        // The sides of cover photos are cropped on mobile.
+        SocialFormat::new("fb_cover", 820, 312, SafeArea::new(0, 90, 0, 90)),
+        // Twitter
+        SocialFormat::new("twitter_post", 1024, 512, SafeArea::none()),
+        SocialFormat::new("twitter_header", 1500, 500, SafeArea::new(60, 0, 60, 0)),
+        // LinkedIn
+        SocialFormat::new("linkedin_post", 1200, 627, SafeArea::none()),
+        SocialFormat::new("linkedin_banner", 1400, 425, SafeArea::none()),
+        // Pinterest
+        SocialFormat::new("pinterest", 735, 1102, SafeArea::none()),
+        // YouTube
+        // The video length is shown over the bottom-right of thumbnails.
+        SocialFormat::new("youtube_thumbnail", 1280, 720, SafeArea::new(0, 0, 80, 0)),
+        // Only the centre of channel banners is visible on every device.
+        SocialFormat::new(
+            "youtube_banner",
+            2560,
+            1440,
+            SafeArea::new(508, 507, 509, 507),
+        ),
+        // TikTok
+        SocialFormat::new("tiktok", 1080, 1920, SafeArea::new(130, 140, 480, 60)),
+        // Open Graph link previews, used by most sites and messaging apps.
+        SocialFormat::new("open_graph", 1200, 630, SafeArea::none()),
+        // Mastodon
+        SocialFormat::new("mastodon_post", 1200, 675, SafeArea::none()),
+        SocialFormat::new("mastodon_header", 1500, 500, SafeArea::none()),
+    ]
+}
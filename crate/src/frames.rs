@@ -0,0 +1,282 @@
+//! Frames for photos, such as plain borders, polaroids and film strips, along with drop shadows and rotation.
+//!
+//! Frames are sized relative to the photo, so the same style looks alike at any size.
+//!
+//! ### Example
+//! ```ignore
+//! let framed = frame_image(&photo, &FrameStyle::Polaroid(Rgba::new(255, 255, 255, 255)), Some("Summer"));
+//! let tilted = rotate(&framed, -4.0);
+//! let (shadowed, padding) = drop_shadow(&tilted, &Shadow::default());
+//! ```
+
+use crate::raster::{self, Quality};
+use crate::text::{self, TextAlign};
+use crate::{blend, Rgb, Rgba};
+use image::{DynamicImage, GenericImageView, ImageBuffer};
+use imageproc::geometric_transformations::{rotate_about_center, Interpolation};
+
+/// Style of frame drawn around a photo.
+#[derive(Debug, Clone, Copy)]
+pub enum FrameStyle {
+    /// No frame.
+    None,
+    /// A border of the same width on every side, given as a fraction of the photo's width.
+    Border(f32, Rgba),
+    /// An instant photo, with a deeper border along the bottom for a handwritten caption.
+    Polaroid(Rgba),
+    /// A frame of film, with sprocket holes along the top and bottom edges.
+    FilmStrip,
+}
+
+impl FrameStyle {
+    /// Width of the frame on each side, as fractions of the photo's width: `(top, right, bottom, left)`.
+    pub fn borders(&self) -> (f32, f32, f32, f32) {
+        match *self {
+            FrameStyle::None => (0.0, 0.0, 0.0, 0.0),
+            FrameStyle::Border(width, _) => (width, width, width, width),
+            FrameStyle::Polaroid(_) => (0.06, 0.06, 0.26, 0.06),
+            FrameStyle::FilmStrip => (0.16, 0.03, 0.16, 0.03),
+        }
+    }
+}
+
+/// A soft shadow cast by an image onto whatever is beneath it.
+#[derive(Debug, Clone, Copy)]
+pub struct Shadow {
+    /// Horizontal distance from the image to its shadow, in pixels.
+    pub offset_x: i32,
+    /// Vertical distance from the image to its shadow, in pixels.
+    pub offset_y: i32,
+    /// Standard deviation of the blur applied to the shadow, in pixels.
+    pub blur: f32,
+    pub color: Rgba,
+}
+
+impl Default for Shadow {
+    fn default() -> Shadow {
+        Shadow {
+            offset_x: 4,
+            offset_y: 8,
+            blur: 8.0,
+            color: Rgba::new(0, 0, 0, 110),
+        }
+    }
+}
+
+// Colour of the film around film strip frames.
+const FILM: Rgba = Rgba {
+    r: 24,
+    g: 22,
+    b: 20,
+    a: 255,
+};
+
+/// Draw a frame around a photo, returning a new, larger image.
+///
+/// ### Arguments
+/// * `img` - The photo to be framed.
+/// * `style` - Style of frame.
+/// * `caption` - Text written in the border beneath a polaroid. Ignored by other styles.
+pub fn frame_image(img: &DynamicImage, style: &FrameStyle, caption: Option<&str>) -> DynamicImage {
+    let (width, height) = img.dimensions();
+    let (top, right, bottom, left) = style.borders();
+    let border = |fraction: f32| (fraction * width as f32).round() as u32;
+    let (top, right, bottom, left) = (border(top), border(right), border(bottom), border(left));
+
+    let background = match *style {
+        FrameStyle::None => Rgba::new(0, 0, 0, 0),
+        FrameStyle::Border(_, color) | FrameStyle::Polaroid(color) => color,
+        FrameStyle::FilmStrip => FILM,
+    };
+    let frame_width = width + left + right;
+    let frame_height = height + top + bottom;
+    let mut framed = DynamicImage::ImageRgba8(ImageBuffer::from_pixel(
+        frame_width,
+        frame_height,
+        background.into(),
+    ));
+    blend::composite(&mut framed, img, left as i32, top as i32, 1.0);
+
+    match *style {
+        FrameStyle::Polaroid(_) => {
+            if let Some(caption) = caption {
+                // Write the caption across the bottom border, in a handwritten style.
+                let padding = left;
+                let area_height = bottom.saturating_sub(padding);
+                let ink = Rgb {
+                    r: 40,
+                    g: 40,
+                    b: 48,
+                };
+                let font_size = text::fit_font_size(
+                    caption,
+                    "MrDafoe-Regular",
+                    area_height as f32 * 0.8,
+                    width,
+                    area_height,
+                );
+                let (_, line_height) = text::measure_text(caption, "MrDafoe-Regular", font_size);
+                let text_y = top + height + (bottom.saturating_sub(line_height)) / 2;
+                text::draw_text_block(
+                    &mut framed,
+                    caption,
+                    left,
+                    text_y,
+                    width,
+                    "MrDafoe-Regular",
+                    font_size,
+                    &ink,
+                    TextAlign::Centre,
+                );
+            }
+        }
+        FrameStyle::FilmStrip => {
+            draw_sprocket_holes(&mut framed, 0, 0, frame_width, top);
+            draw_sprocket_holes(&mut framed, 0, top + height, frame_width, bottom);
+        }
+        _ => {}
+    }
+    framed
+}
+
+/// Draw a row of film sprocket holes, evenly spaced along a strip of film.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `x` - X-coordinate of the left edge of the strip.
+/// * `y` - Y-coordinate of the top edge of the strip.
+/// * `width` - Width of the strip.
+/// * `height` - Height of the strip. The holes are sized to fit within it.
+pub fn draw_sprocket_holes(img: &mut DynamicImage, x: u32, y: u32, width: u32, height: u32) {
+    let hole_height = height as f32 * 0.45;
+    let hole_width = hole_height * 0.75;
+    let pitch = hole_width * 2.0;
+    let count = (width as f32 / pitch).floor().max(1.0);
+    // Centre the row of holes along the strip.
+    let start = x as f32 + (width as f32 - count * pitch) / 2.0 + (pitch - hole_width) / 2.0;
+    let hole_y = y as f32 + (height as f32 - hole_height) / 2.0;
+
+    let holes: Vec<_> = (0..count as u32)
+        .map(|i| {
+            raster::rounded_rect(
+                start + i as f32 * pitch,
+                hole_y,
+                hole_width,
+                hole_height,
+                hole_width * 0.2,
+            )
+        })
+        .collect();
+    let color = Rgba::new(236, 232, 220, 255);
+    raster::fill_contours(img, &holes, color.into(), Quality::AntiAliased);
+}
+
+/// Rotate an image by an angle in degrees, clockwise, returning a new image which is large enough
+/// to hold all of the rotated image. Corners uncovered by the rotation are transparent.
+///
+/// ### Arguments
+/// * `img` - The image to be rotated.
+/// * `degrees` - Angle to rotate by, clockwise.
+pub fn rotate(img: &DynamicImage, degrees: f32) -> DynamicImage {
+    let (width, height) = img.dimensions();
+    let (bounds_width, bounds_height) = rotated_size(width as f32, height as f32, degrees);
+    let (bounds_width, bounds_height) = (
+        (bounds_width.ceil() as u32).max(width),
+        (bounds_height.ceil() as u32).max(height),
+    );
+
+    // Place the image in the middle of a canvas which fits it at any rotation, then rotate the canvas.
+    // Colours are premultiplied by alpha, so that edges blend with transparency rather than black.
+    let mut canvas =
+        ImageBuffer::from_pixel(bounds_width, bounds_height, image::Rgba([0, 0, 0, 0]));
+    let (offset_x, offset_y) = ((bounds_width - width) / 2, (bounds_height - height) / 2);
+    for (x, y, pixel) in img.to_rgba8().enumerate_pixels() {
+        let alpha = pixel[3] as u32;
+        let premultiply = |channel: u8| ((channel as u32 * alpha + 127) / 255) as u8;
+        canvas.put_pixel(
+            x + offset_x,
+            y + offset_y,
+            image::Rgba([
+                premultiply(pixel[0]),
+                premultiply(pixel[1]),
+                premultiply(pixel[2]),
+                pixel[3],
+            ]),
+        );
+    }
+
+    let mut rotated = rotate_about_center(
+        &canvas,
+        degrees.to_radians(),
+        Interpolation::Bilinear,
+        image::Rgba([0, 0, 0, 0]),
+    );
+    for pixel in rotated.pixels_mut() {
+        let alpha = pixel[3] as u32;
+        for channel in pixel.0.iter_mut().take(3) {
+            if let Some(value) = (*channel as u32 * 255 + alpha / 2).checked_div(alpha) {
+                *channel = value.min(255) as u8;
+            }
+        }
+    }
+    DynamicImage::ImageRgba8(rotated)
+}
+
+/// The size of the box needed to hold a `width` by `height` rectangle rotated by `degrees`.
+pub fn rotated_size(width: f32, height: f32, degrees: f32) -> (f32, f32) {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let (sin, cos) = (sin.abs(), cos.abs());
+    (width * cos + height * sin, width * sin + height * cos)
+}
+
+/// Draw a soft shadow beneath an image, following the shape of its opaque pixels.
+///
+/// Returns a new image with room around the edges for the shadow, along with the width of
+/// that padding; the original image sits `padding` pixels in from the top-left corner.
+///
+/// ### Arguments
+/// * `img` - The image casting the shadow.
+/// * `shadow` - Offset, blur and colour of the shadow.
+pub fn drop_shadow(img: &DynamicImage, shadow: &Shadow) -> (DynamicImage, u32) {
+    let (width, height) = img.dimensions();
+    let padding = shadow_padding(shadow);
+    let (canvas_width, canvas_height) = (width + 2 * padding, height + 2 * padding);
+
+    // The shadow is the image's alpha channel in the shadow colour, blurred and offset.
+    let mut silhouette = ImageBuffer::from_pixel(canvas_width, canvas_height, image::Luma([0u8]));
+    for (x, y, pixel) in img.to_rgba8().enumerate_pixels() {
+        let sx = (x + padding) as i64 + shadow.offset_x as i64;
+        let sy = (y + padding) as i64 + shadow.offset_y as i64;
+        if sx >= 0 && sy >= 0 && sx < canvas_width as i64 && sy < canvas_height as i64 {
+            silhouette.put_pixel(sx as u32, sy as u32, image::Luma([pixel[3]]));
+        }
+    }
+    let silhouette = if shadow.blur > 0.0 {
+        image::imageops::blur(&silhouette, shadow.blur)
+    } else {
+        silhouette
+    };
+
+    let color = shadow.color;
+    let mut shadowed =
+        DynamicImage::ImageRgba8(ImageBuffer::from_fn(canvas_width, canvas_height, |x, y| {
+            let coverage = silhouette.get_pixel(x, y)[0] as u32;
+            image::Rgba([
+                color.r,
+                color.g,
+                color.b,
+                (coverage * color.a as u32 / 255) as u8,
+            ])
+        }));
+    blend::composite(&mut shadowed, img, padding as i32, padding as i32, 1.0);
+    (shadowed, padding)
+}
+
+/// The padding `drop_shadow` adds around each edge of an image to make room for a shadow.
+pub fn shadow_padding(shadow: &Shadow) -> u32 {
+    let offset = shadow
+        .offset_x
+        .unsigned_abs()
+        .max(shadow.offset_y.unsigned_abs());
+    (shadow.blur * 3.0).ceil() as u32 + offset
+}
@@ -0,0 +1,633 @@
+//! Infographics laid out from lists of items: timelines, numbered steps, and comparison tables.
+//!
+//! Each infographic is drawn within a rectangle of an image, with its items spread out to fill it.
+//! Colours, fonts and the gradient used for accents are shared through an `InfographicStyle`.
+//!
+//! ### Example
+//! ```ignore
+//! let steps = vec![
+//!     Step::new("Pick a template", "Start from one of the built-in designs."),
+//!     Step::new("Add your photo", "It's cropped to fit each format."),
+//!     Step::new("Publish", "Export every size at once."),
+//! ];
+//! draw_steps(&mut img, &steps, &InfographicStyle::default(), 40, 40, 1000, 600);
+//! ```
+
+use crate::elements::create_gradient_preset;
+use crate::mask::{clip_image, Mask};
+use crate::raster::{self, Quality};
+use crate::resize::{self, Fit};
+use crate::text::{self, TextAlign};
+use crate::{blend, Rgb, Rgba};
+use image::DynamicImage;
+
+/// Draw a horizontal timeline: a line across the middle of the area, with a marker for each item
+/// spaced evenly along it. Items alternate above and below the line, so each has room for its text.
+///
+/// ### Arguments
+/// * `img` - Image to draw the timeline onto.
+/// * `items` - Items of the timeline, in order.
+/// * `style` - Colours and fonts of the timeline.
+/// * `x` - X-coordinate of the left edge of the timeline.
+/// * `y` - Y-coordinate of the top edge of the timeline.
+/// * `width` - Width of the timeline.
+/// * `height` - Height of the timeline.
+pub fn draw_horizontal_timeline(
+    img: &mut DynamicImage,
+    items: &[TimelineItem],
+    style: &InfographicStyle,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) {
+    if items.is_empty() {
+        return;
+    }
+    let (x, y, width, height) = (x as f32, y as f32, width as f32, height as f32);
+    let spacing = width / items.len() as f32;
+    let centre_y = y + height / 2.0;
+    let radius = style.marker_radius;
+    let thickness = (radius / 2.0).max(2.0);
+    draw_accent(
+        img,
+        style,
+        &raster::rounded_rect(
+            x,
+            centre_y - thickness / 2.0,
+            width,
+            thickness,
+            thickness / 2.0,
+        ),
+    );
+
+    // Items alternate sides, so each can be as wide as the space for two markers.
+    let text_width = (spacing * 2.0 - style.padding).min(width);
+    let text_height = height / 2.0 - radius - style.padding;
+    for (i, item) in items.iter().enumerate() {
+        let marker_x = x + spacing * (i as f32 + 0.5);
+        draw_marker(img, style, marker_x, centre_y);
+
+        let left = (marker_x - text_width / 2.0).clamp(x, x + width - text_width);
+        let block = TextBlock::new(item, style, text_width, text_height);
+        let top = if i % 2 == 0 {
+            centre_y - radius - style.padding - block.height
+        } else {
+            centre_y + radius + style.padding
+        };
+        block.draw(img, style, left, top, TextAlign::Centre);
+    }
+}
+
+/// Draw a vertical timeline: a line down the left of the area, with a marker for each item spaced
+/// evenly down it, and each item's text beside its marker.
+///
+/// ### Arguments
+/// * `img` - Image to draw the timeline onto.
+/// * `items` - Items of the timeline, in order.
+/// * `style` - Colours and fonts of the timeline.
+/// * `x` - X-coordinate of the left edge of the timeline.
+/// * `y` - Y-coordinate of the top edge of the timeline.
+/// * `width` - Width of the timeline.
+/// * `height` - Height of the timeline.
+pub fn draw_vertical_timeline(
+    img: &mut DynamicImage,
+    items: &[TimelineItem],
+    style: &InfographicStyle,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) {
+    if items.is_empty() {
+        return;
+    }
+    let (x, y, width, height) = (x as f32, y as f32, width as f32, height as f32);
+    let spacing = height / items.len() as f32;
+    let radius = style.marker_radius;
+    let thickness = (radius / 2.0).max(2.0);
+    let line_x = x + radius;
+    draw_accent(
+        img,
+        style,
+        &raster::rounded_rect(
+            line_x - thickness / 2.0,
+            y,
+            thickness,
+            height,
+            thickness / 2.0,
+        ),
+    );
+
+    let left = line_x + radius + style.padding;
+    let text_width = width - (left - x);
+    for (i, item) in items.iter().enumerate() {
+        // Each marker lines up with the first line of its item's text.
+        let top = y + spacing * i as f32;
+        let block = TextBlock::new(item, style, text_width, spacing - style.padding);
+        draw_marker(img, style, line_x, top + block.first_line / 2.0);
+        block.draw(img, style, left, top, TextAlign::Left);
+    }
+}
+
+/// Draw a numbered list of steps, such as "5 steps to a better post". Each step is drawn on a card,
+/// with its number in a badge, its icon if it has one, and its title and description.
+/// The cards are stacked down the area, sharing its height equally.
+///
+/// ### Arguments
+/// * `img` - Image to draw the steps onto.
+/// * `steps` - Steps, in order. They're numbered from 1.
+/// * `style` - Colours and fonts of the steps.
+/// * `x` - X-coordinate of the left edge of the list.
+/// * `y` - Y-coordinate of the top edge of the list.
+/// * `width` - Width of the list.
+/// * `height` - Height of the list.
+pub fn draw_steps(
+    img: &mut DynamicImage,
+    steps: &[Step],
+    style: &InfographicStyle,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) {
+    if steps.is_empty() {
+        return;
+    }
+    let (x, y, width, height) = (x as f32, y as f32, width as f32, height as f32);
+    let gap = style.padding / 2.0;
+    let card_height = (height - gap * (steps.len() - 1) as f32) / steps.len() as f32;
+    let badge = (card_height - style.padding * 2.0)
+        .min(style.title_size * 2.5)
+        .max(1.0);
+
+    for (i, step) in steps.iter().enumerate() {
+        let top = y + (card_height + gap) * i as f32;
+        let card = raster::rounded_rect(x, top, width, card_height, style.corner_radius);
+        raster::fill_contours(img, &[card], style.card_color.into(), Quality::AntiAliased);
+
+        // The number sits in a round badge, in the accent colour or gradient.
+        let badge_x = x + style.padding;
+        let badge_y = top + (card_height - badge) / 2.0;
+        let centre = (badge_x + badge / 2.0, badge_y + badge / 2.0);
+        draw_accent(img, style, &raster::circle(centre.0, centre.1, badge / 2.0));
+        draw_centred_text(
+            img,
+            &(i + 1).to_string(),
+            &style.title_font,
+            badge * 0.5,
+            &style.badge_text_color,
+            centre,
+        );
+
+        let mut left = badge_x + badge + style.padding;
+        if let Some(icon) = &step.icon {
+            let size = badge.round() as u32;
+            let icon = resize::resize_to_fit(icon, size, size, Fit::Contain(Rgba::new(0, 0, 0, 0)));
+            blend::composite(img, &icon, left.round() as i32, badge_y.round() as i32, 1.0);
+            left += badge + style.padding;
+        }
+
+        let text_width = x + width - style.padding - left;
+        let item = TimelineItem::new("", &step.title, &step.description);
+        let block = TextBlock::new(&item, style, text_width, card_height - style.padding);
+        block.draw(
+            img,
+            style,
+            left,
+            top + (card_height - block.height) / 2.0,
+            TextAlign::Left,
+        );
+    }
+}
+
+/// Draw a comparison table, with the options being compared across the top, and a row for each
+/// feature beneath, showing a tick or cross, or some text, for each option.
+///
+/// ### Arguments
+/// * `img` - Image to draw the table onto.
+/// * `comparison` - Options and features to compare.
+/// * `style` - Colours and fonts of the table.
+/// * `x` - X-coordinate of the left edge of the table.
+/// * `y` - Y-coordinate of the top edge of the table.
+/// * `width` - Width of the table.
+/// * `height` - Height of the table.
+pub fn draw_comparison(
+    img: &mut DynamicImage,
+    comparison: &Comparison,
+    style: &InfographicStyle,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) {
+    let (x, y, width, height) = (x as f32, y as f32, width as f32, height as f32);
+    let row_height = height / (comparison.features.len() + 1) as f32;
+    // Feature names get a wider column than the options, which only hold short values.
+    let options = comparison.options.len().max(1) as f32;
+    let label_width = width * 0.4;
+    let column_width = (width - label_width) / options;
+    let column_centre = |i: usize| x + label_width + column_width * (i as f32 + 0.5);
+
+    let header = raster::rounded_rect(x, y, width, row_height, style.corner_radius);
+    draw_accent(img, style, &header);
+    for (i, option) in comparison.options.iter().enumerate() {
+        draw_centred_text(
+            img,
+            option,
+            &style.title_font,
+            style.title_size,
+            &style.badge_text_color,
+            (column_centre(i), y + row_height / 2.0),
+        );
+    }
+
+    for (row, feature) in comparison.features.iter().enumerate() {
+        let top = y + row_height * (row + 1) as f32;
+        // Every other row is shaded, to help the eye follow it across.
+        if row % 2 == 0 {
+            let shade = raster::rect(x, top, width, row_height);
+            raster::fill_contours(img, &[shade], style.card_color.into(), Quality::AntiAliased);
+        }
+        let centre_y = top + row_height / 2.0;
+        let (_, line_height) = text::measure_text(&feature.name, &style.font, style.text_size);
+        text::draw_text_block(
+            img,
+            &feature.name,
+            (x + style.padding).round() as u32,
+            (centre_y - line_height as f32 / 2.0).max(0.0).round() as u32,
+            (label_width - style.padding * 2.0).max(1.0) as u32,
+            &style.font,
+            style.text_size,
+            &style.text_color,
+            TextAlign::Left,
+        );
+
+        let mark_size = (row_height * 0.4).min(style.text_size * 1.2);
+        for (i, value) in feature
+            .values
+            .iter()
+            .enumerate()
+            .take(comparison.options.len())
+        {
+            let centre = (column_centre(i), centre_y);
+            match value {
+                Mark::Yes => draw_tick(img, centre, mark_size, &style.yes_color),
+                Mark::No => draw_cross(img, centre, mark_size, &style.no_color),
+                Mark::Text(value) => draw_centred_text(
+                    img,
+                    value,
+                    &style.font,
+                    style.text_size,
+                    &style.text_color,
+                    centre,
+                ),
+            }
+        }
+    }
+}
+
+// Fill a shape with the style's gradient, running from left to right across the shape, or with
+// its accent colour if it has no gradient.
+fn draw_accent(img: &mut DynamicImage, style: &InfographicStyle, contour: &raster::Contour) {
+    let gradient = match &style.gradient {
+        Some(gradient) => gradient,
+        None => {
+            let contours = [contour.clone()];
+            let color = Rgba::from(&style.accent);
+            raster::fill_contours(img, &contours, color.into(), Quality::AntiAliased);
+            return;
+        }
+    };
+    let (left, top, right, bottom) = contour.iter().fold(
+        (f32::MAX, f32::MAX, f32::MIN, f32::MIN),
+        |(left, top, right, bottom), (x, y)| {
+            (left.min(*x), top.min(*y), right.max(*x), bottom.max(*y))
+        },
+    );
+    let (left, top) = (left.floor(), top.floor());
+    let (width, height) = ((right - left).ceil() as u32, (bottom - top).ceil() as u32);
+    if width == 0 || height == 0 {
+        return;
+    }
+    // The shape is moved to the corner of the gradient, so it can be cut out of it.
+    let shape: raster::Contour = contour.iter().map(|(x, y)| (x - left, y - top)).collect();
+    let mut layer = create_gradient_preset(width, height, gradient);
+    clip_image(
+        &mut layer,
+        &Mask::from_contours(width, height, &[shape], Quality::AntiAliased),
+    );
+    blend::composite(img, &layer, left as i32, top as i32, 1.0);
+}
+
+// Draw a timeline marker: a dot in the accent colour with a ring of the background colour around it.
+fn draw_marker(img: &mut DynamicImage, style: &InfographicStyle, x: f32, y: f32) {
+    let radius = style.marker_radius;
+    let ring = raster::circle(x, y, radius);
+    raster::fill_contours(img, &[ring], style.card_color.into(), Quality::AntiAliased);
+    draw_accent(img, style, &raster::circle(x, y, radius * 0.6));
+}
+
+// Draw a single line of text centred on a point.
+fn draw_centred_text(
+    img: &mut DynamicImage,
+    label: &str,
+    font: &str,
+    font_size: f32,
+    color: &Rgb,
+    centre: (f32, f32),
+) {
+    let (width, height) = text::measure_text(label, font, font_size);
+    text::draw_text_block(
+        img,
+        label,
+        (centre.0 - width as f32 / 2.0).max(0.0).round() as u32,
+        (centre.1 - height as f32 / 2.0).max(0.0).round() as u32,
+        width + 1,
+        font,
+        font_size,
+        color,
+        TextAlign::Centre,
+    );
+}
+
+// Draw a tick mark centred on a point.
+fn draw_tick(img: &mut DynamicImage, centre: (f32, f32), size: f32, color: &Rgb) {
+    let (x, y, half) = (centre.0, centre.1, size / 2.0);
+    let points = [
+        (x - half, y),
+        (x - half * 0.3, y + half * 0.7),
+        (x + half, y - half * 0.7),
+    ];
+    let tick = raster::polyline(&points, (size / 6.0).max(2.0));
+    raster::fill_contours(img, &tick, Rgba::from(color).into(), Quality::AntiAliased);
+}
+
+// Draw a cross centred on a point.
+fn draw_cross(img: &mut DynamicImage, centre: (f32, f32), size: f32, color: &Rgb) {
+    let (x, y, half) = (centre.0, centre.1, size * 0.4);
+    let thickness = (size / 6.0).max(2.0);
+    let cross = [
+        raster::line((x - half, y - half), (x + half, y + half), thickness),
+        raster::line((x - half, y + half), (x + half, y - half), thickness),
+    ];
+    raster::fill_contours(img, &cross, Rgba::from(color).into(), Quality::AntiAliased);
+}
+
+// The label, title and description of an item, wrapped to a width, with the description's font
+// shrunk if needed so that the whole block fits within a height.
+struct TextBlock<'a> {
+    item: &'a TimelineItem,
+    width: f32,
+    description_size: f32,
+    // Height of the whole block, and of its first line.
+    height: f32,
+    first_line: f32,
+}
+
+impl<'a> TextBlock<'a> {
+    fn new(
+        item: &'a TimelineItem,
+        style: &InfographicStyle,
+        width: f32,
+        max_height: f32,
+    ) -> TextBlock<'a> {
+        let width = width.max(1.0);
+        let block_height = |text: &str, font: &str, size: f32| {
+            if text.is_empty() {
+                return 0.0;
+            }
+            let lines = text::wrap_text(text, font, size, width as u32).len();
+            (text::measure_text(text, font, size).1 as usize * lines) as f32
+        };
+        let label = block_height(&item.label, &style.title_font, style.text_size);
+        let title = block_height(&item.title, &style.title_font, style.title_size);
+        let remaining = (max_height - label - title).max(1.0);
+        let description_size = match item.description.is_empty() {
+            true => style.text_size,
+            false => text::fit_font_size(
+                &item.description,
+                &style.font,
+                style.text_size,
+                width as u32,
+                remaining as u32,
+            ),
+        };
+        let description = block_height(&item.description, &style.font, description_size);
+        let first_line = match (label > 0.0, title > 0.0) {
+            (true, _) => text::measure_text("0", &style.title_font, style.text_size).1,
+            (false, true) => text::measure_text("0", &style.title_font, style.title_size).1,
+            _ => text::measure_text("0", &style.font, description_size).1,
+        } as f32;
+        TextBlock {
+            item,
+            width,
+            description_size,
+            height: label + title + description,
+            first_line,
+        }
+    }
+
+    // Draw the block with its top-left corner at `x`, `y`.
+    fn draw(
+        &self,
+        img: &mut DynamicImage,
+        style: &InfographicStyle,
+        x: f32,
+        y: f32,
+        align: TextAlign,
+    ) {
+        let mut top = y.max(0.0).round() as u32;
+        let left = x.max(0.0).round() as u32;
+        let width = self.width as u32;
+        let parts = [
+            (
+                &self.item.label,
+                &style.title_font,
+                style.text_size,
+                &style.accent,
+            ),
+            (
+                &self.item.title,
+                &style.title_font,
+                style.title_size,
+                &style.text_color,
+            ),
+            (
+                &self.item.description,
+                &style.font,
+                self.description_size,
+                &style.muted_color,
+            ),
+        ];
+        for (text, font, size, color) in parts.iter() {
+            if !text.is_empty() {
+                top +=
+                    text::draw_text_block(img, text, left, top, width, font, *size, color, align);
+            }
+        }
+    }
+}
+
+/// Colours, fonts and spacing shared by infographics.
+#[derive(Debug, Clone)]
+pub struct InfographicStyle {
+    /// Font for descriptions and other body text, such as "Roboto-Regular".
+    pub font: String,
+    /// Font for titles, numbers and labels, such as "Roboto-Bold".
+    pub title_font: String,
+    pub title_size: f32,
+    pub text_size: f32,
+    pub text_color: Rgb,
+    /// Colour of descriptions, which are usually less prominent than titles.
+    pub muted_color: Rgb,
+    /// Colour of timelines, markers, badges and headers, and of items' labels.
+    pub accent: Rgb,
+    /// Name of a gradient preset to fill timelines, markers, badges and headers with instead of the
+    /// accent colour, such as "pinkblue" or "lemongrass".
+    pub gradient: Option<String>,
+    /// Colour of text on badges and headers.
+    pub badge_text_color: Rgb,
+    /// Colour of the cards behind steps, shaded rows of tables, and the rings around markers.
+    pub card_color: Rgba,
+    /// Colour of ticks in comparison tables.
+    pub yes_color: Rgb,
+    /// Colour of crosses in comparison tables.
+    pub no_color: Rgb,
+    /// Space around and between items, in pixels.
+    pub padding: f32,
+    pub corner_radius: f32,
+    /// Radius of the markers on timelines, in pixels.
+    pub marker_radius: f32,
+}
+
+impl Default for InfographicStyle {
+    fn default() -> InfographicStyle {
+        InfographicStyle {
+            font: "Roboto-Regular".to_string(),
+            title_font: "Roboto-Bold".to_string(),
+            title_size: 32.0,
+            text_size: 24.0,
+            text_color: Rgb {
+                r: 240,
+                g: 240,
+                b: 240,
+            },
+            muted_color: Rgb {
+                r: 170,
+                g: 170,
+                b: 175,
+            },
+            accent: Rgb {
+                r: 52,
+                g: 148,
+                b: 230,
+            },
+            gradient: None,
+            badge_text_color: Rgb {
+                r: 255,
+                g: 255,
+                b: 255,
+            },
+            card_color: Rgba::new(255, 255, 255, 20),
+            yes_color: Rgb {
+                r: 89,
+                g: 191,
+                b: 110,
+            },
+            no_color: Rgb {
+                r: 225,
+                g: 87,
+                b: 89,
+            },
+            padding: 24.0,
+            corner_radius: 16.0,
+            marker_radius: 14.0,
+        }
+    }
+}
+
+/// An item on a timeline, such as a milestone of a roadmap.
+#[derive(Debug, Clone)]
+pub struct TimelineItem {
+    /// Short label, such as a date, drawn in the accent colour above the title.
+    pub label: String,
+    pub title: String,
+    pub description: String,
+}
+
+impl TimelineItem {
+    /// Create a new timeline item. Any part can be left empty.
+    pub fn new(label: &str, title: &str, description: &str) -> TimelineItem {
+        TimelineItem {
+            label: label.to_string(),
+            title: title.to_string(),
+            description: description.to_string(),
+        }
+    }
+}
+
+/// A step of a numbered list of steps.
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub title: String,
+    pub description: String,
+    /// Icon drawn beside the step's number. It's scaled to fit the badge the number sits in.
+    pub icon: Option<DynamicImage>,
+}
+
+impl Step {
+    /// Create a new step, without an icon.
+    pub fn new(title: &str, description: &str) -> Step {
+        Step {
+            title: title.to_string(),
+            description: description.to_string(),
+            icon: None,
+        }
+    }
+}
+
+/// What a comparison table shows for a feature of an option.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mark {
+    /// A tick, for a feature the option has.
+    Yes,
+    /// A cross, for a feature the option doesn't have.
+    No,
+    /// A short piece of text, such as "10 GB".
+    Text(String),
+}
+
+/// A row of a comparison table: a feature, and what each option offers for it.
+#[derive(Debug, Clone)]
+pub struct Feature {
+    pub name: String,
+    /// One mark for each option, in the same order as the options.
+    pub values: Vec<Mark>,
+}
+
+/// Options compared feature by feature, such as pricing plans.
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    /// Names of the options, across the top of the table.
+    pub options: Vec<String>,
+    pub features: Vec<Feature>,
+}
+
+impl Comparison {
+    /// Create a new comparison of options, without any features.
+    pub fn new(options: &[&str]) -> Comparison {
+        Comparison {
+            options: options.iter().map(|option| option.to_string()).collect(),
+            features: vec![],
+        }
+    }
+
+    /// Add a feature, with a mark for each option.
+    pub fn add_feature(&mut self, name: &str, values: Vec<Mark>) {
+        self.features.push(Feature {
+            name: name.to_string(),
+            values,
+        });
+    }
+}
@@ -0,0 +1,363 @@
+//! Responsive designs, which are re-laid out for each social media format rather than rescaled.
+//!
+//! Each element of a design is positioned by a `Constraint` relative to the safe area of the
+//! format being rendered, and a `Layout` can give different constraints for landscape, square
+//! and portrait formats. Text is wrapped and sized to fit its box, and images are cropped to fill theirs.
+//!
+//! ### Example
+//! ```ignore
+//! let mut design = Design::new(Background::Image(photo, CropStrategy::Saliency));
+//! design.add_text(
+//!     "Summer Sale",
+//!     "BebasKai",
+//!     0.25,
+//!     &white,
+//!     Layout::new(
+//!         Constraint::new(Anchor::Left, 0.0, 0.0, 0.5, 0.6),
+//!         Constraint::new(Anchor::Top, 0.0, 0.0, 1.0, 0.4),
+//!         Constraint::new(Anchor::Top, 0.0, 0.1, 1.0, 0.3),
+//!     ),
+//! );
+//! let registry = FormatRegistry::new();
+//! let graphics = design.render_all(registry.formats());
+//! ```
+
+use crate::crop::CropStrategy;
+use crate::elements::create_gradient_preset;
+use crate::formats::SocialFormat;
+use crate::raster::{self, Quality};
+use crate::resize::{self, Fit, ResizeOptions};
+use crate::text::{self, TextAlign};
+use crate::{blend, new_with_background, Rgb, Rgba};
+use image::DynamicImage;
+use rusttype::Font;
+
+/// The shape of a format, which decides which constraint of a `Layout` is used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Orientation {
+    Landscape,
+    Square,
+    Portrait,
+}
+
+impl Orientation {
+    /// The orientation of a format. Formats within 10% of square are treated as square.
+    pub fn of(width: u32, height: u32) -> Orientation {
+        let aspect = width as f32 / height.max(1) as f32;
+        if aspect > 1.1 {
+            Orientation::Landscape
+        } else if aspect < 1.0 / 1.1 {
+            Orientation::Portrait
+        } else {
+            Orientation::Square
+        }
+    }
+}
+
+/// The point of the safe area which an element is positioned relative to.
+///
+/// The anchor also aligns text, so that text anchored to the right is right-aligned.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Centre,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    // Position along each axis, where 0.0 is the start, 0.5 the centre and 1.0 the end.
+    fn factors(self) -> (f32, f32) {
+        match self {
+            Anchor::TopLeft => (0.0, 0.0),
+            Anchor::Top => (0.5, 0.0),
+            Anchor::TopRight => (1.0, 0.0),
+            Anchor::Left => (0.0, 0.5),
+            Anchor::Centre => (0.5, 0.5),
+            Anchor::Right => (1.0, 0.5),
+            Anchor::BottomLeft => (0.0, 1.0),
+            Anchor::Bottom => (0.5, 1.0),
+            Anchor::BottomRight => (1.0, 1.0),
+        }
+    }
+}
+
+/// The box an element occupies, relative to the safe area of a format.
+///
+/// All values are fractions of the safe area's width or height, so that the same constraint
+/// works for formats of any size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Constraint {
+    /// Point of the safe area the box is attached to.
+    pub anchor: Anchor,
+    /// Distance of the box from its anchor, moving inwards, as a fraction of the safe area's width.
+    pub x: f32,
+    /// Distance of the box from its anchor, moving inwards, as a fraction of the safe area's height.
+    pub y: f32,
+    /// Width of the box, as a fraction of the safe area's width.
+    pub width: f32,
+    /// Height of the box, as a fraction of the safe area's height.
+    pub height: f32,
+}
+
+impl Constraint {
+    /// Create a new constraint.
+    pub fn new(anchor: Anchor, x: f32, y: f32, width: f32, height: f32) -> Constraint {
+        Constraint {
+            anchor,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// A constraint filling the whole safe area.
+    pub fn fill() -> Constraint {
+        Constraint::new(Anchor::TopLeft, 0.0, 0.0, 1.0, 1.0)
+    }
+
+    /// Work out the box in pixels, as `(x, y, width, height)`, for a safe area given in the same form.
+    pub fn resolve(&self, area: (u32, u32, u32, u32)) -> (f32, f32, f32, f32) {
+        let (area_x, area_y) = (area.0 as f32, area.1 as f32);
+        let (area_width, area_height) = (area.2 as f32, area.3 as f32);
+        let width = self.width * area_width;
+        let height = self.height * area_height;
+        let (fx, fy) = self.anchor.factors();
+
+        // Offsets move the box away from the edge it's anchored to, towards the centre.
+        let direction = |factor: f32| if factor > 0.5 { -1.0 } else { 1.0 };
+        let x = area_x + (area_width - width) * fx + direction(fx) * self.x * area_width;
+        let y = area_y + (area_height - height) * fy + direction(fy) * self.y * area_height;
+        (x, y, width, height)
+    }
+}
+
+/// Constraints for an element in landscape, square and portrait formats.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Layout {
+    pub landscape: Constraint,
+    pub square: Constraint,
+    pub portrait: Constraint,
+}
+
+impl Layout {
+    /// Create a layout with a constraint for each orientation.
+    pub fn new(landscape: Constraint, square: Constraint, portrait: Constraint) -> Layout {
+        Layout {
+            landscape,
+            square,
+            portrait,
+        }
+    }
+
+    /// Create a layout which uses the same constraint for every orientation.
+    pub fn uniform(constraint: Constraint) -> Layout {
+        Layout::new(constraint, constraint, constraint)
+    }
+
+    /// The constraint used for an orientation.
+    pub fn get(&self, orientation: Orientation) -> Constraint {
+        match orientation {
+            Orientation::Landscape => self.landscape,
+            Orientation::Square => self.square,
+            Orientation::Portrait => self.portrait,
+        }
+    }
+}
+
+/// The background of a design, which fills the whole format including the area outside the safe area.
+#[derive(Debug, Clone)]
+pub enum Background {
+    /// A solid colour.
+    Color(Rgb),
+    /// One of the preset gradients, such as "pinkblue" (see `elements::create_gradient_preset`).
+    Gradient(String),
+    /// An image, cropped to each format using the crop strategy.
+    Image(DynamicImage, CropStrategy),
+}
+
+#[derive(Debug, Clone)]
+enum Element {
+    Text {
+        text: String,
+        font: String,
+        max_font_size: f32,
+        color: Rgb,
+    },
+    Image {
+        img: DynamicImage,
+        strategy: CropStrategy,
+    },
+    Rect {
+        color: Rgba,
+        corner_radius: f32,
+    },
+}
+
+/// A design which can be rendered to any social media format.
+///
+/// Elements are drawn in the order they were added, on top of the background.
+#[derive(Debug, Clone)]
+pub struct Design {
+    background: Background,
+    elements: Vec<(Element, Layout)>,
+}
+
+impl Design {
+    /// Create a new design with a background and no elements.
+    pub fn new(background: Background) -> Design {
+        Design {
+            background,
+            elements: vec![],
+        }
+    }
+
+    /// Add text, which is wrapped and shrunk until it fits within its box, then aligned according to its anchor.
+    ///
+    /// ### Arguments
+    /// * `text` - Text string to be drawn.
+    /// * `font` - Font name, such as "BebasKai" or "Roboto-Bold".
+    /// * `max_font_size` - Largest font size, as a fraction of the shorter side of the safe area.
+    /// * `color` - Rgb text color.
+    /// * `layout` - Position of the text's box in each orientation.
+    pub fn add_text(
+        &mut self,
+        text: &str,
+        font: &str,
+        max_font_size: f32,
+        color: &Rgb,
+        layout: Layout,
+    ) {
+        let element = Element::Text {
+            text: text.to_string(),
+            font: font.to_string(),
+            max_font_size,
+            color: color.clone(),
+        };
+        self.elements.push((element, layout));
+    }
+
+    /// Add an image, which is cropped to fill its box using the crop strategy.
+    pub fn add_image(&mut self, img: DynamicImage, strategy: CropStrategy, layout: Layout) {
+        self.elements
+            .push((Element::Image { img, strategy }, layout));
+    }
+
+    /// Add a rectangle, such as a panel behind some text.
+    ///
+    /// ### Arguments
+    /// * `color` - Colour of the rectangle, which may be translucent.
+    /// * `corner_radius` - Radius of the corners, as a fraction of the rectangle's shorter side (0.5 gives fully rounded ends).
+    /// * `layout` - Position of the rectangle in each orientation.
+    pub fn add_rect(&mut self, color: impl Into<Rgba>, corner_radius: f32, layout: Layout) {
+        let element = Element::Rect {
+            color: color.into(),
+            corner_radius,
+        };
+        self.elements.push((element, layout));
+    }
+
+    /// Render the design for a format, laying out each element within the format's safe area.
+    pub fn render(&self, format: &SocialFormat) -> DynamicImage {
+        let (width, height) = (format.width, format.height);
+        let mut img = match &self.background {
+            Background::Color(color) => new_with_background(width, height, color),
+            Background::Gradient(name) => create_gradient_preset(width, height, name),
+            Background::Image(background, strategy) => {
+                resize::resize_to_fit(background, width, height, Fit::Cover(*strategy))
+            }
+        };
+
+        let area = format.safe_rect();
+        let orientation = Orientation::of(width, height);
+        for (element, layout) in &self.elements {
+            let constraint = layout.get(orientation);
+            let bounds = constraint.resolve(area);
+            draw_element(&mut img, element, bounds, constraint.anchor, area);
+        }
+        img
+    }
+
+    /// Render the design for each of a list of formats, such as `FormatRegistry::new().formats()`.
+    /// The graphics are returned in the same order as the formats.
+    pub fn render_all(&self, formats: &[SocialFormat]) -> Vec<DynamicImage> {
+        formats.iter().map(|format| self.render(format)).collect()
+    }
+}
+
+fn draw_element(
+    img: &mut DynamicImage,
+    element: &Element,
+    bounds: (f32, f32, f32, f32),
+    anchor: Anchor,
+    area: (u32, u32, u32, u32),
+) {
+    let (x, y, width, height) = bounds;
+    if width < 1.0 || height < 1.0 {
+        return;
+    }
+
+    match element {
+        Element::Rect {
+            color,
+            corner_radius,
+        } => {
+            let radius = corner_radius * width.min(height);
+            let contour = raster::rounded_rect(x, y, width, height, radius);
+            raster::fill_contours(img, &[contour], (*color).into(), Quality::AntiAliased);
+        }
+        Element::Image {
+            img: source,
+            strategy,
+        } => {
+            let options = ResizeOptions {
+                fit: Fit::Cover(*strategy),
+                ..ResizeOptions::default()
+            };
+            let resized = resize::resize_dyn_image(
+                source,
+                width.round() as u32,
+                height.round() as u32,
+                &options,
+            );
+            blend::composite(img, &resized, x.round() as i32, y.round() as i32, 1.0);
+        }
+        Element::Text {
+            text,
+            font,
+            max_font_size,
+            color,
+        } => {
+            let max_size = max_font_size * area.2.min(area.3) as f32;
+            let font_vec = text::open_font(font);
+            let parsed = Font::try_from_bytes(&font_vec).unwrap();
+            let (font_size, block_height) =
+                text::fit_with_font(&parsed, text, max_size, width, height);
+
+            let (fx, fy) = anchor.factors();
+            let align = match fx {
+                f if f < 0.5 => TextAlign::Left,
+                f if f > 0.5 => TextAlign::Right,
+                _ => TextAlign::Centre,
+            };
+            let text_y = y + (height - block_height) * fy;
+            text::draw_text_block(
+                img,
+                text,
+                x.max(0.0).round() as u32,
+                text_y.max(0.0).round() as u32,
+                width.round() as u32,
+                font,
+                font_size,
+                color,
+                align,
+            );
+        }
+    }
+}
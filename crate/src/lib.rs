@@ -131,26 +131,19 @@ impl PhotonImage {
         };
     }
 
-    /// Create a new social media graphic.
-    /// Available types include: linkedin_banner, pinterest, fb_ad, fb_post, instagram_post,
-    /// twitter_header, twitter_post.
-    pub fn new_socialmedia_graphic(name: &str) -> PhotonImage {
-        let (width, height) = match name {
-            "linkedin_banner" => (1400, 425),
-            "pinterest" => (735, 1102),
-            "fb_ad" => (1200, 628),
-            "fb_post" => (940, 788),
-            "instagram_post" => (1080, 1080),
-            "twitter_post" => (1024, 512),
-            "twitter_header" => (1500, 500),
-            _ => (192, 120),
-        };
-        let new_vec = Vec::new();
-        return PhotonImage {
-            raw_pixels: new_vec,
-            width: width,
-            height: height,
-        };
+    /// Create a new, transparent social media graphic.
+    /// Available types include: instagram_post, instagram_story, instagram_reel, fb_post, fb_ad,
+    /// twitter_post, twitter_header, linkedin_banner, pinterest, youtube_thumbnail, tiktok, open_graph,
+    /// and others listed in `formats::builtin_formats`.
+    ///
+    /// Returns an error if the format is unknown.
+    pub fn new_socialmedia_graphic(name: &str) -> Result<PhotonImage, formats::FormatError> {
+        let format = formats::social_format(name)?;
+        Ok(PhotonImage {
+            raw_pixels: vec![0; (format.width * format.height * 4) as usize],
+            width: format.width,
+            height: format.height,
+        })
     }
 
     pub fn raw_pix(self) -> Vec<u8> {
@@ -217,7 +210,7 @@ impl Font {
 
 /// Rgb color type.
 #[wasm_bindgen]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Rgb {
     pub r: u8,
     pub g: u8,
@@ -232,6 +225,55 @@ impl Rgb {
     }
 }
 
+/// Rgba color type, an Rgb color with an alpha channel for transparency.
+///
+/// An alpha of 0 is fully transparent, while 255 is fully opaque.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+#[wasm_bindgen]
+impl Rgba {
+    /// Create a new Rgba color.
+    pub fn new(r: u8, g: u8, b: u8, a: u8) -> Rgba {
+        Rgba { r, g, b, a }
+    }
+
+    /// Create a fully opaque Rgba color from an Rgb color.
+    pub fn from_rgb(rgb: &Rgb) -> Rgba {
+        Rgba::new(rgb.r, rgb.g, rgb.b, 255)
+    }
+}
+
+impl From<&Rgb> for Rgba {
+    fn from(rgb: &Rgb) -> Rgba {
+        Rgba::from_rgb(rgb)
+    }
+}
+
+impl From<Rgb> for Rgba {
+    fn from(rgb: Rgb) -> Rgba {
+        Rgba::from_rgb(&rgb)
+    }
+}
+
+impl From<&Rgba> for Rgba {
+    fn from(rgba: &Rgba) -> Rgba {
+        *rgba
+    }
+}
+
+impl From<Rgba> for image::Rgba<u8> {
+    fn from(rgba: Rgba) -> image::Rgba<u8> {
+        image::Rgba([rgba.r, rgba.g, rgba.b, rgba.a])
+    }
+}
+
 #[wasm_bindgen]
 pub fn draw_rect_web(ctx: CanvasRenderingContext2d) {
     ctx.rect(10.0, 20.0, 50.0, 50.0);
@@ -343,10 +385,21 @@ fn set_panic_hook() {
 }
 
 pub mod background;
+pub mod blend;
 pub mod collage;
+pub mod crop;
 pub mod diagrams;
 pub mod elements;
+pub mod filters;
+pub mod flowchart;
+pub mod formats;
+pub mod frames;
 pub mod helpers;
+pub mod infographics;
+pub mod layout;
+pub mod mask;
 pub mod presets;
+pub mod raster;
 pub mod resize;
+pub mod table;
 pub mod text;
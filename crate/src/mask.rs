@@ -0,0 +1,201 @@
+//! Clip images to shapes, text, or the luminance of other images.
+//!
+//! A mask stores an alpha value for each pixel, from 0.0 (hidden) to 1.0 (visible).
+//! Applying a mask to an image multiplies the image's alpha channel by the mask,
+//! so that it can then be composited onto a design, e.g. to create circular avatars.
+
+use crate::blend;
+use crate::raster::{self, Contour, Quality};
+use crate::text::draw_text;
+use crate::Rgb;
+use image::DynamicImage;
+use std::f32::consts::PI;
+
+/// An alpha mask, which can be applied to an image before compositing it.
+#[derive(Debug, Clone)]
+pub struct Mask {
+    pub width: u32,
+    pub height: u32,
+    values: Vec<f32>,
+}
+
+impl Mask {
+    /// Create a mask where every pixel is set to the same value.
+    pub fn new(width: u32, height: u32, value: f32) -> Mask {
+        Mask {
+            width,
+            height,
+            values: vec![value.clamp(0.0, 1.0); (width * height) as usize],
+        }
+    }
+
+    /// Create a mask from a shape, described by a set of contours (see the `raster` module).
+    /// Pixels inside the shape are visible, while those outside are hidden.
+    ///
+    /// ### Arguments
+    /// * `width` - Width of the mask.
+    /// * `height` - Height of the mask.
+    /// * `contours` - Contours describing the shape.
+    /// * `quality` - Whether the edges of the shape should be anti-aliased.
+    pub fn from_contours(width: u32, height: u32, contours: &[Contour], quality: Quality) -> Mask {
+        let mut mask = Mask::new(width, height, 0.0);
+        let coverage = raster::rasterize(contours, quality, width, height);
+        for y in 0..coverage.height {
+            for x in 0..coverage.width {
+                mask.set(coverage.x + x, coverage.y + y, coverage.get(x, y));
+            }
+        }
+        mask
+    }
+
+    /// Create an elliptical mask filling the mask's bounds, which is circular if the mask is square.
+    pub fn circle(width: u32, height: u32) -> Mask {
+        let (rx, ry) = (width as f32 / 2.0, height as f32 / 2.0);
+        let contour = raster::ellipse(rx, ry, rx, ry);
+        Mask::from_contours(width, height, &[contour], Quality::AntiAliased)
+    }
+
+    /// Create a mask in the shape of a rectangle with rounded corners, filling the mask's bounds.
+    ///
+    /// ### Arguments
+    /// * `width` - Width of the mask.
+    /// * `height` - Height of the mask.
+    /// * `radius` - Radius of the rounded corners.
+    pub fn rounded_rect(width: u32, height: u32, radius: f32) -> Mask {
+        let contour = raster::rounded_rect(0.0, 0.0, width as f32, height as f32, radius);
+        Mask::from_contours(width, height, &[contour], Quality::AntiAliased)
+    }
+
+    /// Create a mask in the shape of a hexagon with a flat top and bottom, fitted within the mask's bounds.
+    pub fn hexagon(width: u32, height: u32) -> Mask {
+        Mask::regular_polygon(width, height, 6, 0.0)
+    }
+
+    /// Create a mask in the shape of a regular polygon, centred within the mask's bounds.
+    ///
+    /// ### Arguments
+    /// * `width` - Width of the mask.
+    /// * `height` - Height of the mask.
+    /// * `sides` - Number of sides of the polygon.
+    /// * `rotation` - Angle of the first corner in radians, clockwise from the positive x-axis.
+    pub fn regular_polygon(width: u32, height: u32, sides: u32, rotation: f32) -> Mask {
+        let (cx, cy) = (width as f32 / 2.0, height as f32 / 2.0);
+        // Shrink the polygon if needed, so that its flat sides also fit vertically.
+        let half_height = (PI / sides.max(3) as f32).cos();
+        let radius = cx.min(cy / half_height);
+        let contour = raster::regular_polygon(cx, cy, radius, sides, rotation);
+        Mask::from_contours(width, height, &[contour], Quality::AntiAliased)
+    }
+
+    /// Create a mask in the shape of some text, so that an image shows through the letters.
+    ///
+    /// ### Arguments
+    /// * `width` - Width of the mask.
+    /// * `height` - Height of the mask.
+    /// * `text` - Text string to be drawn.
+    /// * `x` - X-coordinate of top corner of text.
+    /// * `y` - Y-coordinate of top corner of text.
+    /// * `font` - Font name, such as "BebasKai" or "Roboto-Bold".
+    /// * `font_size` - f32 that represents the font's size.
+    pub fn from_text(
+        width: u32,
+        height: u32,
+        text: &str,
+        x: u32,
+        y: u32,
+        font: &str,
+        font_size: f32,
+    ) -> Mask {
+        let mut text_img = DynamicImage::new_luma8(width, height);
+        let white = Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        };
+        draw_text(&mut text_img, text, x, y, font, font_size, &white);
+        Mask::from_luminance(&text_img)
+    }
+
+    /// Create a mask from the luminance of an image, where white is visible and black is hidden.
+    pub fn from_luminance(img: &DynamicImage) -> Mask {
+        let luma = img.to_luma8();
+        Mask {
+            width: luma.width(),
+            height: luma.height(),
+            values: luma.pixels().map(|p| p[0] as f32 / 255.0).collect(),
+        }
+    }
+
+    /// Get the value of the mask at a pixel. Pixels outside the mask are hidden.
+    pub fn get(&self, x: u32, y: u32) -> f32 {
+        if x >= self.width || y >= self.height {
+            return 0.0;
+        }
+        self.values[(y * self.width + x) as usize]
+    }
+
+    /// Set the value of the mask at a pixel.
+    pub fn set(&mut self, x: u32, y: u32, value: f32) {
+        if x < self.width && y < self.height {
+            self.values[(y * self.width + x) as usize] = value.clamp(0.0, 1.0);
+        }
+    }
+
+    /// Invert the mask, so that hidden areas become visible and vice-versa.
+    pub fn invert(&mut self) {
+        for value in self.values.iter_mut() {
+            *value = 1.0 - *value;
+        }
+    }
+
+    /// Combine with another mask, so that only areas visible in both masks remain visible.
+    pub fn intersect(&mut self, other: &Mask) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let value = self.get(x, y) * other.get(x, y);
+                self.set(x, y, value);
+            }
+        }
+    }
+
+    /// Apply the mask to an image, returning a copy of the image whose alpha channel has
+    /// been multiplied by the mask. The mask is aligned with the top-left corner of the image.
+    pub fn apply(&self, img: &DynamicImage) -> DynamicImage {
+        let mut masked = img.to_rgba8();
+        for (x, y, pixel) in masked.enumerate_pixels_mut() {
+            pixel[3] = (pixel[3] as f32 * self.get(x, y)).round() as u8;
+        }
+        DynamicImage::ImageRgba8(masked)
+    }
+}
+
+/// Apply a mask to an image, and composite the result onto a container image.
+///
+/// ### Arguments
+/// * `container_img` - A mutable ref to the DynamicImage being drawn onto.
+/// * `img` - The image to be masked.
+/// * `mask` - Mask to apply to `img`.
+/// * `x` - X-coordinate of the top-left corner of `img` on `container_img`.
+/// * `y` - Y-coordinate of the top-left corner of `img` on `container_img`.
+///
+/// ### Example
+/// ```ignore
+/// // Draw a circular avatar onto a speaker announcement graphic.
+/// let mask = Mask::circle(avatar.width(), avatar.height());
+/// draw_masked_image(&mut graphic, &avatar, &mask, 40, 40);
+/// ```
+pub fn draw_masked_image(
+    container_img: &mut DynamicImage,
+    img: &DynamicImage,
+    mask: &Mask,
+    x: i32,
+    y: i32,
+) {
+    let masked = mask.apply(img);
+    blend::composite(container_img, &masked, x, y, 1.0);
+}
+
+/// Clip an image to a mask in place, making the hidden areas transparent.
+pub fn clip_image(img: &mut DynamicImage, mask: &Mask) {
+    *img = mask.apply(img);
+}
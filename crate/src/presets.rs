@@ -1,6 +1,7 @@
 //! Templates for rapid graphic creation.
 
 use crate::elements::*;
+use crate::resize::{self, Resampling};
 use crate::text::*;
 use crate::{new_with_background, Rgb};
 use image::{DynamicImage, GenericImageView};
@@ -332,14 +333,12 @@ pub fn postcard(
     };
 
     let mut container_img = new_with_background(width, height, &white);
-    let sampling_filter = image::imageops::FilterType::Nearest;
-
-    let resized_img = image::DynamicImage::ImageRgba8(image::imageops::resize(
+    let resized_img = resize::resample(
         background_img,
         width - 20,
         height - 20,
-        sampling_filter,
-    ));
+        Resampling::default(),
+    );
 
     let main_img_width = background_img.width();
     let main_img_height = background_img.height();
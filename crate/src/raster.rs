@@ -0,0 +1,734 @@
+//! Rasterize shapes onto images, with optional anti-aliasing.
+//!
+//! Shapes are described as closed contours made up of sub-pixel `(f32, f32)` points.
+//! Contours are rasterized into a coverage mask, which stores the fraction of each pixel
+//! covered by the shape, and the mask is then used to blend a colour onto an image.
+
+use crate::blend::{blend_pixel, BlendMode};
+use image::{DynamicImage, GenericImage, GenericImageView, Rgba};
+use std::f32::consts::PI;
+
+/// A closed contour, made up of sub-pixel points. The last point joins back onto the first.
+pub type Contour = Vec<(f32, f32)>;
+
+/// Quality used when rasterizing shapes.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Quality {
+    /// Each pixel is either fully inside the shape or outside it, giving hard, jagged edges.
+    Fast,
+    /// Each pixel is shaded by the fraction of its area covered by the shape, giving smooth edges.
+    #[default]
+    AntiAliased,
+}
+
+/// Coverage of a shape over a rectangular region of an image.
+///
+/// Each value lies between 0.0 (not covered) and 1.0 (fully covered).
+#[derive(Debug, Clone)]
+pub struct CoverageMask {
+    /// X-coordinate of the top-left corner of the mask on the target image.
+    pub x: u32,
+    /// Y-coordinate of the top-left corner of the mask on the target image.
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    coverage: Vec<f32>,
+}
+
+impl CoverageMask {
+    /// Get the coverage at a position relative to the top-left corner of the mask.
+    pub fn get(&self, x: u32, y: u32) -> f32 {
+        self.coverage[(y * self.width + x) as usize]
+    }
+
+    /// Returns true if the mask covers no pixels.
+    pub fn is_empty(&self) -> bool {
+        self.coverage.is_empty()
+    }
+
+    fn empty() -> CoverageMask {
+        CoverageMask {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+            coverage: vec![],
+        }
+    }
+}
+
+/// Rasterize a set of contours into a coverage mask, clipped to `clip_width` x `clip_height`.
+///
+/// Contours wound in the same direction are combined, while a contour wound in the
+/// opposite direction inside another one cuts a hole out of it.
+/// All shape functions in this module produce contours wound in the same direction.
+/// Overlapping contours are merged, so their edges aren't covered twice where they overlap.
+///
+/// ### Arguments
+/// * `contours` - Contours describing the shape.
+/// * `quality` - Whether the edges of the shape should be anti-aliased.
+/// * `clip_width` - Width of the image the mask will be applied to.
+/// * `clip_height` - Height of the image the mask will be applied to.
+pub fn rasterize(
+    contours: &[Contour],
+    quality: Quality,
+    clip_width: u32,
+    clip_height: u32,
+) -> CoverageMask {
+    let points = contours.iter().flatten();
+    let (mut min_x, mut min_y) = (f32::MAX, f32::MAX);
+    let (mut max_x, mut max_y) = (f32::MIN, f32::MIN);
+    for &(x, y) in points {
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(y);
+    }
+
+    let x0 = min_x.floor().max(0.0) as u32;
+    let y0 = min_y.floor().max(0.0) as u32;
+    let x1 = (max_x.ceil().max(0.0) as u32).min(clip_width);
+    let y1 = (max_y.ceil().max(0.0) as u32).min(clip_height);
+    if x1 <= x0 || y1 <= y0 {
+        return CoverageMask::empty();
+    }
+
+    let (width, height) = ((x1 - x0) as usize, (y1 - y0) as usize);
+    let mut edges: Vec<Edge> = contours
+        .iter()
+        .flat_map(|contour| {
+            let len = contour.len();
+            (0..len).filter_map(move |i| {
+                let (ax, ay) = contour[i];
+                let (bx, by) = contour[(i + 1) % len];
+                Edge::new(
+                    (ax - x0 as f32, ay - y0 as f32),
+                    (bx - x0 as f32, by - y0 as f32),
+                )
+            })
+        })
+        .collect();
+    edges.sort_by(|a, b| {
+        a.top
+            .partial_cmp(&b.top)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut coverage = Vec::with_capacity(width * height);
+    let mut row = Row::new(width);
+    let mut active: Vec<usize> = vec![];
+    let mut next_edge = 0;
+    let mut crossings: Vec<(f32, i32)> = vec![];
+    for y in 0..height {
+        row.clear();
+        for sub in 0..SUBSCANLINES {
+            let sy = y as f32 + (sub as f32 + 0.5) / SUBSCANLINES as f32;
+            while next_edge < edges.len() && edges[next_edge].top <= sy {
+                active.push(next_edge);
+                next_edge += 1;
+            }
+            active.retain(|&i| edges[i].bottom > sy);
+
+            crossings.clear();
+            crossings.extend(
+                active
+                    .iter()
+                    .map(|&i| (edges[i].x_at(sy), edges[i].direction)),
+            );
+            crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+            // Pixels are inside wherever the edges crossed so far wind around them (the non-zero
+            // rule), so overlapping contours merge, and contours wound the other way cut holes.
+            let mut winding = 0;
+            let mut start = 0.0;
+            for &(x, direction) in &crossings {
+                if winding == 0 {
+                    start = x;
+                }
+                winding += direction;
+                if winding == 0 {
+                    row.add_span(start, x, 1.0 / SUBSCANLINES as f32);
+                }
+            }
+        }
+        coverage.extend(row.coverage().map(|value| {
+            let value = value.clamp(0.0, 1.0);
+            match quality {
+                Quality::AntiAliased => value,
+                Quality::Fast => {
+                    if value >= 0.5 {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                }
+            }
+        }));
+    }
+
+    CoverageMask {
+        x: x0,
+        y: y0,
+        width: x1 - x0,
+        height: y1 - y0,
+        coverage,
+    }
+}
+
+/// Blend a colour onto an image, weighted by the coverage of a mask.
+///
+/// The colour's alpha channel is respected, and it is composited over the existing pixels.
+pub fn fill_mask(img: &mut DynamicImage, mask: &CoverageMask, color: Rgba<u8>) {
+    fill_mask_with_blend(img, mask, color, BlendMode::Normal);
+}
+
+/// Blend a colour onto an image with a blend mode, weighted by the coverage of a mask.
+pub fn fill_mask_with_blend(
+    img: &mut DynamicImage,
+    mask: &CoverageMask,
+    color: Rgba<u8>,
+    mode: BlendMode,
+) {
+    for y in 0..mask.height {
+        for x in 0..mask.width {
+            let coverage = mask.get(x, y);
+            if coverage <= 0.0 {
+                continue;
+            }
+            let (px, py) = (mask.x + x, mask.y + y);
+            let dst = img.get_pixel(px, py);
+            img.put_pixel(px, py, blend_pixel(dst, color, mode, coverage));
+        }
+    }
+}
+
+/// Rasterize a set of contours and fill them with a colour.
+///
+/// ### Arguments
+/// * `img` - A mutable ref to a DynamicImage.
+/// * `contours` - Contours describing the shape.
+/// * `color` - Fill colour.
+/// * `quality` - Whether the edges of the shape should be anti-aliased.
+pub fn fill_contours(
+    img: &mut DynamicImage,
+    contours: &[Contour],
+    color: Rgba<u8>,
+    quality: Quality,
+) {
+    let mask = rasterize(contours, quality, img.width(), img.height());
+    fill_mask(img, &mask, color);
+}
+
+/// Contour of an axis-aligned rectangle.
+pub fn rect(x: f32, y: f32, width: f32, height: f32) -> Contour {
+    vec![
+        (x, y),
+        (x + width, y),
+        (x + width, y + height),
+        (x, y + height),
+    ]
+}
+
+/// Contour of an axis-aligned rectangle with rounded corners.
+pub fn rounded_rect(x: f32, y: f32, width: f32, height: f32, radius: f32) -> Contour {
+    let radius = radius.min(width / 2.0).min(height / 2.0).max(0.0);
+    if radius == 0.0 {
+        return rect(x, y, width, height);
+    }
+
+    let corners = [
+        (x + width - radius, y + radius, -PI / 2.0),
+        (x + width - radius, y + height - radius, 0.0),
+        (x + radius, y + height - radius, PI / 2.0),
+        (x + radius, y + radius, PI),
+    ];
+    let segments = segments_for_radius(radius) / 4 + 1;
+    let mut contour = vec![];
+    for &(cx, cy, start) in &corners {
+        for i in 0..=segments {
+            let angle = start + PI / 2.0 * i as f32 / segments as f32;
+            contour.push((cx + radius * angle.cos(), cy + radius * angle.sin()));
+        }
+    }
+    contour
+}
+
+/// Contour of a regular polygon, such as a hexagon, centred on `(cx, cy)`.
+///
+/// ### Arguments
+/// * `cx` - X-coordinate of the centre of the polygon.
+/// * `cy` - Y-coordinate of the centre of the polygon.
+/// * `radius` - Distance from the centre to each corner.
+/// * `sides` - Number of sides, which must be at least 3.
+/// * `rotation` - Angle of the first corner in radians, clockwise from the positive x-axis.
+pub fn regular_polygon(cx: f32, cy: f32, radius: f32, sides: u32, rotation: f32) -> Contour {
+    let sides = sides.max(3);
+    (0..sides)
+        .map(|i| {
+            let angle = rotation + 2.0 * PI * i as f32 / sides as f32;
+            (cx + radius * angle.cos(), cy + radius * angle.sin())
+        })
+        .collect()
+}
+
+/// Contours of the outline of an axis-aligned rectangle, drawn inside its edges.
+pub fn rect_outline(x: f32, y: f32, width: f32, height: f32, thickness: f32) -> Vec<Contour> {
+    let thickness = thickness.min(width / 2.0).min(height / 2.0);
+    let mut inner = rect(
+        x + thickness,
+        y + thickness,
+        width - 2.0 * thickness,
+        height - 2.0 * thickness,
+    );
+    // Wind the inner rectangle the opposite way, so that it cuts a hole.
+    inner.reverse();
+    vec![rect(x, y, width, height), inner]
+}
+
+/// Contour of an ellipse, centred on `(cx, cy)`.
+pub fn ellipse(cx: f32, cy: f32, rx: f32, ry: f32) -> Contour {
+    let segments = segments_for_radius(rx.max(ry));
+    (0..segments)
+        .map(|i| {
+            let angle = 2.0 * PI * i as f32 / segments as f32;
+            (cx + rx * angle.cos(), cy + ry * angle.sin())
+        })
+        .collect()
+}
+
+/// Contour of a circle, centred on `(cx, cy)`.
+pub fn circle(cx: f32, cy: f32, radius: f32) -> Contour {
+    ellipse(cx, cy, radius, radius)
+}
+
+/// Contour of a straight line of a given thickness, with square ends.
+pub fn line(start: (f32, f32), end: (f32, f32), thickness: f32) -> Contour {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let len = (dx * dx + dy * dy).sqrt();
+    if len == 0.0 {
+        return vec![];
+    }
+    // Offset perpendicular to the direction of the line.
+    let (nx, ny) = (-dy / len * thickness / 2.0, dx / len * thickness / 2.0);
+    oriented(vec![
+        (start.0 + nx, start.1 + ny),
+        (end.0 + nx, end.1 + ny),
+        (end.0 - nx, end.1 - ny),
+        (start.0 - nx, start.1 - ny),
+    ])
+}
+
+/// Contours of a line passing through a series of points, with rounded joins.
+pub fn polyline(points: &[(f32, f32)], thickness: f32) -> Vec<Contour> {
+    let mut contours: Vec<Contour> = points
+        .windows(2)
+        .map(|pair| line(pair[0], pair[1], thickness))
+        .collect();
+
+    if points.len() > 2 {
+        for &(x, y) in &points[1..points.len() - 1] {
+            contours.push(circle(x, y, thickness / 2.0));
+        }
+    }
+    contours
+}
+
+/// Contour of a closed polygon, wound in the same direction as other shapes.
+pub fn polygon(points: &[(f32, f32)]) -> Contour {
+    oriented(points.to_vec())
+}
+
+/// Contour of a slice of a ring between two radii, such as a segment of a donut chart,
+/// running clockwise from `start` to `end`. An inner radius of 0 gives a slice of pie.
+///
+/// ### Arguments
+/// * `cx` - X-coordinate of the centre of the ring.
+/// * `cy` - Y-coordinate of the centre of the ring.
+/// * `inner_radius` - Radius of the inside edge of the slice.
+/// * `outer_radius` - Radius of the outside edge of the slice.
+/// * `start` - Angle the slice starts at, in radians clockwise from the positive x-axis.
+/// * `end` - Angle the slice ends at, in radians clockwise from the positive x-axis.
+pub fn annular_sector(
+    cx: f32,
+    cy: f32,
+    inner_radius: f32,
+    outer_radius: f32,
+    start: f32,
+    end: f32,
+) -> Contour {
+    let sweep = end - start;
+    let full_circle = segments_for_radius(outer_radius) as f32;
+    let steps = ((full_circle * sweep.abs() / (2.0 * PI)).ceil() as usize).max(2);
+    let point = |radius: f32, step: usize| {
+        let angle = start + sweep * step as f32 / steps as f32;
+        (cx + radius * angle.cos(), cy + radius * angle.sin())
+    };
+
+    let mut contour: Contour = (0..=steps).map(|i| point(outer_radius, i)).collect();
+    if inner_radius > 0.0 {
+        contour.extend((0..=steps).rev().map(|i| point(inner_radius, i)));
+    } else {
+        contour.push((cx, cy));
+    }
+    oriented(contour)
+}
+
+/// Contours of an arc of a given thickness, centred on a circle of `radius`,
+/// running clockwise from `start` to `end`, with square or rounded ends.
+///
+/// ### Arguments
+/// * `cx` - X-coordinate of the centre of the circle.
+/// * `cy` - Y-coordinate of the centre of the circle.
+/// * `radius` - Radius of the circle the arc follows.
+/// * `thickness` - Thickness of the arc.
+/// * `start` - Angle the arc starts at, in radians clockwise from the positive x-axis.
+/// * `end` - Angle the arc ends at, in radians clockwise from the positive x-axis.
+/// * `round_caps` - Whether the ends of the arc are rounded.
+pub fn arc(
+    cx: f32,
+    cy: f32,
+    radius: f32,
+    thickness: f32,
+    start: f32,
+    end: f32,
+    round_caps: bool,
+) -> Vec<Contour> {
+    let half = thickness / 2.0;
+    let inner_radius = (radius - half).max(0.0);
+    let mut contours = vec![annular_sector(
+        cx,
+        cy,
+        inner_radius,
+        radius + half,
+        start,
+        end,
+    )];
+    if round_caps {
+        for angle in [start, end] {
+            let (x, y) = (cx + radius * angle.cos(), cy + radius * angle.sin());
+            contours.push(circle(x, y, half));
+        }
+    }
+    contours
+}
+
+// Number of segments needed to approximate a curve of the given radius,
+// keeping the flattening error well under a tenth of a pixel.
+fn segments_for_radius(radius: f32) -> usize {
+    let segments = (PI * (radius.abs() / 0.1).sqrt()).ceil() as usize;
+    segments.max(12)
+}
+
+// Reverse a contour if needed, so that it has a positive signed area.
+fn oriented(mut contour: Contour) -> Contour {
+    if signed_area(&contour) < 0.0 {
+        contour.reverse();
+    }
+    contour
+}
+
+fn signed_area(contour: &[(f32, f32)]) -> f32 {
+    let len = contour.len();
+    let mut area = 0.0;
+    for i in 0..len {
+        let (x0, y0) = contour[i];
+        let (x1, y1) = contour[(i + 1) % len];
+        area += x0 * y1 - x1 * y0;
+    }
+    area / 2.0
+}
+
+// Number of horizontal lines each row of pixels is sampled along. Coverage along each line is
+// exact, so only edges close to horizontal are affected by the number of lines.
+const SUBSCANLINES: usize = 16;
+
+// An edge of a contour, running from its top to its bottom.
+struct Edge {
+    top: f32,
+    bottom: f32,
+    // X-coordinate at the top, and change in x for each unit down.
+    x: f32,
+    dxdy: f32,
+    // +1 if the contour runs down along this edge, or -1 if it runs up.
+    direction: i32,
+}
+
+impl Edge {
+    // Horizontal edges are left out, since no horizontal line crosses them.
+    fn new(p0: (f32, f32), p1: (f32, f32)) -> Option<Edge> {
+        if p0.1 == p1.1 || !(p0.1.is_finite() && p1.1.is_finite()) {
+            return None;
+        }
+        let (direction, p0, p1) = if p0.1 < p1.1 {
+            (1, p0, p1)
+        } else {
+            (-1, p1, p0)
+        };
+        Some(Edge {
+            top: p0.1,
+            bottom: p1.1,
+            x: p0.0,
+            dxdy: (p1.0 - p0.0) / (p1.1 - p0.1),
+            direction,
+        })
+    }
+
+    fn x_at(&self, y: f32) -> f32 {
+        self.x + (y - self.top) * self.dxdy
+    }
+}
+
+// Coverage of a row of pixels, built up from spans along the lines sampling it.
+//
+// Pixels a span only partly covers get the covered fraction, while pixels it fully covers are
+// marked by a step up at its start and back down at its end, so that long spans are cheap to add.
+struct Row {
+    partial: Vec<f32>,
+    steps: Vec<f32>,
+}
+
+impl Row {
+    fn new(width: usize) -> Row {
+        Row {
+            partial: vec![0.0; width + 1],
+            steps: vec![0.0; width + 1],
+        }
+    }
+
+    fn clear(&mut self) {
+        self.partial.iter_mut().for_each(|value| *value = 0.0);
+        self.steps.iter_mut().for_each(|value| *value = 0.0);
+    }
+
+    // Add a span from `start` to `end` along one of the lines, weighted by the line's share of the row.
+    fn add_span(&mut self, start: f32, end: f32, weight: f32) {
+        let width = (self.partial.len() - 1) as f32;
+        let (start, end) = (start.clamp(0.0, width), end.clamp(0.0, width));
+        if end <= start {
+            return;
+        }
+        let (first, last) = (start.floor() as usize, end.floor() as usize);
+        if first == last {
+            self.partial[first] += (end - start) * weight;
+            return;
+        }
+        self.partial[first] += (first as f32 + 1.0 - start) * weight;
+        self.steps[first + 1] += weight;
+        self.steps[last] -= weight;
+        self.partial[last] += (end - last as f32) * weight;
+    }
+
+    fn coverage(&self) -> impl Iterator<Item = f32> + '_ {
+        let width = self.partial.len() - 1;
+        self.partial[..width]
+            .iter()
+            .zip(&self.steps[..width])
+            .scan(0.0, |full, (partial, step)| {
+                *full += step;
+                Some(*full + partial)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Coverage of every pixel of a `size` x `size` image by some contours.
+    fn coverage(contours: &[Contour], quality: Quality, size: u32) -> Vec<Vec<f32>> {
+        let mask = rasterize(contours, quality, size, size);
+        (0..size)
+            .map(|y| {
+                (0..size)
+                    .map(|x| {
+                        let inside = x >= mask.x
+                            && y >= mask.y
+                            && x < mask.x + mask.width
+                            && y < mask.y + mask.height;
+                        match inside {
+                            true => mask.get(x - mask.x, y - mask.y),
+                            false => 0.0,
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Coverage of every pixel estimated by sampling a grid of points within it.
+    fn sampled(size: u32, inside: impl Fn(f32, f32) -> bool) -> Vec<Vec<f32>> {
+        let samples = 32;
+        (0..size)
+            .map(|y| {
+                (0..size)
+                    .map(|x| {
+                        let mut hits = 0;
+                        for sy in 0..samples {
+                            for sx in 0..samples {
+                                let px = x as f32 + (sx as f32 + 0.5) / samples as f32;
+                                let py = y as f32 + (sy as f32 + 0.5) / samples as f32;
+                                if inside(px, py) {
+                                    hits += 1;
+                                }
+                            }
+                        }
+                        hits as f32 / (samples * samples) as f32
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Whether a point lies inside a contour, by counting the edges a line to its right crosses.
+    fn contains(contour: &[(f32, f32)], x: f32, y: f32) -> bool {
+        let len = contour.len();
+        (0..len).fold(false, |inside, i| {
+            let ((ax, ay), (bx, by)) = (contour[i], contour[(i + 1) % len]);
+            let crosses = (ay > y) != (by > y) && x < ax + (y - ay) / (by - ay) * (bx - ax);
+            inside != crosses
+        })
+    }
+
+    fn assert_close(actual: &[Vec<f32>], expected: &[Vec<f32>], tolerance: f32) {
+        for (y, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+            for (x, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+                assert!(
+                    (actual - expected).abs() <= tolerance,
+                    "pixel ({}, {}) has coverage {}, expected {}",
+                    x,
+                    y,
+                    actual,
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn half_pixel_offset_rect() {
+        let cov = coverage(&[rect(2.5, 2.5, 4.0, 3.0)], Quality::AntiAliased, 10);
+        // Corners are covered by a quarter, edges by a half, and the inside fully.
+        assert_eq!(cov[2][2], 0.25);
+        assert_eq!(cov[2][6], 0.25);
+        assert_eq!(cov[5][2], 0.25);
+        assert_eq!(cov[5][6], 0.25);
+        assert_eq!(cov[2][3..6], [0.5; 3]);
+        assert_eq!(cov[3][3..6], [1.0; 3]);
+        assert_eq!(cov[4][3..6], [1.0; 3]);
+        assert_eq!(cov[5][3..6], [0.5; 3]);
+        for row in &cov[3..5] {
+            assert_eq!(row[1..3], [0.0, 0.5]);
+            assert_eq!(row[6..8], [0.5, 0.0]);
+        }
+    }
+
+    #[test]
+    fn diagonal_triangle_edge() {
+        let triangle = polygon(&[(0.0, 0.0), (8.0, 0.0), (0.0, 8.0)]);
+        let cov = coverage(&[triangle], Quality::AntiAliased, 10);
+        for (y, row) in cov.iter().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                // The edge x + y = 8 cuts the pixels it crosses corner to corner.
+                let expected = match x + y {
+                    d if d < 7 => 1.0,
+                    7 => 0.5,
+                    _ => 0.0,
+                };
+                assert!(
+                    (value - expected).abs() < 1e-5,
+                    "pixel ({}, {}) has coverage {}, expected {}",
+                    x,
+                    y,
+                    value,
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn circle_edges_match_sampled_coverage() {
+        // The circle is a polygon, so coverage is sampled from the polygon itself.
+        let contour = circle(10.3, 9.6, 6.2);
+        let cov = coverage(std::slice::from_ref(&contour), Quality::AntiAliased, 20);
+        assert_close(&cov, &sampled(20, |x, y| contains(&contour, x, y)), 0.03);
+
+        // The coverage adds up to the polygon's area, which is within 2% of the circle's.
+        let area: f32 = cov.iter().flatten().sum();
+        assert!(
+            (area - signed_area(&contour)).abs() < 0.05,
+            "area is {}",
+            area
+        );
+        assert!(
+            (area / (PI * 6.2 * 6.2) - 1.0).abs() < 0.02,
+            "area is {}",
+            area
+        );
+    }
+
+    #[test]
+    fn fast_quality_differs_only_on_edges() {
+        let shapes = vec![
+            vec![rect(2.5, 2.5, 9.0, 7.0)],
+            vec![polygon(&[(1.0, 1.0), (18.0, 3.0), (4.0, 17.0)])],
+            vec![circle(10.3, 9.6, 6.2)],
+            rect_outline(1.5, 1.5, 16.0, 16.0, 3.0),
+        ];
+        for contours in &shapes {
+            let smooth = coverage(contours, Quality::AntiAliased, 20);
+            let fast = coverage(contours, Quality::Fast, 20);
+            for (smooth, fast) in smooth.iter().flatten().zip(fast.iter().flatten()) {
+                assert!(*fast == 0.0 || *fast == 1.0);
+                if *smooth == 0.0 || *smooth == 1.0 {
+                    assert_eq!(fast, smooth);
+                } else {
+                    assert_eq!(*fast, if *smooth >= 0.5 { 1.0 } else { 0.0 });
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn outline_cuts_a_hole() {
+        let cov = coverage(
+            &rect_outline(2.0, 2.0, 12.0, 12.0, 2.0),
+            Quality::AntiAliased,
+            16,
+        );
+        assert_eq!(cov[8][8], 0.0);
+        assert_eq!(cov[8][2], 1.0);
+        assert_eq!(cov[8][4], 0.0);
+        assert_eq!(cov[2][8], 1.0);
+    }
+
+    #[test]
+    fn polyline_joins_have_no_seams() {
+        // A straight polyline through a join covers exactly what a single line does.
+        let joined = polyline(&[(2.0, 8.3), (9.7, 8.3), (18.0, 8.3)], 5.0);
+        let single = line((2.0, 8.3), (18.0, 8.3), 5.0);
+        assert_close(
+            &coverage(&joined, Quality::AntiAliased, 20),
+            &coverage(&[single], Quality::AntiAliased, 20),
+            1e-4,
+        );
+
+        // A bent polyline matches sampled coverage of the segments and round join.
+        let points = [(2.0, 3.0), (10.5, 12.5), (18.0, 4.0)];
+        let cov = coverage(&polyline(&points, 4.0), Quality::AntiAliased, 20);
+        let expected = sampled(20, |x, y| {
+            let near_segment = |(ax, ay): (f32, f32), (bx, by): (f32, f32)| {
+                let (dx, dy) = (bx - ax, by - ay);
+                let t = ((x - ax) * dx + (y - ay) * dy) / (dx * dx + dy * dy);
+                if !(0.0..=1.0).contains(&t) {
+                    return false;
+                }
+                let (px, py) = (ax + t * dx, ay + t * dy);
+                (x - px).powi(2) + (y - py).powi(2) <= 4.0
+            };
+            let near_join = (x - 10.5).powi(2) + (y - 12.5).powi(2) <= 4.0;
+            near_segment(points[0], points[1]) || near_segment(points[1], points[2]) || near_join
+        });
+        assert_close(&cov, &expected, 0.03);
+    }
+}
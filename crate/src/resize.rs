@@ -1,114 +1,391 @@
 //! Resize images to specific sizes/for various social media platforms.
 
-use crate::{helpers, PhotonImage};
-use image::GenericImageView;
+use crate::blend;
+use crate::crop::{self, CropStrategy};
+use crate::formats::{self, FormatError, SocialFormat};
+use crate::{helpers, PhotonImage, Rgba};
+use image::{DynamicImage, GenericImageView, ImageBuffer};
 use wasm_bindgen::prelude::*;
 
+/// How an image is fitted to a new size with a different aspect ratio.
+#[derive(Debug, Clone, Copy)]
+pub enum Fit {
+    /// Stretch the image to fill the new size, distorting it if the aspect ratio differs.
+    Stretch,
+    /// Scale the image to cover the new size, cropping the overflow.
+    /// The crop strategy chooses which part of the image is kept.
+    Cover(CropStrategy),
+    /// Scale the image to fit within the new size, filling the remaining space with a colour.
+    Contain(Rgba),
+}
+
+impl Default for Fit {
+    fn default() -> Fit {
+        Fit::Cover(CropStrategy::default())
+    }
+}
+
+/// Resampling filters, which decide how pixels are combined when an image is resized.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Resampling {
+    /// Nearest neighbour. Fastest, but gives blocky results.
+    Nearest,
+    /// Linear interpolation.
+    Triangle,
+    /// Cubic interpolation, which is sharp and fairly fast.
+    CatmullRom,
+    /// Gaussian filter, which gives soft results.
+    Gaussian,
+    /// Lanczos filter with a window of 3. Slowest, but keeps the most detail.
+    #[default]
+    Lanczos3,
+    /// Average the pixels covered by each new pixel, which suits large reductions in size
+    /// such as creating thumbnails from photos.
+    Area,
+}
+
+/// Options for resizing an image.
+#[derive(Debug, Clone, Copy)]
+pub struct ResizeOptions {
+    /// Resampling filter used to scale the image.
+    pub filter: Resampling,
+    /// How the image is fitted to the new size, if the aspect ratio differs.
+    pub fit: Fit,
+}
+
+impl Default for ResizeOptions {
+    /// High-quality resampling which stretches the image to the new size, as with `resize`.
+    fn default() -> ResizeOptions {
+        ResizeOptions {
+            filter: Resampling::default(),
+            fit: Fit::Stretch,
+        }
+    }
+}
+
 /// Resize an image for a particular format on social media.
-/// Available formats include: pinterest, fb_ad, fb_post, instagram_post, twitter_header, linkedin_banner
+/// Available formats include: instagram_post, instagram_story, fb_post, twitter_header, linkedin_banner,
+/// youtube_thumbnail, tiktok, open_graph, and others listed in `formats::builtin_formats`.
+///
+/// The image keeps its aspect ratio, and is cropped to the format around its most salient region.
+/// Use `resize_socialmedia_with_fit` to choose how the image is fitted.
+///
+/// Returns an error if the format is unknown.
 ///
 /// # Arguments
 /// * `img` - A mutable ref to a PhotonImage.
 /// * `type` - Social media format. The available types are shown above.
 /// ### Example
 /// ```ignore
-/// resize_socialmedia(&mut img, "linkedin_banner");
+/// resize_socialmedia(&mut img, "linkedin_banner")?;
 /// ```
 #[wasm_bindgen]
-pub fn resize_socialmedia(img: &PhotonImage, format: &str) -> PhotonImage {
-    let sampling_filter = image::imageops::FilterType::Nearest;
-    let dynimage = helpers::dyn_image_from_raw(&img);
-    let (width, height) = match format {
-        "linkedin_banner" => (1400, 425),
-        "pinterest" => (735, 1102),
-        "fb_ad" => (1200, 628),
-        "fb_post" => (940, 788),
-        "instagram_post" => (1080, 1080),
-        "twitter_post" => (1024, 512),
-        "twitter_header" => (1500, 500),
-        _ => (192, 120),
+pub fn resize_socialmedia(img: &PhotonImage, format: &str) -> Result<PhotonImage, FormatError> {
+    resize_socialmedia_with_fit(img, format, Fit::default())
+}
+
+/// Resize an image for a particular format on social media, choosing how it is fitted to the format.
+///
+/// # Arguments
+/// * `img` - A ref to a PhotonImage.
+/// * `format` - Social media format, as for `resize_socialmedia`.
+/// * `fit` - Whether to stretch, crop (cover) or letterbox (contain) the image.
+/// ### Example
+/// ```ignore
+/// // Keep the top-left of the photo in frame.
+/// resize_socialmedia_with_fit(&img, "pinterest", Fit::Cover(CropStrategy::FocalPoint(0.2, 0.2)))?;
+/// ```
+pub fn resize_socialmedia_with_fit(
+    img: &PhotonImage,
+    format: &str,
+    fit: Fit,
+) -> Result<PhotonImage, FormatError> {
+    let format = formats::social_format(format)?;
+    Ok(resize_to_format(img, &format, fit))
+}
+
+/// Resize an image to a social media format, which may be a custom format from a `FormatRegistry`.
+///
+/// # Arguments
+/// * `img` - A ref to a PhotonImage.
+/// * `format` - The format to resize to.
+/// * `fit` - Whether to stretch, crop (cover) or letterbox (contain) the image.
+pub fn resize_to_format(img: &PhotonImage, format: &SocialFormat, fit: Fit) -> PhotonImage {
+    let dynimage = helpers::dyn_image_from_raw(img);
+    let resized_img = resize_to_fit(&dynimage, format.width, format.height, fit);
+    PhotonImage {
+        raw_pixels: resized_img.to_rgba8().into_raw(),
+        width: format.width,
+        height: format.height,
+    }
+}
+
+/// Resize an image to an exact size, fitting it according to `fit` if the aspect ratio differs.
+///
+/// # Arguments
+/// * `img` - A ref to a DynamicImage.
+/// * `width` - Width of the new image.
+/// * `height` - Height of the new image.
+/// * `fit` - Whether to stretch, crop (cover) or letterbox (contain) the image.
+pub fn resize_to_fit(img: &DynamicImage, width: u32, height: u32, fit: Fit) -> DynamicImage {
+    let options = ResizeOptions {
+        fit,
+        ..ResizeOptions::default()
     };
-    let resized_img = image::DynamicImage::ImageRgba8(image::imageops::resize(
-        &dynimage,
-        width,
-        height,
-        sampling_filter,
-    ));
-    let raw_pixels = resized_img.to_bytes();
-    return PhotonImage {
-        raw_pixels: raw_pixels,
-        width: width,
-        height: height,
+    resize_dyn_image(img, width, height, &options)
+}
+
+/// Resize an image to an exact size, with a choice of resampling filter and fit.
+///
+/// # Arguments
+/// * `img` - A ref to a DynamicImage.
+/// * `width` - Width of the new image.
+/// * `height` - Height of the new image.
+/// * `options` - Resampling filter and fit to use.
+/// ### Example
+/// ```ignore
+/// let options = ResizeOptions {
+///     filter: Resampling::Area,
+///     fit: Fit::Cover(CropStrategy::Centre),
+/// };
+/// let thumbnail = resize_dyn_image(&photo, 200, 200, &options);
+/// ```
+pub fn resize_dyn_image(
+    img: &DynamicImage,
+    width: u32,
+    height: u32,
+    options: &ResizeOptions,
+) -> DynamicImage {
+    let (img_width, img_height) = img.dimensions();
+    // There's nothing to resample to or from an empty image, so return a blank one at the new size.
+    if width == 0 || height == 0 || img_width == 0 || img_height == 0 {
+        let pixel = match options.fit {
+            Fit::Contain(background) => background.into(),
+            _ => image::Rgba([0, 0, 0, 0]),
+        };
+        return DynamicImage::ImageRgba8(ImageBuffer::from_pixel(width, height, pixel));
+    }
+    match options.fit {
+        Fit::Stretch => resample(img, width, height, options.filter),
+        Fit::Cover(strategy) => {
+            let (x, y, crop_width, crop_height) = crop::crop_window(img, width, height, strategy);
+            let cropped = img.crop_imm(x, y, crop_width, crop_height);
+            resample(&cropped, width, height, options.filter)
+        }
+        Fit::Contain(background) => {
+            let scale = (width as f64 / img_width as f64).min(height as f64 / img_height as f64);
+            let scaled_width = ((img_width as f64 * scale).round() as u32).clamp(1, width);
+            let scaled_height = ((img_height as f64 * scale).round() as u32).clamp(1, height);
+            let scaled = resample(img, scaled_width, scaled_height, options.filter);
+            let pixel: image::Rgba<u8> = background.into();
+            let mut container =
+                DynamicImage::ImageRgba8(ImageBuffer::from_pixel(width, height, pixel));
+            let x = (width - scaled_width) / 2;
+            let y = (height - scaled_height) / 2;
+            blend::composite(&mut container, &scaled, x as i32, y as i32, 1.0);
+            container
+        }
+    }
+}
+
+/// Resize an image to an exact size using a resampling filter, ignoring its aspect ratio.
+///
+/// # Arguments
+/// * `img` - A ref to a DynamicImage.
+/// * `width` - Width of the new image.
+/// * `height` - Height of the new image.
+/// * `filter` - Resampling filter, such as `Resampling::Lanczos3`.
+pub fn resample(img: &DynamicImage, width: u32, height: u32, filter: Resampling) -> DynamicImage {
+    let sampling_filter = match filter {
+        Resampling::Nearest => image::imageops::FilterType::Nearest,
+        Resampling::Triangle => image::imageops::FilterType::Triangle,
+        Resampling::CatmullRom => image::imageops::FilterType::CatmullRom,
+        Resampling::Gaussian => image::imageops::FilterType::Gaussian,
+        Resampling::Lanczos3 => image::imageops::FilterType::Lanczos3,
+        Resampling::Area => return area_resize(img, width, height),
     };
+    DynamicImage::ImageRgba8(image::imageops::resize(img, width, height, sampling_filter))
 }
 
 /// Resizes each image in a vec of PhotonImages to the desired social media format.
-pub fn resize_socialmedia_vec(imgs: Vec<PhotonImage>, format: &str) -> Vec<PhotonImage> {
-    let mut resized_imgs = vec![];
-    for img in imgs {
-        let resized_img = resize_socialmedia(&img, format);
-        resized_imgs.push(resized_img);
-    }
-    return resized_imgs;
+///
+/// Returns an error if the format is unknown.
+pub fn resize_socialmedia_vec(
+    imgs: Vec<PhotonImage>,
+    format: &str,
+) -> Result<Vec<PhotonImage>, FormatError> {
+    let format = formats::social_format(format)?;
+    Ok(imgs
+        .iter()
+        .map(|img| resize_to_format(img, &format, Fit::default()))
+        .collect())
 }
 
-/// Resizes each image in a vec of PhotonImages to each of
-/// the available social media formats, and a vec of all new images is returned.
+/// Resizes an image to each of the built-in social media formats, and a vec of all new images is returned,
+/// in the same order as `formats::builtin_formats`.
+///
+/// This rescales a finished graphic. To re-lay out a design for each format instead, see `layout::Design`.
 pub fn resize_socialmedia_all(img: &PhotonImage) -> Vec<PhotonImage> {
-    let formats = [
-        "linkedin_banner",
-        "pinterest",
-        "fb_ad",
-        "fb_post",
-        "instagram_post",
-        "twitter_post",
-        "twitter_post",
-        "twitter_header",
-    ];
-    let mut resized_imgs = vec![];
-    for format in &formats {
-        let new_img = resize_socialmedia(&img, format);
-        resized_imgs.push(new_img)
-    }
-    return resized_imgs;
+    formats::builtin_formats()
+        .iter()
+        .map(|format| resize_to_format(img, format, Fit::default()))
+        .collect()
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 pub fn resize(photon_img: &PhotonImage, width: u32, height: u32) -> PhotonImage {
-    let sampling_filter = image::imageops::FilterType::Nearest;
-
-    let dyn_img = helpers::dyn_image_from_raw(&photon_img);
-    let resized_img = image::DynamicImage::ImageRgba8(image::imageops::resize(
-        &dyn_img,
-        width,
-        height,
-        sampling_filter,
-    ));
-
-    return PhotonImage {
-        raw_pixels: resized_img.to_bytes(),
-        width: resized_img.width(),
-        height: resized_img.height(),
-    };
+    resize_with_options(photon_img, width, height, &ResizeOptions::default())
 }
 
 #[cfg(target_arch = "wasm32")]
 pub fn resize(photon_img: &PhotonImage, width: u32, height: u32) -> PhotonImage {
-    let sampling_filter = image::imageops::FilterType::Nearest;
-
-    let dyn_img = helpers::dyn_image_from_raw(&photon_img);
-    let resized_img = image::DynamicImage::ImageRgba8(image::imageops::resize(
-        &dyn_img,
-        width,
-        height,
-        sampling_filter,
-    ));
-
-    return PhotonImage {
-        raw_pixels: resized_img.to_bytes(),
+    resize_with_options(photon_img, width, height, &ResizeOptions::default())
+}
+
+/// Resize a PhotonImage, with a choice of resampling filter and fit.
+pub fn resize_with_options(
+    photon_img: &PhotonImage,
+    width: u32,
+    height: u32,
+    options: &ResizeOptions,
+) -> PhotonImage {
+    let dyn_img = helpers::dyn_image_from_raw(photon_img);
+    let resized_img = resize_dyn_image(&dyn_img, width, height, options);
+
+    PhotonImage {
+        raw_pixels: resized_img.to_rgba8().into_raw(),
         width: resized_img.width(),
         height: resized_img.height(),
-    };
+    }
+}
+
+// Resize by averaging the source pixels covered by each new pixel, weighted by how much of each is covered.
+// Colours are weighted by alpha, so that transparent pixels don't darken the result.
+fn area_resize(img: &DynamicImage, width: u32, height: u32) -> DynamicImage {
+    let source = img.to_rgba8();
+    let (src_width, src_height) = source.dimensions();
+    let premultiplied: Vec<[f32; 4]> = source
+        .pixels()
+        .map(|p| {
+            let a = p[3] as f32 / 255.0;
+            [
+                p[0] as f32 * a,
+                p[1] as f32 * a,
+                p[2] as f32 * a,
+                p[3] as f32,
+            ]
+        })
+        .collect();
+
+    // Resize horizontally, then vertically.
+    let columns = area_weights(src_width, width);
+    let mut horizontal = vec![[0.0f32; 4]; (width * src_height) as usize];
+    for y in 0..src_height {
+        for (x, weights) in columns.iter().enumerate() {
+            let mut sum = [0.0f32; 4];
+            for &(src_x, weight) in weights {
+                let p = premultiplied[(y * src_width + src_x) as usize];
+                for c in 0..4 {
+                    sum[c] += p[c] * weight;
+                }
+            }
+            horizontal[(y * width) as usize + x] = sum;
+        }
+    }
+
+    let rows = area_weights(src_height, height);
+    let mut resized = image::RgbaImage::new(width, height);
+    for (y, weights) in rows.iter().enumerate() {
+        for x in 0..width {
+            let mut sum = [0.0f32; 4];
+            for &(src_y, weight) in weights {
+                let p = horizontal[(src_y * width + x) as usize];
+                for c in 0..4 {
+                    sum[c] += p[c] * weight;
+                }
+            }
+            let alpha = sum[3] / 255.0;
+            let unpremultiply = |v: f32| {
+                if alpha > 0.0 {
+                    (v / alpha).round().clamp(0.0, 255.0) as u8
+                } else {
+                    0
+                }
+            };
+            resized.put_pixel(
+                x,
+                y as u32,
+                image::Rgba([
+                    unpremultiply(sum[0]),
+                    unpremultiply(sum[1]),
+                    unpremultiply(sum[2]),
+                    sum[3].round().clamp(0.0, 255.0) as u8,
+                ]),
+            );
+        }
+    }
+    DynamicImage::ImageRgba8(resized)
+}
+
+// For each new pixel along an axis, the source pixels it covers and the weight given to each.
+fn area_weights(src_len: u32, len: u32) -> Vec<Vec<(u32, f32)>> {
+    let scale = src_len as f64 / len.max(1) as f64;
+    (0..len)
+        .map(|i| {
+            let start = i as f64 * scale;
+            let end = ((i + 1) as f64 * scale).min(src_len as f64);
+            let mut weights = vec![];
+            let mut src = start.floor() as u32;
+            while (src as f64) < end && src < src_len {
+                let covered = (end.min(src as f64 + 1.0) - start.max(src as f64)) as f32;
+                if covered > 0.0 {
+                    weights.push((src, covered / scale as f32));
+                }
+                src += 1;
+            }
+            weights
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_sizes_give_blank_images() {
+        let white = Rgba::new(255, 255, 255, 255);
+        let fits = [
+            Fit::Stretch,
+            Fit::Cover(CropStrategy::Centre),
+            Fit::Contain(white),
+        ];
+        for &fit in &fits {
+            let img = DynamicImage::new_rgba8(20, 10);
+            assert_eq!(
+                resize_to_fit(&img, 0, 10, fit).to_rgba8().dimensions(),
+                (0, 10)
+            );
+            assert_eq!(
+                resize_to_fit(&img, 10, 0, fit).to_rgba8().dimensions(),
+                (10, 0)
+            );
+
+            for &(width, height) in &[(0, 0), (0, 10), (10, 0)] {
+                let empty = DynamicImage::new_rgba8(width, height);
+                let resized = resize_to_fit(&empty, 8, 6, fit).to_rgba8();
+                assert_eq!(resized.dimensions(), (8, 6));
+            }
+        }
+    }
+
+    #[test]
+    fn contain_fills_empty_source_with_background() {
+        let white = Rgba::new(255, 255, 255, 255);
+        let empty = DynamicImage::new_rgba8(0, 4);
+        let resized = resize_to_fit(&empty, 3, 2, Fit::Contain(white)).to_rgba8();
+        assert!(resized.pixels().all(|p| p.0 == [255, 255, 255, 255]));
+    }
 }
 
 // #[cfg(not(target_arch = "wasm32"))]
@@ -0,0 +1,440 @@
+//! Tables of data, drawn as images.
+//!
+//! A `Table` holds a row of headers and rows of text and numbers. Each column is sized to fit its
+//! widest cell, measured with the text module, and cells too wide for a style's `max_column_width`
+//! are wrapped onto several lines. Numbers are right-aligned unless a column's alignment is set.
+//!
+//! ### Example
+//! ```ignore
+//! let mut table = Table::new(&["Format", "Width", "Height"]);
+//! table.add_row(vec!["Instagram post".into(), 1080.into(), 1080.into()]);
+//! table.add_row(vec!["Twitter post".into(), 1200.into(), 675.into()]);
+//! let img = create_table(&table, &TableStyle::default());
+//! ```
+
+use crate::diagrams::format_value;
+use crate::elements::draw_preset_rect_gradient;
+use crate::raster::{self, Quality};
+use crate::text::{self, TextAlign};
+use crate::{Rgb, Rgba};
+use image::DynamicImage;
+
+/// Create an image of a table, exactly the size of the table.
+///
+/// ### Arguments
+/// * `table` - Table to draw.
+/// * `style` - Colours, fonts, borders and spacing of the table.
+pub fn create_table(table: &Table, style: &TableStyle) -> DynamicImage {
+    let (width, height) = table.size(style);
+    let mut img = DynamicImage::new_rgba8(width.max(1), height.max(1));
+    draw_table(&mut img, table, style, 0, 0);
+    img
+}
+
+/// Draw a table onto an image, with its top-left corner at `x`, `y`.
+///
+/// ### Arguments
+/// * `img` - Image to draw the table onto.
+/// * `table` - Table to draw.
+/// * `style` - Colours, fonts, borders and spacing of the table.
+/// * `x` - X-coordinate of the left edge of the table.
+/// * `y` - Y-coordinate of the top edge of the table.
+pub fn draw_table(img: &mut DynamicImage, table: &Table, style: &TableStyle, x: u32, y: u32) {
+    let widths = table.column_widths(style);
+    let heights = table.row_heights(style);
+    let width: u32 = widths.iter().sum();
+    let height: u32 = heights.iter().sum();
+    if width == 0 || height == 0 {
+        return;
+    }
+    let (left, top) = (x as f32, y as f32);
+    let fill = |img: &mut DynamicImage, contour: raster::Contour, color: Rgba| {
+        raster::fill_contours(img, &[contour], color.into(), Quality::AntiAliased);
+    };
+
+    fill(
+        img,
+        raster::rect(left, top, width as f32, height as f32),
+        style.background,
+    );
+    let header_height = heights[0];
+    match &style.header_gradient {
+        Some(preset) => draw_preset_rect_gradient(img, width, header_height, x, y, preset, 1.0),
+        None => fill(
+            img,
+            raster::rect(left, top, width as f32, header_height as f32),
+            style.header_background,
+        ),
+    }
+
+    // Every other row is striped, starting with the second, so the first row stands apart from
+    // the header.
+    if let Some(stripe) = style.stripe_color {
+        let mut row_top = top + header_height as f32;
+        for (row, row_height) in heights.iter().skip(1).enumerate() {
+            if row % 2 == 1 {
+                let stripe_rect = raster::rect(left, row_top, width as f32, *row_height as f32);
+                fill(img, stripe_rect, stripe);
+            }
+            row_top += *row_height as f32;
+        }
+    }
+
+    let mut row_top = y;
+    for (row, row_height) in heights.iter().enumerate() {
+        let mut cell_left = x;
+        for (column, column_width) in widths.iter().enumerate() {
+            let (text, font, font_size, color) = match row {
+                0 => (
+                    table.headers.get(column).cloned().unwrap_or_default(),
+                    &style.header_font,
+                    style.header_font_size,
+                    &style.header_text_color,
+                ),
+                _ => (
+                    table.rows[row - 1]
+                        .get(column)
+                        .map(|cell| cell.format(style))
+                        .unwrap_or_default(),
+                    &style.font,
+                    style.font_size,
+                    &style.text_color,
+                ),
+            };
+            if !text.is_empty() {
+                // Cells with fewer lines than the tallest in their row are centred vertically.
+                let inner_width = column_width.saturating_sub(style.padding_x * 2).max(1);
+                let lines = text::wrap_text(&text, font, font_size, inner_width).len() as u32;
+                let line_height = text::measure_text(&text, font, font_size).1;
+                let offset = row_height.saturating_sub(lines * line_height) / 2;
+                text::draw_text_block(
+                    img,
+                    &text,
+                    cell_left + style.padding_x,
+                    row_top + offset,
+                    inner_width,
+                    font,
+                    font_size,
+                    color,
+                    table.alignment(column),
+                );
+            }
+            cell_left += column_width;
+        }
+        row_top += row_height;
+    }
+
+    draw_borders(img, style, (left, top), &widths, &heights);
+}
+
+// Draw the borders of a table, with its columns and rows of the given sizes, from its top-left corner.
+fn draw_borders(
+    img: &mut DynamicImage,
+    style: &TableStyle,
+    (left, top): (f32, f32),
+    widths: &[u32],
+    heights: &[u32],
+) {
+    let thickness = style.border_width;
+    if style.borders == Borders::None || thickness <= 0.0 {
+        return;
+    }
+    let width = widths.iter().sum::<u32>() as f32;
+    let height = heights.iter().sum::<u32>() as f32;
+    let mut contours = vec![];
+
+    if style.borders == Borders::Outer || style.borders == Borders::Grid {
+        contours.extend(raster::rect_outline(left, top, width, height, thickness));
+    }
+    // Lines between rows, centred on the edges of the rows, and under the header.
+    if style.borders == Borders::Horizontal || style.borders == Borders::Grid {
+        let mut row_top = top;
+        for row_height in &heights[..heights.len() - 1] {
+            row_top += *row_height as f32;
+            contours.push(raster::rect(
+                left,
+                row_top - thickness / 2.0,
+                width,
+                thickness,
+            ));
+        }
+    }
+    if style.borders == Borders::Grid {
+        let mut column_left = left;
+        for column_width in &widths[..widths.len() - 1] {
+            column_left += *column_width as f32;
+            contours.push(raster::rect(
+                column_left - thickness / 2.0,
+                top,
+                thickness,
+                height,
+            ));
+        }
+    }
+    raster::fill_contours(
+        img,
+        &contours,
+        style.border_color.into(),
+        Quality::AntiAliased,
+    );
+}
+
+/// Which borders are drawn around and within a table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Borders {
+    None,
+    /// A border around the outside of the table only.
+    Outer,
+    /// Lines between rows, and under the header.
+    Horizontal,
+    /// A border around the outside, and lines between every row and column.
+    Grid,
+}
+
+/// A cell of a table.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Text(String),
+    /// A number, formatted with the table style's `decimals`.
+    Number(f64),
+}
+
+impl Cell {
+    // Text of the cell, as drawn.
+    fn format(&self, style: &TableStyle) -> String {
+        match (self, style.decimals) {
+            (Cell::Text(text), _) => text.clone(),
+            (Cell::Number(value), Some(decimals)) => format!("{:.*}", decimals, value),
+            (Cell::Number(value), None) => format_value(*value),
+        }
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(text: &str) -> Cell {
+        Cell::Text(text.to_string())
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Cell {
+        Cell::Text(text)
+    }
+}
+
+impl From<f64> for Cell {
+    fn from(value: f64) -> Cell {
+        Cell::Number(value)
+    }
+}
+
+impl From<i32> for Cell {
+    fn from(value: i32) -> Cell {
+        Cell::Number(value as f64)
+    }
+}
+
+/// A table of data, with a row of headers and rows of cells.
+#[derive(Debug, Clone)]
+pub struct Table {
+    pub headers: Vec<String>,
+    /// Rows of cells. Rows shorter than the headers leave their last cells empty.
+    pub rows: Vec<Vec<Cell>>,
+    /// Alignment of each column's cells, including its header. Columns without an alignment
+    /// are right-aligned if all their cells are numbers, and left-aligned otherwise.
+    pub alignments: Vec<Option<TextAlign>>,
+}
+
+impl Table {
+    /// Create a new table with the given headers, and no rows.
+    pub fn new(headers: &[&str]) -> Table {
+        Table {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            rows: vec![],
+            alignments: vec![None; headers.len()],
+        }
+    }
+
+    /// Add a row of cells to the bottom of the table.
+    pub fn add_row(&mut self, cells: Vec<Cell>) {
+        self.rows.push(cells);
+    }
+
+    /// Set the alignment of a column's cells, including its header.
+    pub fn set_alignment(&mut self, column: usize, align: TextAlign) {
+        if self.alignments.len() <= column {
+            self.alignments.resize(column + 1, None);
+        }
+        self.alignments[column] = Some(align);
+    }
+
+    /// Number of columns: the most of the headers, or the cells of any row.
+    pub fn columns(&self) -> usize {
+        self.rows
+            .iter()
+            .map(|row| row.len())
+            .fold(self.headers.len(), usize::max)
+    }
+
+    /// Alignment of a column's cells, which is its set alignment, or else depends on its cells.
+    pub fn alignment(&self, column: usize) -> TextAlign {
+        if let Some(Some(align)) = self.alignments.get(column) {
+            return *align;
+        }
+        let mut cells = self
+            .rows
+            .iter()
+            .filter_map(|row| row.get(column))
+            .peekable();
+        let numeric = cells.peek().is_some() && cells.all(|cell| matches!(cell, Cell::Number(_)));
+        match numeric {
+            true => TextAlign::Right,
+            false => TextAlign::Left,
+        }
+    }
+
+    /// Width of each column in pixels, including padding: the width of its widest header or cell,
+    /// up to the style's `max_column_width`.
+    pub fn column_widths(&self, style: &TableStyle) -> Vec<u32> {
+        (0..self.columns())
+            .map(|column| {
+                let header = self.headers.get(column).map(|header| {
+                    text::measure_text(header, &style.header_font, style.header_font_size).0
+                });
+                let widest = self
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(column))
+                    .map(|cell| {
+                        text::measure_text(&cell.format(style), &style.font, style.font_size).0
+                    })
+                    .chain(header)
+                    .max()
+                    .unwrap_or(0);
+                let widest = match style.max_column_width {
+                    Some(max_width) => widest.min(max_width),
+                    None => widest,
+                };
+                widest + style.padding_x * 2
+            })
+            .collect()
+    }
+
+    /// Height of each row in pixels, including padding, starting with the header row. Each row
+    /// is as tall as its tallest cell, once cells are wrapped to fit their columns.
+    pub fn row_heights(&self, style: &TableStyle) -> Vec<u32> {
+        let widths = self.column_widths(style);
+        let height = |text: &str, font: &str, font_size: f32, width: u32| {
+            let line_height = text::measure_text("0", font, font_size).1;
+            let lines = match text.is_empty() {
+                true => 1,
+                false => text::wrap_text(text, font, font_size, width).len().max(1),
+            };
+            line_height * lines as u32 + style.padding_y * 2
+        };
+        let inner_width = |column: usize| widths[column].saturating_sub(style.padding_x * 2).max(1);
+
+        let header = self
+            .headers
+            .iter()
+            .enumerate()
+            .map(|(column, header)| {
+                height(
+                    header,
+                    &style.header_font,
+                    style.header_font_size,
+                    inner_width(column),
+                )
+            })
+            .max()
+            .unwrap_or(0);
+        let rows = self.rows.iter().map(|row| {
+            row.iter()
+                .enumerate()
+                .map(|(column, cell)| {
+                    height(
+                        &cell.format(style),
+                        &style.font,
+                        style.font_size,
+                        inner_width(column),
+                    )
+                })
+                .max()
+                .unwrap_or_else(|| height("", &style.font, style.font_size, 1))
+        });
+        std::iter::once(header).chain(rows).collect()
+    }
+
+    /// Width and height of the table in pixels, when drawn with the given style.
+    pub fn size(&self, style: &TableStyle) -> (u32, u32) {
+        (
+            self.column_widths(style).iter().sum(),
+            self.row_heights(style).iter().sum(),
+        )
+    }
+}
+
+/// Colours, fonts, borders and spacing of a table.
+#[derive(Debug, Clone)]
+pub struct TableStyle {
+    /// Font of the cells, such as "Roboto-Regular".
+    pub font: String,
+    pub font_size: f32,
+    pub text_color: Rgb,
+    /// Font of the headers, such as "Roboto-Bold".
+    pub header_font: String,
+    pub header_font_size: f32,
+    pub header_text_color: Rgb,
+    /// Background of the header row, unless it has a gradient.
+    pub header_background: Rgba,
+    /// Name of a gradient preset to fill the header row with instead, such as "pinkblue".
+    pub header_gradient: Option<String>,
+    /// Background of the table's rows.
+    pub background: Rgba,
+    /// Colour drawn over every other row, if the rows are striped.
+    pub stripe_color: Option<Rgba>,
+    pub borders: Borders,
+    pub border_color: Rgba,
+    pub border_width: f32,
+    /// Space between the left and right edges of each cell and its text, in pixels.
+    pub padding_x: u32,
+    /// Space between the top and bottom edges of each cell and its text, in pixels.
+    pub padding_y: u32,
+    /// Widest a column's text can be, in pixels, before it's wrapped onto more lines.
+    pub max_column_width: Option<u32>,
+    /// Number of decimal places numbers are shown with. Without it, numbers are shown with
+    /// no more than two, and none if they're whole.
+    pub decimals: Option<usize>,
+}
+
+impl Default for TableStyle {
+    fn default() -> TableStyle {
+        TableStyle {
+            font: "Roboto-Regular".to_string(),
+            font_size: 24.0,
+            text_color: Rgb {
+                r: 230,
+                g: 230,
+                b: 230,
+            },
+            header_font: "Roboto-Bold".to_string(),
+            header_font_size: 24.0,
+            header_text_color: Rgb {
+                r: 255,
+                g: 255,
+                b: 255,
+            },
+            header_background: Rgba::new(52, 148, 230, 255),
+            header_gradient: None,
+            background: Rgba::new(30, 34, 40, 255),
+            stripe_color: Some(Rgba::new(255, 255, 255, 14)),
+            borders: Borders::Horizontal,
+            border_color: Rgba::new(255, 255, 255, 40),
+            border_width: 1.0,
+            padding_x: 20,
+            padding_y: 12,
+            max_column_width: None,
+            decimals: None,
+        }
+    }
+}
@@ -1,12 +1,13 @@
 //! Draw text onto images.
 
+use crate::blend::{self, BlendMode};
 use crate::Rgb;
 use image::imageops::{rotate180, rotate270, rotate90};
-use image::{DynamicImage, GenericImage, GenericImageView, Rgba};
+use image::{DynamicImage, GenericImage, GenericImageView, Rgba, RgbaImage};
 use imageproc::distance_transform::Norm;
 use imageproc::drawing::draw_text_mut;
 use imageproc::morphology::dilate_mut;
-use rusttype::{Font, Scale};
+use rusttype::{point, Font, Scale};
 use std::fs;
 
 /// Draw text onto an image with a border around the text.
@@ -101,6 +102,39 @@ pub fn draw_text(
     );
 }
 
+/// Draw text onto an image, blending it with the pixels underneath using a blend mode.
+///
+/// ### Arguments
+/// * `img` - Mutable reference to a DynamicImage.
+/// * `text` - Text string to be drawn.
+/// * `x` - X-coordinate of top corner of text.
+/// * `y` - Y coordinae of top corner of text.
+/// * `font` - Font name. Fonts available include Roboto-Regular, BebasKai, Roboto-Light, among many others.
+/// * `font_size`: f32 that represents the font's size.
+/// * `rgb`: Rgb text color.
+/// * `mode`: Blend mode, such as `BlendMode::Overlay` or `BlendMode::Screen`.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_text_with_blend(
+    image: &mut DynamicImage,
+    text: &str,
+    x: u32,
+    y: u32,
+    font: &str,
+    font_size: f32,
+    rgb: &Rgb,
+    mode: BlendMode,
+) {
+    // Draw onto a transparent layer of the text's own colour, so that
+    // the anti-aliased edges of each glyph only vary in alpha.
+    let mut layer = DynamicImage::ImageRgba8(RgbaImage::from_pixel(
+        image.width(),
+        image.height(),
+        Rgba([rgb.r, rgb.g, rgb.b, 0]),
+    ));
+    draw_text(&mut layer, text, x, y, font, font_size, rgb);
+    blend::blend(image, &layer, 0, 0, mode, 1.0);
+}
+
 /// Draw vertical text onto an image.
 ///
 /// This is done by drawing the text horizontally onto an image,
@@ -179,9 +213,21 @@ pub fn draw_upsidedown_text(
     draw_rotated_text(img, text, x, y, font, font_size, "180", rgb);
 }
 
-// Draw rotated text. Available: 90, 180, 270.
-fn draw_rotated_text(
-    image: &mut DynamicImage,
+/// Draw text rotated by 90, 180 or 270 degrees, blending it with the pixels underneath using a blend mode.
+///
+/// ### Arguments
+/// * `img` - Mutable reference to a DynamicImage.
+/// * `text` - Text string to be drawn.
+/// * `x` - X-coordinate of top corner of the rotated text.
+/// * `y` - Y coordinae of top corner of the rotated text.
+/// * `font` - Font name. Fonts available include Roboto-Regular, BebasKai, Roboto-Light, among many others.
+/// * `font_size`: f32 that represents the font's size.
+/// * `rotation`: Clockwise rotation in degrees, either "90", "180" or "270". Other values are treated as "90".
+/// * `rgb`: Rgb text color.
+/// * `mode`: Blend mode, such as `BlendMode::Overlay` or `BlendMode::Screen`.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_rotated_text_with_blend(
+    img: &mut DynamicImage,
     text: &str,
     x: u32,
     y: u32,
@@ -189,14 +235,18 @@ fn draw_rotated_text(
     font_size: f32,
     rotation: &str,
     rgb: &Rgb,
+    mode: BlendMode,
 ) {
     // Since the image will be rotated, the height of the container image will be the width of the
     // text image.
-
     let font_img_height = text.len() as f32 * (font_size * 0.48);
     let font_img_width = font_size * 1.3;
-    let mut image2: DynamicImage =
-        DynamicImage::new_luma8(font_img_height as u32, font_img_width as u32);
+    // As in `draw_text_with_blend`, the layer is transparent and of the text's own colour.
+    let mut layer = RgbaImage::from_pixel(
+        font_img_height as u32,
+        font_img_width as u32,
+        Rgba([rgb.r, rgb.g, rgb.b, 0]),
+    );
 
     let font_vec = open_font(font);
 
@@ -206,32 +256,240 @@ fn draw_rotated_text(
         y: font_size,
     };
     draw_text_mut(
-        &mut image2,
-        Rgba([rgb.r as u8, rgb.g as u8, rgb.b as u8, 255u8]),
+        &mut layer,
+        Rgba([rgb.r, rgb.g, rgb.b, 255u8]),
         10,
         10,
         scale,
         &font,
-        &text,
+        text,
     );
 
-    let image2 = image2.to_rgba8();
-
-    // draw_text_mut(&mut image, Rgba([rgb.r as u8, rgb.g as u8, rgb.b as u8, 255u8]), x + 10, y - 10, scale, &font, text);
-
     let rotated_img = match rotation {
-        "90" => rotate90(&image2),
-        "180" => rotate180(&image2),
-        "270" => rotate270(&image2),
-        _ => rotate90(&image2),
+        "90" => rotate90(&layer),
+        "180" => rotate180(&layer),
+        "270" => rotate270(&layer),
+        _ => rotate90(&layer),
     };
 
-    let mut image2 = image::DynamicImage::ImageRgba8(rotated_img);
+    let layer = DynamicImage::ImageRgba8(rotated_img);
+    blend::blend(img, &layer, x as i32, y as i32, mode, 1.0);
+}
+
+/// Horizontal alignment of lines of text within a block.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Centre,
+    Right,
+}
 
-    image::imageops::overlay(image, &mut image2, x, y);
+/// Measure the size of a single line of text, returning its width and line height in pixels.
+///
+/// ### Arguments
+/// * `text` - Text string to be measured.
+/// * `font` - Font name, such as "Roboto-Regular".
+/// * `font_size`: f32 that represents the font's size.
+pub fn measure_text(text: &str, font: &str, font_size: f32) -> (u32, u32) {
+    let font_vec = open_font(font);
+    let font = Font::try_from_bytes(&font_vec).unwrap();
+    let (width, line_height) = measure_with_font(&font, text, font_size);
+    (width.ceil() as u32, line_height.ceil() as u32)
+}
+
+/// Break text into lines which each fit within a maximum width.
+/// Words are never split, so a single word wider than `max_width` is given a line of its own.
+///
+/// ### Arguments
+/// * `text` - Text string to be wrapped. Existing line breaks are kept.
+/// * `font` - Font name, such as "Roboto-Regular".
+/// * `font_size`: f32 that represents the font's size.
+/// * `max_width` - Maximum width of each line, in pixels.
+pub fn wrap_text(text: &str, font: &str, font_size: f32, max_width: u32) -> Vec<String> {
+    let font_vec = open_font(font);
+    let font = Font::try_from_bytes(&font_vec).unwrap();
+    wrap_with_font(&font, text, font_size, max_width as f32)
+}
+
+/// Draw a block of text, wrapped to fit within a maximum width and aligned within it.
+///
+/// Unlike `draw_text`, the text is drawn with its top-left corner exactly at `x` and `y`.
+/// Returns the height of the block in pixels.
+///
+/// ### Arguments
+/// * `img` - Mutable reference to a DynamicImage.
+/// * `text` - Text string to be drawn.
+/// * `x` - X-coordinate of the left edge of the block.
+/// * `y` - Y-coordinate of the top edge of the block.
+/// * `max_width` - Width of the block, which lines are wrapped to fit and aligned within.
+/// * `font` - Font name, such as "Roboto-Regular".
+/// * `font_size`: f32 that represents the font's size.
+/// * `rgb`: Rgb text color.
+/// * `align`: Alignment of each line within the block.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_text_block(
+    image: &mut DynamicImage,
+    text: &str,
+    x: u32,
+    y: u32,
+    max_width: u32,
+    font: &str,
+    font_size: f32,
+    rgb: &Rgb,
+    align: TextAlign,
+) -> u32 {
+    let font_vec = open_font(font);
+    let font = Font::try_from_bytes(&font_vec).unwrap();
+    let scale = Scale::uniform(font_size);
+    let color = Rgba([rgb.r, rgb.g, rgb.b, 255]);
+
+    let mut line_y = y as f32;
+    for line in wrap_with_font(&font, text, font_size, max_width as f32) {
+        let (line_width, line_height) = measure_with_font(&font, &line, font_size);
+        let offset = match align {
+            TextAlign::Left => 0.0,
+            TextAlign::Centre => (max_width as f32 - line_width) / 2.0,
+            TextAlign::Right => max_width as f32 - line_width,
+        };
+        let line_x = (x as f32 + offset).max(0.0).round() as u32;
+        draw_text_mut(
+            image,
+            color,
+            line_x,
+            line_y.round() as u32,
+            scale,
+            &font,
+            &line,
+        );
+        line_y += line_height;
+    }
+    (line_y - y as f32).round() as u32
+}
+
+/// Find the largest font size, up to `max_font_size`, at which text wrapped to `max_width`
+/// fits within `max_height`. Sizes are reduced in small steps, down to a minimum of 6.
+///
+/// ### Arguments
+/// * `text` - Text string to be fitted.
+/// * `font` - Font name, such as "Roboto-Regular".
+/// * `max_font_size` - Largest font size to use.
+/// * `max_width` - Width of the box, which lines are wrapped to fit.
+/// * `max_height` - Height of the box.
+pub fn fit_font_size(
+    text: &str,
+    font: &str,
+    max_font_size: f32,
+    max_width: u32,
+    max_height: u32,
+) -> f32 {
+    let font_vec = open_font(font);
+    let font = Font::try_from_bytes(&font_vec).unwrap();
+    fit_with_font(
+        &font,
+        text,
+        max_font_size,
+        max_width as f32,
+        max_height as f32,
+    )
+    .0
+}
+
+// Find the largest font size, up to `max_size`, at which the wrapped text fits within the box.
+// Returns the font size and the height of the wrapped text.
+pub(crate) fn fit_with_font(
+    font: &Font,
+    text: &str,
+    max_size: f32,
+    width: f32,
+    height: f32,
+) -> (f32, f32) {
+    let mut font_size = max_size.max(1.0);
+    loop {
+        let lines = wrap_with_font(font, text, font_size, width);
+        let line_height = {
+            let v_metrics = font.v_metrics(Scale::uniform(font_size));
+            v_metrics.ascent - v_metrics.descent + v_metrics.line_gap
+        };
+        let widest = lines
+            .iter()
+            .map(|line| measure_with_font(font, line, font_size).0)
+            .fold(0.0, f32::max);
+        let block_height = line_height * lines.len() as f32;
+
+        if (block_height <= height && widest <= width) || font_size <= 6.0 {
+            return (font_size, block_height);
+        }
+        font_size *= 0.92;
+    }
+}
+
+// Width and line height of a line of text, in pixels.
+pub(crate) fn measure_with_font(font: &Font, text: &str, font_size: f32) -> (f32, f32) {
+    let scale = Scale::uniform(font_size);
+    let v_metrics = font.v_metrics(scale);
+    let line_height = v_metrics.ascent - v_metrics.descent + v_metrics.line_gap;
+    let width = font
+        .layout(text, scale, point(0.0, 0.0))
+        .last()
+        .map(|glyph| glyph.position().x + glyph.unpositioned().h_metrics().advance_width)
+        .unwrap_or(0.0);
+    (width, line_height)
+}
+
+// Greedily fill each line with as many words as will fit.
+pub(crate) fn wrap_with_font(
+    font: &Font,
+    text: &str,
+    font_size: f32,
+    max_width: f32,
+) -> Vec<String> {
+    let mut lines = vec![];
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", line, word)
+            };
+            if line.is_empty() || measure_with_font(font, &candidate, font_size).0 <= max_width {
+                line = candidate;
+            } else {
+                lines.push(line);
+                line = word.to_string();
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+// Draw rotated text. Available: 90, 180, 270.
+fn draw_rotated_text(
+    image: &mut DynamicImage,
+    text: &str,
+    x: u32,
+    y: u32,
+    font: &str,
+    font_size: f32,
+    rotation: &str,
+    rgb: &Rgb,
+) {
+    draw_rotated_text_with_blend(
+        image,
+        text,
+        x,
+        y,
+        font,
+        font_size,
+        rotation,
+        rgb,
+        BlendMode::Normal,
+    );
 }
 
-fn open_font(font: &str) -> std::vec::Vec<u8> {
+pub(crate) fn open_font(font: &str) -> std::vec::Vec<u8> {
     let font_path = format!("fonts/{}.ttf", font);
     let font_vec1 = fs::read(font_path).expect("Unable to read file");
 
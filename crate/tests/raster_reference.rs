@@ -0,0 +1,90 @@
+//! Compare rasterized shapes against reference images.
+//!
+//! The references are in `tests/reference`. After an intended change to rasterization, check the
+//! new output by eye and regenerate them with `GDL_BLESS=1 cargo test --test raster_reference`.
+
+extern crate gdl;
+use gdl::raster::{self, Contour, Quality};
+use gdl::{new_with_background, Rgb};
+use image::{DynamicImage, GenericImageView};
+use std::path::PathBuf;
+
+// Largest difference allowed in any channel, to allow for differences in floating point rounding.
+const TOLERANCE: i32 = 1;
+
+// Draw white contours onto a black square image.
+fn render(contours: &[Contour], quality: Quality) -> DynamicImage {
+    let black = Rgb { r: 0, g: 0, b: 0 };
+    let mut img = new_with_background(32, 32, &black);
+    let white = image::Rgba([255, 255, 255, 255]);
+    raster::fill_contours(&mut img, contours, white, quality);
+    img
+}
+
+// Compare an image with its reference, or save it as the reference if GDL_BLESS is set.
+fn assert_matches_reference(name: &str, img: &DynamicImage) {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "reference", name]
+        .iter()
+        .collect();
+    if std::env::var_os("GDL_BLESS").is_some() {
+        img.save(&path).unwrap();
+        return;
+    }
+    let reference = image::open(&path)
+        .unwrap_or_else(|err| panic!("couldn't open {}: {}", path.display(), err));
+    assert_eq!(
+        img.dimensions(),
+        reference.dimensions(),
+        "{} has changed size",
+        name
+    );
+    for (x, y, pixel) in img.pixels() {
+        let expected = reference.get_pixel(x, y);
+        let differs = pixel
+            .0
+            .iter()
+            .zip(expected.0.iter())
+            .any(|(a, b)| (*a as i32 - *b as i32).abs() > TOLERANCE);
+        assert!(
+            !differs,
+            "{} differs at ({}, {}): {:?}, expected {:?}",
+            name, x, y, pixel.0, expected.0
+        );
+    }
+}
+
+fn assert_shape_matches(name: &str, contours: &[Contour]) {
+    let smooth = format!("{}_antialiased.png", name);
+    assert_matches_reference(&smooth, &render(contours, Quality::AntiAliased));
+    assert_matches_reference(
+        &format!("{}_fast.png", name),
+        &render(contours, Quality::Fast),
+    );
+}
+
+#[test]
+fn half_pixel_offset_rect() {
+    assert_shape_matches("rect", &[raster::rect(4.5, 6.5, 20.0, 15.0)]);
+}
+
+#[test]
+fn diagonal_triangle() {
+    let triangle = raster::polygon(&[(3.0, 3.0), (29.0, 3.0), (3.0, 29.0)]);
+    assert_shape_matches("triangle", &[triangle]);
+}
+
+#[test]
+fn circle() {
+    assert_shape_matches("circle", &[raster::circle(15.7, 16.2, 11.3)]);
+}
+
+#[test]
+fn outline() {
+    assert_shape_matches("outline", &raster::rect_outline(3.5, 3.5, 25.0, 25.0, 4.0));
+}
+
+#[test]
+fn polyline() {
+    let points = [(3.0, 26.0), (10.5, 6.5), (17.0, 24.0), (28.5, 5.0)];
+    assert_shape_matches("polyline", &raster::polyline(&points, 3.5));
+}